@@ -92,6 +92,7 @@ impl Parametric<SoyBoyParameter> for PluginConfigV02 {
             SoyBoyParameter::OscNsInterval => self.osc_noise_interval = value,
             SoyBoyParameter::DacFreq => self.dac_freq = value,
             SoyBoyParameter::DacQ => self.dac_q = value,
+            _ => (),
         }
     }
 
@@ -116,6 +117,7 @@ impl Parametric<SoyBoyParameter> for PluginConfigV02 {
             SoyBoyParameter::OscNsInterval => self.osc_noise_interval,
             SoyBoyParameter::DacFreq => self.dac_freq,
             SoyBoyParameter::DacQ => self.dac_q,
+            _ => 0.0,
         }
     }
 }
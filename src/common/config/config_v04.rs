@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{constants, i4};
+use crate::soyboy::parameters::{ParameterDef, Parametric, SoyBoyParameter};
+
+use super::PluginConfigV03;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PluginConfigV04 {
+    pub waveform_view_enabled: bool,
+    // soyboy parameters
+    pub master_volume: f64,
+    pub pitch_bend: f64,
+    pub detune: f64,
+    pub oscillator_type: f64,
+    pub num_voices: f64,
+    pub sweep_type: f64,
+    pub sweep_amount: f64,
+    pub sweep_period: f64,
+    pub stutter_time: f64,
+    pub stutter_depth: f64,
+    pub stutter_when: f64,
+    pub envelope_attack: f64,
+    pub envelope_decay: f64,
+    pub envelope_sustain: f64,
+    pub envelope_release: f64,
+    pub osc_sq_duty: f64,
+    pub osc_noise_interval: f64,
+    pub dac_freq: f64,
+    pub dac_q: f64,
+    pub sweep_sync: f64,
+    pub sweep_division: f64,
+    pub stutter_sync: f64,
+    pub stutter_division: f64,
+    pub stutter_count: f64,
+    pub stutter_pitch: f64,
+    pub stutter_ping_pong: f64,
+    pub stutter_probability: f64,
+    pub delay_mix: f64,
+    pub delay_time: f64,
+    pub delay_sync: f64,
+    pub delay_division: f64,
+    pub delay_feedback: f64,
+    pub delay_ping_pong: f64,
+    pub delay_quantize: f64,
+    pub crush_bits: f64,
+    pub crush_rate: f64,
+    pub crush_anti_alias: f64,
+    pub master_clip: f64,
+    pub filter_env_amount: f64,
+    pub filter_env_attack: f64,
+    pub filter_env_decay: f64,
+    pub filter_key_tracking: f64,
+    pub filter_velocity: f64,
+    pub filter_type: f64,
+    pub envelope_mode: f64,
+    pub envelope_hw_volume: f64,
+    pub envelope_hw_direction: f64,
+    pub envelope_hw_period: f64,
+    pub envelope_attack_curve: f64,
+    pub envelope_attack_tension: f64,
+    pub envelope_decay_curve: f64,
+    pub envelope_decay_tension: f64,
+    pub envelope_release_curve: f64,
+    pub envelope_release_tension: f64,
+    pub envelope_hold: f64,
+    pub envelope_loop: f64,
+    pub velocity_amount: f64,
+    pub velocity_curve: f64,
+    pub velocity_to_attack: f64,
+    pub velocity_to_stutter: f64,
+    pub wavetable: [i4; constants::WAVETABLE_SIZE],
+}
+
+impl PluginConfigV04 {
+    /// This version is for versioning configuration data.
+    /// So this is not equal to Cargo.toml's one.
+    pub const CONFIG_VERSION: u32 = 4;
+
+    pub fn set_wavetable_sample(&mut self, idx: usize, v: i4) {
+        self.wavetable[idx] = v;
+    }
+
+    pub fn set_wavetable(&mut self, wavetable: &[i4; constants::WAVETABLE_SIZE]) {
+        self.wavetable = wavetable.clone();
+    }
+
+    pub fn from_v03(
+        v03: PluginConfigV03,
+        param_defs: &HashMap<SoyBoyParameter, ParameterDef>,
+    ) -> Self {
+        let mut v04 = Self::default();
+
+        for param in SoyBoyParameter::iter() {
+            let param_def = param_defs.get(&param).unwrap();
+            let v = match param {
+                SoyBoyParameter::EgHold
+                | SoyBoyParameter::EgLoop
+                | SoyBoyParameter::VelocityAmount
+                | SoyBoyParameter::VelocityCurve
+                | SoyBoyParameter::VelocityToAttack
+                | SoyBoyParameter::VelocityToStutter => param_def.default_value,
+                _ => v03.get_param(&param),
+            };
+
+            v04.set_param(&param, param_def, v);
+        }
+        v04.set_wavetable(&v03.wavetable);
+
+        v04
+    }
+}
+
+impl Parametric<SoyBoyParameter> for PluginConfigV04 {
+    fn set_param(&mut self, param: &SoyBoyParameter, param_def: &ParameterDef, value: f64) {
+        let value = param_def.clamp(value);
+
+        match param {
+            SoyBoyParameter::MasterVolume => self.master_volume = value,
+            SoyBoyParameter::PitchBend => self.pitch_bend = value,
+            SoyBoyParameter::Detune => self.detune = value,
+            SoyBoyParameter::OscillatorType => self.oscillator_type = value,
+            SoyBoyParameter::NumVoices => self.num_voices = value,
+            SoyBoyParameter::SweepType => self.sweep_type = value,
+            SoyBoyParameter::SweepAmount => self.sweep_amount = value,
+            SoyBoyParameter::SweepPeriod => self.sweep_period = value,
+            SoyBoyParameter::StutterTime => self.stutter_time = value,
+            SoyBoyParameter::StutterDepth => self.stutter_depth = value,
+            SoyBoyParameter::StutterWhen => self.stutter_when = value,
+            SoyBoyParameter::EgAttack => self.envelope_attack = value,
+            SoyBoyParameter::EgDecay => self.envelope_decay = value,
+            SoyBoyParameter::EgSustain => self.envelope_sustain = value,
+            SoyBoyParameter::EgRelease => self.envelope_release = value,
+            SoyBoyParameter::OscSqDuty => self.osc_sq_duty = value,
+            SoyBoyParameter::OscNsInterval => self.osc_noise_interval = value,
+            SoyBoyParameter::DacFreq => self.dac_freq = value,
+            SoyBoyParameter::DacQ => self.dac_q = value,
+            SoyBoyParameter::SweepSync => self.sweep_sync = value,
+            SoyBoyParameter::SweepDivision => self.sweep_division = value,
+            SoyBoyParameter::StutterSync => self.stutter_sync = value,
+            SoyBoyParameter::StutterDivision => self.stutter_division = value,
+            SoyBoyParameter::StutterCount => self.stutter_count = value,
+            SoyBoyParameter::StutterPitch => self.stutter_pitch = value,
+            SoyBoyParameter::StutterPingPong => self.stutter_ping_pong = value,
+            SoyBoyParameter::StutterProbability => self.stutter_probability = value,
+            SoyBoyParameter::DelayMix => self.delay_mix = value,
+            SoyBoyParameter::DelayTime => self.delay_time = value,
+            SoyBoyParameter::DelaySync => self.delay_sync = value,
+            SoyBoyParameter::DelayDivision => self.delay_division = value,
+            SoyBoyParameter::DelayFeedback => self.delay_feedback = value,
+            SoyBoyParameter::DelayPingPong => self.delay_ping_pong = value,
+            SoyBoyParameter::DelayQuantize => self.delay_quantize = value,
+            SoyBoyParameter::CrushBits => self.crush_bits = value,
+            SoyBoyParameter::CrushRate => self.crush_rate = value,
+            SoyBoyParameter::CrushAntiAlias => self.crush_anti_alias = value,
+            SoyBoyParameter::MasterClip => self.master_clip = value,
+            SoyBoyParameter::FilterEnvAmount => self.filter_env_amount = value,
+            SoyBoyParameter::FilterEnvAttack => self.filter_env_attack = value,
+            SoyBoyParameter::FilterEnvDecay => self.filter_env_decay = value,
+            SoyBoyParameter::FilterKeyTracking => self.filter_key_tracking = value,
+            SoyBoyParameter::FilterVelocity => self.filter_velocity = value,
+            SoyBoyParameter::FilterType => self.filter_type = value,
+            SoyBoyParameter::EgMode => self.envelope_mode = value,
+            SoyBoyParameter::EgHwVolume => self.envelope_hw_volume = value,
+            SoyBoyParameter::EgHwDirection => self.envelope_hw_direction = value,
+            SoyBoyParameter::EgHwPeriod => self.envelope_hw_period = value,
+            SoyBoyParameter::EgAttackCurve => self.envelope_attack_curve = value,
+            SoyBoyParameter::EgAttackTension => self.envelope_attack_tension = value,
+            SoyBoyParameter::EgDecayCurve => self.envelope_decay_curve = value,
+            SoyBoyParameter::EgDecayTension => self.envelope_decay_tension = value,
+            SoyBoyParameter::EgReleaseCurve => self.envelope_release_curve = value,
+            SoyBoyParameter::EgReleaseTension => self.envelope_release_tension = value,
+            SoyBoyParameter::EgHold => self.envelope_hold = value,
+            SoyBoyParameter::EgLoop => self.envelope_loop = value,
+            SoyBoyParameter::VelocityAmount => self.velocity_amount = value,
+            SoyBoyParameter::VelocityCurve => self.velocity_curve = value,
+            SoyBoyParameter::VelocityToAttack => self.velocity_to_attack = value,
+            SoyBoyParameter::VelocityToStutter => self.velocity_to_stutter = value,
+            _ => (),
+        }
+    }
+
+    fn get_param(&self, param: &SoyBoyParameter) -> f64 {
+        match param {
+            SoyBoyParameter::MasterVolume => self.master_volume,
+            SoyBoyParameter::PitchBend => self.pitch_bend,
+            SoyBoyParameter::Detune => self.detune,
+            SoyBoyParameter::OscillatorType => self.oscillator_type,
+            SoyBoyParameter::NumVoices => self.num_voices,
+            SoyBoyParameter::SweepType => self.sweep_type,
+            SoyBoyParameter::SweepAmount => self.sweep_amount,
+            SoyBoyParameter::SweepPeriod => self.sweep_period,
+            SoyBoyParameter::StutterTime => self.stutter_time,
+            SoyBoyParameter::StutterDepth => self.stutter_depth,
+            SoyBoyParameter::StutterWhen => self.stutter_when,
+            SoyBoyParameter::EgAttack => self.envelope_attack,
+            SoyBoyParameter::EgDecay => self.envelope_decay,
+            SoyBoyParameter::EgSustain => self.envelope_sustain,
+            SoyBoyParameter::EgRelease => self.envelope_release,
+            SoyBoyParameter::OscSqDuty => self.osc_sq_duty,
+            SoyBoyParameter::OscNsInterval => self.osc_noise_interval,
+            SoyBoyParameter::DacFreq => self.dac_freq,
+            SoyBoyParameter::DacQ => self.dac_q,
+            SoyBoyParameter::SweepSync => self.sweep_sync,
+            SoyBoyParameter::SweepDivision => self.sweep_division,
+            SoyBoyParameter::StutterSync => self.stutter_sync,
+            SoyBoyParameter::StutterDivision => self.stutter_division,
+            SoyBoyParameter::StutterCount => self.stutter_count,
+            SoyBoyParameter::StutterPitch => self.stutter_pitch,
+            SoyBoyParameter::StutterPingPong => self.stutter_ping_pong,
+            SoyBoyParameter::StutterProbability => self.stutter_probability,
+            SoyBoyParameter::DelayMix => self.delay_mix,
+            SoyBoyParameter::DelayTime => self.delay_time,
+            SoyBoyParameter::DelaySync => self.delay_sync,
+            SoyBoyParameter::DelayDivision => self.delay_division,
+            SoyBoyParameter::DelayFeedback => self.delay_feedback,
+            SoyBoyParameter::DelayPingPong => self.delay_ping_pong,
+            SoyBoyParameter::DelayQuantize => self.delay_quantize,
+            SoyBoyParameter::CrushBits => self.crush_bits,
+            SoyBoyParameter::CrushRate => self.crush_rate,
+            SoyBoyParameter::CrushAntiAlias => self.crush_anti_alias,
+            SoyBoyParameter::MasterClip => self.master_clip,
+            SoyBoyParameter::FilterEnvAmount => self.filter_env_amount,
+            SoyBoyParameter::FilterEnvAttack => self.filter_env_attack,
+            SoyBoyParameter::FilterEnvDecay => self.filter_env_decay,
+            SoyBoyParameter::FilterKeyTracking => self.filter_key_tracking,
+            SoyBoyParameter::FilterVelocity => self.filter_velocity,
+            SoyBoyParameter::FilterType => self.filter_type,
+            SoyBoyParameter::EgMode => self.envelope_mode,
+            SoyBoyParameter::EgHwVolume => self.envelope_hw_volume,
+            SoyBoyParameter::EgHwDirection => self.envelope_hw_direction,
+            SoyBoyParameter::EgHwPeriod => self.envelope_hw_period,
+            SoyBoyParameter::EgAttackCurve => self.envelope_attack_curve,
+            SoyBoyParameter::EgAttackTension => self.envelope_attack_tension,
+            SoyBoyParameter::EgDecayCurve => self.envelope_decay_curve,
+            SoyBoyParameter::EgDecayTension => self.envelope_decay_tension,
+            SoyBoyParameter::EgReleaseCurve => self.envelope_release_curve,
+            SoyBoyParameter::EgReleaseTension => self.envelope_release_tension,
+            SoyBoyParameter::EgHold => self.envelope_hold,
+            SoyBoyParameter::EgLoop => self.envelope_loop,
+            SoyBoyParameter::VelocityAmount => self.velocity_amount,
+            SoyBoyParameter::VelocityCurve => self.velocity_curve,
+            SoyBoyParameter::VelocityToAttack => self.velocity_to_attack,
+            SoyBoyParameter::VelocityToStutter => self.velocity_to_stutter,
+            _ => 0.0,
+        }
+    }
+}
+
+impl Default for PluginConfigV04 {
+    fn default() -> Self {
+        Self {
+            waveform_view_enabled: false,
+            master_volume: 0.0,
+            pitch_bend: 0.0,
+            detune: 0.0,
+            oscillator_type: 0.0,
+            num_voices: 0.0,
+            sweep_type: 0.0,
+            sweep_amount: 0.0,
+            sweep_period: 0.0,
+            stutter_time: 0.0,
+            stutter_depth: 0.0,
+            stutter_when: 0.0,
+            envelope_attack: 0.0,
+            envelope_decay: 0.0,
+            envelope_sustain: 0.0,
+            envelope_release: 0.0,
+            osc_sq_duty: 0.0,
+            osc_noise_interval: 0.0,
+            dac_freq: 0.0,
+            dac_q: 0.0,
+            sweep_sync: 0.0,
+            sweep_division: 0.0,
+            stutter_sync: 0.0,
+            stutter_division: 0.0,
+            stutter_count: 0.0,
+            stutter_pitch: 0.0,
+            stutter_ping_pong: 0.0,
+            stutter_probability: 0.0,
+            delay_mix: 0.0,
+            delay_time: 0.0,
+            delay_sync: 0.0,
+            delay_division: 0.0,
+            delay_feedback: 0.0,
+            delay_ping_pong: 0.0,
+            delay_quantize: 0.0,
+            crush_bits: 0.0,
+            crush_rate: 0.0,
+            crush_anti_alias: 0.0,
+            master_clip: 0.0,
+            filter_env_amount: 0.0,
+            filter_env_attack: 0.0,
+            filter_env_decay: 0.0,
+            filter_key_tracking: 0.0,
+            filter_velocity: 0.0,
+            filter_type: 0.0,
+            envelope_mode: 0.0,
+            envelope_hw_volume: 0.0,
+            envelope_hw_direction: 0.0,
+            envelope_hw_period: 0.0,
+            envelope_attack_curve: 0.0,
+            envelope_attack_tension: 0.0,
+            envelope_decay_curve: 0.0,
+            envelope_decay_tension: 0.0,
+            envelope_release_curve: 0.0,
+            envelope_release_tension: 0.0,
+            envelope_hold: 0.0,
+            envelope_loop: 0.0,
+            velocity_amount: 0.0,
+            velocity_curve: 0.0,
+            velocity_to_attack: 0.0,
+            velocity_to_stutter: 0.0,
+            wavetable: [i4::from(0i8); constants::WAVETABLE_SIZE],
+        }
+    }
+}
+
+impl PartialEq for PluginConfigV04 {
+    fn eq(&self, _other: &Self) -> bool {
+        false
+    }
+
+    fn ne(&self, _other: &Self) -> bool {
+        true
+    }
+}
+impl Eq for PluginConfigV04 {}
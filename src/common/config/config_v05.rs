@@ -0,0 +1,881 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{constants, i4};
+use crate::soyboy::parameters::{ParameterDef, Parametric, SoyBoyParameter};
+
+use super::PluginConfigV04;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PluginConfigV05 {
+    pub waveform_view_enabled: bool,
+    // soyboy parameters
+    pub master_volume: f64,
+    pub pitch_bend: f64,
+    pub detune: f64,
+    pub oscillator_type: f64,
+    pub num_voices: f64,
+    pub sweep_type: f64,
+    pub sweep_amount: f64,
+    pub sweep_period: f64,
+    pub stutter_time: f64,
+    pub stutter_depth: f64,
+    pub stutter_when: f64,
+    pub envelope_attack: f64,
+    pub envelope_decay: f64,
+    pub envelope_sustain: f64,
+    pub envelope_release: f64,
+    pub osc_sq_duty: f64,
+    pub osc_noise_interval: f64,
+    pub dac_freq: f64,
+    pub dac_q: f64,
+    pub sweep_sync: f64,
+    pub sweep_division: f64,
+    pub stutter_sync: f64,
+    pub stutter_division: f64,
+    pub stutter_count: f64,
+    pub stutter_pitch: f64,
+    pub stutter_ping_pong: f64,
+    pub stutter_probability: f64,
+    pub delay_mix: f64,
+    pub delay_time: f64,
+    pub delay_sync: f64,
+    pub delay_division: f64,
+    pub delay_feedback: f64,
+    pub delay_ping_pong: f64,
+    pub delay_quantize: f64,
+    pub crush_bits: f64,
+    pub crush_rate: f64,
+    pub crush_anti_alias: f64,
+    pub master_clip: f64,
+    pub filter_env_amount: f64,
+    pub filter_env_attack: f64,
+    pub filter_env_decay: f64,
+    pub filter_key_tracking: f64,
+    pub filter_velocity: f64,
+    pub filter_type: f64,
+    pub envelope_mode: f64,
+    pub envelope_hw_volume: f64,
+    pub envelope_hw_direction: f64,
+    pub envelope_hw_period: f64,
+    pub envelope_attack_curve: f64,
+    pub envelope_attack_tension: f64,
+    pub envelope_decay_curve: f64,
+    pub envelope_decay_tension: f64,
+    pub envelope_release_curve: f64,
+    pub envelope_release_tension: f64,
+    pub envelope_hold: f64,
+    pub envelope_loop: f64,
+    pub velocity_amount: f64,
+    pub velocity_curve: f64,
+    pub velocity_to_attack: f64,
+    pub velocity_to_stutter: f64,
+    pub lfo1_rate: f64,
+    pub lfo1_shape: f64,
+    pub lfo2_rate: f64,
+    pub lfo2_shape: f64,
+    pub mod_env_attack: f64,
+    pub mod_env_decay: f64,
+    pub mod_wheel: f64,
+    pub aftertouch: f64,
+    pub mod_slot1_source: f64,
+    pub mod_slot1_destination: f64,
+    pub mod_slot1_amount: f64,
+    pub mod_slot2_source: f64,
+    pub mod_slot2_destination: f64,
+    pub mod_slot2_amount: f64,
+    pub mod_slot3_source: f64,
+    pub mod_slot3_destination: f64,
+    pub mod_slot3_amount: f64,
+    pub mod_slot4_source: f64,
+    pub mod_slot4_destination: f64,
+    pub mod_slot4_amount: f64,
+    pub mod_slot5_source: f64,
+    pub mod_slot5_destination: f64,
+    pub mod_slot5_amount: f64,
+    pub mod_slot6_source: f64,
+    pub mod_slot6_destination: f64,
+    pub mod_slot6_amount: f64,
+    pub mod_slot7_source: f64,
+    pub mod_slot7_destination: f64,
+    pub mod_slot7_amount: f64,
+    pub mod_slot8_source: f64,
+    pub mod_slot8_destination: f64,
+    pub mod_slot8_amount: f64,
+    pub kit_mode: f64,
+    pub kit_pad: f64,
+    pub kit_note_low: f64,
+    pub kit_note_high: f64,
+    pub kit_choke_group: f64,
+    pub kit_pitch_mode: f64,
+    pub kit_fixed_note: f64,
+    pub part_mode: f64,
+    pub part_edit: f64,
+    pub part_voices: f64,
+    pub part_level: f64,
+    pub part_output: f64,
+    pub split_mode: f64,
+    pub zone1_key_low: f64,
+    pub zone1_key_high: f64,
+    pub zone1_velocity_low: f64,
+    pub zone1_velocity_high: f64,
+    pub zone1_oscillator: f64,
+    pub zone1_transpose: f64,
+    pub zone1_duty: f64,
+    pub zone1_wavetable: f64,
+    pub zone2_key_low: f64,
+    pub zone2_key_high: f64,
+    pub zone2_velocity_low: f64,
+    pub zone2_velocity_high: f64,
+    pub zone2_oscillator: f64,
+    pub zone2_transpose: f64,
+    pub zone2_duty: f64,
+    pub zone2_wavetable: f64,
+    pub zone3_key_low: f64,
+    pub zone3_key_high: f64,
+    pub zone3_velocity_low: f64,
+    pub zone3_velocity_high: f64,
+    pub zone3_oscillator: f64,
+    pub zone3_transpose: f64,
+    pub zone3_duty: f64,
+    pub zone3_wavetable: f64,
+    pub zone4_key_low: f64,
+    pub zone4_key_high: f64,
+    pub zone4_velocity_low: f64,
+    pub zone4_velocity_high: f64,
+    pub zone4_oscillator: f64,
+    pub zone4_transpose: f64,
+    pub zone4_duty: f64,
+    pub zone4_wavetable: f64,
+    pub sustain: f64,
+    pub sostenuto: f64,
+    pub breath: f64,
+    pub mod_wheel_destination: f64,
+    pub mod_wheel_amount: f64,
+    pub aftertouch_destination: f64,
+    pub aftertouch_amount: f64,
+    pub breath_destination: f64,
+    pub breath_amount: f64,
+    pub bend_up: f64,
+    pub bend_down: f64,
+    pub bend_quantize: f64,
+    pub mpe_zone: f64,
+    pub mpe_channels: f64,
+    pub mpe_bend_range: f64,
+    pub timbre_destination: f64,
+    pub timbre_amount: f64,
+    pub poly_pressure_destination: f64,
+    pub poly_pressure_amount: f64,
+    pub smoothing_time: f64,
+    pub master_headroom: f64,
+    pub wavetable: [i4; constants::WAVETABLE_SIZE],
+    // drum kit pads, indexed by parameter IDs
+    pub kit: Vec<Vec<f64>>,
+    pub kit_wavetables: Vec<[i4; constants::WAVETABLE_SIZE]>,
+    // multitimbral parts, indexed by parameter IDs
+    pub parts: Vec<Vec<f64>>,
+    pub part_wavetables: Vec<[i4; constants::WAVETABLE_SIZE]>,
+    // MIDI CCs learned for parameters as (CC number, parameter ID)
+    pub midi_mappings: Vec<(i16, u32)>,
+}
+
+impl PluginConfigV05 {
+    /// This version is for versioning configuration data.
+    /// So this is not equal to Cargo.toml's one.
+    pub const CONFIG_VERSION: u32 = 5;
+
+    pub fn set_wavetable_sample(&mut self, idx: usize, v: i4) {
+        self.wavetable[idx] = v;
+    }
+
+    pub fn set_wavetable(&mut self, wavetable: &[i4; constants::WAVETABLE_SIZE]) {
+        self.wavetable = wavetable.clone();
+    }
+
+    pub fn set_kit(
+        &mut self,
+        kit: Vec<Vec<f64>>,
+        wavetables: Vec<[i4; constants::WAVETABLE_SIZE]>,
+    ) {
+        self.kit = kit;
+        self.kit_wavetables = wavetables;
+    }
+
+    pub fn set_parts(
+        &mut self,
+        parts: Vec<Vec<f64>>,
+        wavetables: Vec<[i4; constants::WAVETABLE_SIZE]>,
+    ) {
+        self.parts = parts;
+        self.part_wavetables = wavetables;
+    }
+
+    /// Returns the parameter ID which the MIDI CC is learned for.
+    pub fn midi_mapping(&self, cc: i16) -> Option<u32> {
+        self.midi_mappings
+            .iter()
+            .find(|(c, _)| *c == cc)
+            .map(|(_, param)| *param)
+    }
+
+    /// Binds the MIDI CC to the parameter. The CC and the parameter lose their former bindings.
+    pub fn learn_midi_cc(&mut self, cc: i16, param: SoyBoyParameter) {
+        self.forget_midi_cc(param);
+        self.midi_mappings.retain(|(c, _)| *c != cc);
+        self.midi_mappings.push((cc, param as u32));
+    }
+
+    pub fn forget_midi_cc(&mut self, param: SoyBoyParameter) {
+        self.midi_mappings.retain(|(_, p)| *p != param as u32);
+    }
+
+    /// Puts the pedals and the controllers back at rest. They are saved with the state but
+    /// should not stay held or moved in the loaded one.
+    pub fn reset_controllers(&mut self, param_defs: &HashMap<SoyBoyParameter, ParameterDef>) {
+        for param in [
+            SoyBoyParameter::PitchBend,
+            SoyBoyParameter::Sustain,
+            SoyBoyParameter::Sostenuto,
+            SoyBoyParameter::ModWheel,
+            SoyBoyParameter::Aftertouch,
+            SoyBoyParameter::Breath,
+        ] {
+            let param_def = param_defs.get(&param).unwrap();
+            self.set_param(&param, param_def, param_def.default_value);
+        }
+    }
+
+    pub fn from_v04(
+        v04: PluginConfigV04,
+        param_defs: &HashMap<SoyBoyParameter, ParameterDef>,
+    ) -> Self {
+        let mut v05 = Self::default();
+
+        for param in SoyBoyParameter::iter() {
+            let param_def = param_defs.get(&param).unwrap();
+            let v = match param {
+                SoyBoyParameter::Lfo1Rate
+                | SoyBoyParameter::Lfo1Shape
+                | SoyBoyParameter::Lfo2Rate
+                | SoyBoyParameter::Lfo2Shape
+                | SoyBoyParameter::ModEnvAttack
+                | SoyBoyParameter::ModEnvDecay
+                | SoyBoyParameter::ModWheel
+                | SoyBoyParameter::Aftertouch
+                | SoyBoyParameter::ModSlot1Source
+                | SoyBoyParameter::ModSlot1Destination
+                | SoyBoyParameter::ModSlot1Amount
+                | SoyBoyParameter::ModSlot2Source
+                | SoyBoyParameter::ModSlot2Destination
+                | SoyBoyParameter::ModSlot2Amount
+                | SoyBoyParameter::ModSlot3Source
+                | SoyBoyParameter::ModSlot3Destination
+                | SoyBoyParameter::ModSlot3Amount
+                | SoyBoyParameter::ModSlot4Source
+                | SoyBoyParameter::ModSlot4Destination
+                | SoyBoyParameter::ModSlot4Amount
+                | SoyBoyParameter::ModSlot5Source
+                | SoyBoyParameter::ModSlot5Destination
+                | SoyBoyParameter::ModSlot5Amount
+                | SoyBoyParameter::ModSlot6Source
+                | SoyBoyParameter::ModSlot6Destination
+                | SoyBoyParameter::ModSlot6Amount
+                | SoyBoyParameter::ModSlot7Source
+                | SoyBoyParameter::ModSlot7Destination
+                | SoyBoyParameter::ModSlot7Amount
+                | SoyBoyParameter::ModSlot8Source
+                | SoyBoyParameter::ModSlot8Destination
+                | SoyBoyParameter::ModSlot8Amount
+                | SoyBoyParameter::KitMode
+                | SoyBoyParameter::KitPad
+                | SoyBoyParameter::KitNoteLow
+                | SoyBoyParameter::KitNoteHigh
+                | SoyBoyParameter::KitChokeGroup
+                | SoyBoyParameter::KitPitchMode
+                | SoyBoyParameter::KitFixedNote
+                | SoyBoyParameter::PartMode
+                | SoyBoyParameter::PartEdit
+                | SoyBoyParameter::PartVoices
+                | SoyBoyParameter::PartLevel
+                | SoyBoyParameter::PartOutput
+                | SoyBoyParameter::SplitMode
+                | SoyBoyParameter::Zone1KeyLow
+                | SoyBoyParameter::Zone1KeyHigh
+                | SoyBoyParameter::Zone1VelocityLow
+                | SoyBoyParameter::Zone1VelocityHigh
+                | SoyBoyParameter::Zone1Oscillator
+                | SoyBoyParameter::Zone1Transpose
+                | SoyBoyParameter::Zone1Duty
+                | SoyBoyParameter::Zone1Wavetable
+                | SoyBoyParameter::Zone2KeyLow
+                | SoyBoyParameter::Zone2KeyHigh
+                | SoyBoyParameter::Zone2VelocityLow
+                | SoyBoyParameter::Zone2VelocityHigh
+                | SoyBoyParameter::Zone2Oscillator
+                | SoyBoyParameter::Zone2Transpose
+                | SoyBoyParameter::Zone2Duty
+                | SoyBoyParameter::Zone2Wavetable
+                | SoyBoyParameter::Zone3KeyLow
+                | SoyBoyParameter::Zone3KeyHigh
+                | SoyBoyParameter::Zone3VelocityLow
+                | SoyBoyParameter::Zone3VelocityHigh
+                | SoyBoyParameter::Zone3Oscillator
+                | SoyBoyParameter::Zone3Transpose
+                | SoyBoyParameter::Zone3Duty
+                | SoyBoyParameter::Zone3Wavetable
+                | SoyBoyParameter::Zone4KeyLow
+                | SoyBoyParameter::Zone4KeyHigh
+                | SoyBoyParameter::Zone4VelocityLow
+                | SoyBoyParameter::Zone4VelocityHigh
+                | SoyBoyParameter::Zone4Oscillator
+                | SoyBoyParameter::Zone4Transpose
+                | SoyBoyParameter::Zone4Duty
+                | SoyBoyParameter::Zone4Wavetable
+                | SoyBoyParameter::Sustain
+                | SoyBoyParameter::Sostenuto
+                | SoyBoyParameter::Breath
+                | SoyBoyParameter::ModWheelDestination
+                | SoyBoyParameter::ModWheelAmount
+                | SoyBoyParameter::AftertouchDestination
+                | SoyBoyParameter::AftertouchAmount
+                | SoyBoyParameter::BreathDestination
+                | SoyBoyParameter::BreathAmount
+                | SoyBoyParameter::BendUp
+                | SoyBoyParameter::BendDown
+                | SoyBoyParameter::BendQuantize
+                | SoyBoyParameter::MpeZone
+                | SoyBoyParameter::MpeChannels
+                | SoyBoyParameter::MpeBendRange
+                | SoyBoyParameter::TimbreDestination
+                | SoyBoyParameter::TimbreAmount
+                | SoyBoyParameter::PolyPressureDestination
+                | SoyBoyParameter::PolyPressureAmount
+                | SoyBoyParameter::SmoothingTime => param_def.default_value,
+                // v1.0.2 and older projects were mixed without the headroom
+                SoyBoyParameter::MasterHeadroom => 0.0,
+                // the pitch bend was in cents up to 4800 before V05
+                SoyBoyParameter::PitchBend => v04.get_param(&param) / 4800.0,
+                _ => v04.get_param(&param),
+            };
+
+            v05.set_param(&param, param_def, v);
+        }
+        v05.set_wavetable(&v04.wavetable);
+
+        v05
+    }
+}
+
+impl Parametric<SoyBoyParameter> for PluginConfigV05 {
+    fn set_param(&mut self, param: &SoyBoyParameter, param_def: &ParameterDef, value: f64) {
+        let value = param_def.clamp(value);
+
+        match param {
+            SoyBoyParameter::MasterVolume => self.master_volume = value,
+            SoyBoyParameter::PitchBend => self.pitch_bend = value,
+            SoyBoyParameter::Detune => self.detune = value,
+            SoyBoyParameter::OscillatorType => self.oscillator_type = value,
+            SoyBoyParameter::NumVoices => self.num_voices = value,
+            SoyBoyParameter::SweepType => self.sweep_type = value,
+            SoyBoyParameter::SweepAmount => self.sweep_amount = value,
+            SoyBoyParameter::SweepPeriod => self.sweep_period = value,
+            SoyBoyParameter::StutterTime => self.stutter_time = value,
+            SoyBoyParameter::StutterDepth => self.stutter_depth = value,
+            SoyBoyParameter::StutterWhen => self.stutter_when = value,
+            SoyBoyParameter::EgAttack => self.envelope_attack = value,
+            SoyBoyParameter::EgDecay => self.envelope_decay = value,
+            SoyBoyParameter::EgSustain => self.envelope_sustain = value,
+            SoyBoyParameter::EgRelease => self.envelope_release = value,
+            SoyBoyParameter::OscSqDuty => self.osc_sq_duty = value,
+            SoyBoyParameter::OscNsInterval => self.osc_noise_interval = value,
+            SoyBoyParameter::DacFreq => self.dac_freq = value,
+            SoyBoyParameter::DacQ => self.dac_q = value,
+            SoyBoyParameter::SweepSync => self.sweep_sync = value,
+            SoyBoyParameter::SweepDivision => self.sweep_division = value,
+            SoyBoyParameter::StutterSync => self.stutter_sync = value,
+            SoyBoyParameter::StutterDivision => self.stutter_division = value,
+            SoyBoyParameter::StutterCount => self.stutter_count = value,
+            SoyBoyParameter::StutterPitch => self.stutter_pitch = value,
+            SoyBoyParameter::StutterPingPong => self.stutter_ping_pong = value,
+            SoyBoyParameter::StutterProbability => self.stutter_probability = value,
+            SoyBoyParameter::DelayMix => self.delay_mix = value,
+            SoyBoyParameter::DelayTime => self.delay_time = value,
+            SoyBoyParameter::DelaySync => self.delay_sync = value,
+            SoyBoyParameter::DelayDivision => self.delay_division = value,
+            SoyBoyParameter::DelayFeedback => self.delay_feedback = value,
+            SoyBoyParameter::DelayPingPong => self.delay_ping_pong = value,
+            SoyBoyParameter::DelayQuantize => self.delay_quantize = value,
+            SoyBoyParameter::CrushBits => self.crush_bits = value,
+            SoyBoyParameter::CrushRate => self.crush_rate = value,
+            SoyBoyParameter::CrushAntiAlias => self.crush_anti_alias = value,
+            SoyBoyParameter::MasterClip => self.master_clip = value,
+            SoyBoyParameter::FilterEnvAmount => self.filter_env_amount = value,
+            SoyBoyParameter::FilterEnvAttack => self.filter_env_attack = value,
+            SoyBoyParameter::FilterEnvDecay => self.filter_env_decay = value,
+            SoyBoyParameter::FilterKeyTracking => self.filter_key_tracking = value,
+            SoyBoyParameter::FilterVelocity => self.filter_velocity = value,
+            SoyBoyParameter::FilterType => self.filter_type = value,
+            SoyBoyParameter::EgMode => self.envelope_mode = value,
+            SoyBoyParameter::EgHwVolume => self.envelope_hw_volume = value,
+            SoyBoyParameter::EgHwDirection => self.envelope_hw_direction = value,
+            SoyBoyParameter::EgHwPeriod => self.envelope_hw_period = value,
+            SoyBoyParameter::EgAttackCurve => self.envelope_attack_curve = value,
+            SoyBoyParameter::EgAttackTension => self.envelope_attack_tension = value,
+            SoyBoyParameter::EgDecayCurve => self.envelope_decay_curve = value,
+            SoyBoyParameter::EgDecayTension => self.envelope_decay_tension = value,
+            SoyBoyParameter::EgReleaseCurve => self.envelope_release_curve = value,
+            SoyBoyParameter::EgReleaseTension => self.envelope_release_tension = value,
+            SoyBoyParameter::EgHold => self.envelope_hold = value,
+            SoyBoyParameter::EgLoop => self.envelope_loop = value,
+            SoyBoyParameter::VelocityAmount => self.velocity_amount = value,
+            SoyBoyParameter::VelocityCurve => self.velocity_curve = value,
+            SoyBoyParameter::VelocityToAttack => self.velocity_to_attack = value,
+            SoyBoyParameter::VelocityToStutter => self.velocity_to_stutter = value,
+            SoyBoyParameter::Lfo1Rate => self.lfo1_rate = value,
+            SoyBoyParameter::Lfo1Shape => self.lfo1_shape = value,
+            SoyBoyParameter::Lfo2Rate => self.lfo2_rate = value,
+            SoyBoyParameter::Lfo2Shape => self.lfo2_shape = value,
+            SoyBoyParameter::ModEnvAttack => self.mod_env_attack = value,
+            SoyBoyParameter::ModEnvDecay => self.mod_env_decay = value,
+            SoyBoyParameter::ModWheel => self.mod_wheel = value,
+            SoyBoyParameter::Aftertouch => self.aftertouch = value,
+            SoyBoyParameter::ModSlot1Source => self.mod_slot1_source = value,
+            SoyBoyParameter::ModSlot1Destination => self.mod_slot1_destination = value,
+            SoyBoyParameter::ModSlot1Amount => self.mod_slot1_amount = value,
+            SoyBoyParameter::ModSlot2Source => self.mod_slot2_source = value,
+            SoyBoyParameter::ModSlot2Destination => self.mod_slot2_destination = value,
+            SoyBoyParameter::ModSlot2Amount => self.mod_slot2_amount = value,
+            SoyBoyParameter::ModSlot3Source => self.mod_slot3_source = value,
+            SoyBoyParameter::ModSlot3Destination => self.mod_slot3_destination = value,
+            SoyBoyParameter::ModSlot3Amount => self.mod_slot3_amount = value,
+            SoyBoyParameter::ModSlot4Source => self.mod_slot4_source = value,
+            SoyBoyParameter::ModSlot4Destination => self.mod_slot4_destination = value,
+            SoyBoyParameter::ModSlot4Amount => self.mod_slot4_amount = value,
+            SoyBoyParameter::ModSlot5Source => self.mod_slot5_source = value,
+            SoyBoyParameter::ModSlot5Destination => self.mod_slot5_destination = value,
+            SoyBoyParameter::ModSlot5Amount => self.mod_slot5_amount = value,
+            SoyBoyParameter::ModSlot6Source => self.mod_slot6_source = value,
+            SoyBoyParameter::ModSlot6Destination => self.mod_slot6_destination = value,
+            SoyBoyParameter::ModSlot6Amount => self.mod_slot6_amount = value,
+            SoyBoyParameter::ModSlot7Source => self.mod_slot7_source = value,
+            SoyBoyParameter::ModSlot7Destination => self.mod_slot7_destination = value,
+            SoyBoyParameter::ModSlot7Amount => self.mod_slot7_amount = value,
+            SoyBoyParameter::ModSlot8Source => self.mod_slot8_source = value,
+            SoyBoyParameter::ModSlot8Destination => self.mod_slot8_destination = value,
+            SoyBoyParameter::ModSlot8Amount => self.mod_slot8_amount = value,
+            SoyBoyParameter::KitMode => self.kit_mode = value,
+            SoyBoyParameter::KitPad => self.kit_pad = value,
+            SoyBoyParameter::KitNoteLow => self.kit_note_low = value,
+            SoyBoyParameter::KitNoteHigh => self.kit_note_high = value,
+            SoyBoyParameter::KitChokeGroup => self.kit_choke_group = value,
+            SoyBoyParameter::KitPitchMode => self.kit_pitch_mode = value,
+            SoyBoyParameter::KitFixedNote => self.kit_fixed_note = value,
+            SoyBoyParameter::PartMode => self.part_mode = value,
+            SoyBoyParameter::PartEdit => self.part_edit = value,
+            SoyBoyParameter::PartVoices => self.part_voices = value,
+            SoyBoyParameter::PartLevel => self.part_level = value,
+            SoyBoyParameter::PartOutput => self.part_output = value,
+            SoyBoyParameter::SplitMode => self.split_mode = value,
+            SoyBoyParameter::Zone1KeyLow => self.zone1_key_low = value,
+            SoyBoyParameter::Zone1KeyHigh => self.zone1_key_high = value,
+            SoyBoyParameter::Zone1VelocityLow => self.zone1_velocity_low = value,
+            SoyBoyParameter::Zone1VelocityHigh => self.zone1_velocity_high = value,
+            SoyBoyParameter::Zone1Oscillator => self.zone1_oscillator = value,
+            SoyBoyParameter::Zone1Transpose => self.zone1_transpose = value,
+            SoyBoyParameter::Zone1Duty => self.zone1_duty = value,
+            SoyBoyParameter::Zone1Wavetable => self.zone1_wavetable = value,
+            SoyBoyParameter::Zone2KeyLow => self.zone2_key_low = value,
+            SoyBoyParameter::Zone2KeyHigh => self.zone2_key_high = value,
+            SoyBoyParameter::Zone2VelocityLow => self.zone2_velocity_low = value,
+            SoyBoyParameter::Zone2VelocityHigh => self.zone2_velocity_high = value,
+            SoyBoyParameter::Zone2Oscillator => self.zone2_oscillator = value,
+            SoyBoyParameter::Zone2Transpose => self.zone2_transpose = value,
+            SoyBoyParameter::Zone2Duty => self.zone2_duty = value,
+            SoyBoyParameter::Zone2Wavetable => self.zone2_wavetable = value,
+            SoyBoyParameter::Zone3KeyLow => self.zone3_key_low = value,
+            SoyBoyParameter::Zone3KeyHigh => self.zone3_key_high = value,
+            SoyBoyParameter::Zone3VelocityLow => self.zone3_velocity_low = value,
+            SoyBoyParameter::Zone3VelocityHigh => self.zone3_velocity_high = value,
+            SoyBoyParameter::Zone3Oscillator => self.zone3_oscillator = value,
+            SoyBoyParameter::Zone3Transpose => self.zone3_transpose = value,
+            SoyBoyParameter::Zone3Duty => self.zone3_duty = value,
+            SoyBoyParameter::Zone3Wavetable => self.zone3_wavetable = value,
+            SoyBoyParameter::Zone4KeyLow => self.zone4_key_low = value,
+            SoyBoyParameter::Zone4KeyHigh => self.zone4_key_high = value,
+            SoyBoyParameter::Zone4VelocityLow => self.zone4_velocity_low = value,
+            SoyBoyParameter::Zone4VelocityHigh => self.zone4_velocity_high = value,
+            SoyBoyParameter::Zone4Oscillator => self.zone4_oscillator = value,
+            SoyBoyParameter::Zone4Transpose => self.zone4_transpose = value,
+            SoyBoyParameter::Zone4Duty => self.zone4_duty = value,
+            SoyBoyParameter::Zone4Wavetable => self.zone4_wavetable = value,
+            SoyBoyParameter::Sustain => self.sustain = value,
+            SoyBoyParameter::Sostenuto => self.sostenuto = value,
+            SoyBoyParameter::Breath => self.breath = value,
+            SoyBoyParameter::ModWheelDestination => self.mod_wheel_destination = value,
+            SoyBoyParameter::ModWheelAmount => self.mod_wheel_amount = value,
+            SoyBoyParameter::AftertouchDestination => self.aftertouch_destination = value,
+            SoyBoyParameter::AftertouchAmount => self.aftertouch_amount = value,
+            SoyBoyParameter::BreathDestination => self.breath_destination = value,
+            SoyBoyParameter::BreathAmount => self.breath_amount = value,
+            SoyBoyParameter::BendUp => self.bend_up = value,
+            SoyBoyParameter::BendDown => self.bend_down = value,
+            SoyBoyParameter::BendQuantize => self.bend_quantize = value,
+            SoyBoyParameter::MpeZone => self.mpe_zone = value,
+            SoyBoyParameter::MpeChannels => self.mpe_channels = value,
+            SoyBoyParameter::MpeBendRange => self.mpe_bend_range = value,
+            SoyBoyParameter::TimbreDestination => self.timbre_destination = value,
+            SoyBoyParameter::TimbreAmount => self.timbre_amount = value,
+            SoyBoyParameter::PolyPressureDestination => self.poly_pressure_destination = value,
+            SoyBoyParameter::PolyPressureAmount => self.poly_pressure_amount = value,
+            SoyBoyParameter::SmoothingTime => self.smoothing_time = value,
+            SoyBoyParameter::MasterHeadroom => self.master_headroom = value,
+        }
+    }
+
+    fn get_param(&self, param: &SoyBoyParameter) -> f64 {
+        match param {
+            SoyBoyParameter::MasterVolume => self.master_volume,
+            SoyBoyParameter::PitchBend => self.pitch_bend,
+            SoyBoyParameter::Detune => self.detune,
+            SoyBoyParameter::OscillatorType => self.oscillator_type,
+            SoyBoyParameter::NumVoices => self.num_voices,
+            SoyBoyParameter::SweepType => self.sweep_type,
+            SoyBoyParameter::SweepAmount => self.sweep_amount,
+            SoyBoyParameter::SweepPeriod => self.sweep_period,
+            SoyBoyParameter::StutterTime => self.stutter_time,
+            SoyBoyParameter::StutterDepth => self.stutter_depth,
+            SoyBoyParameter::StutterWhen => self.stutter_when,
+            SoyBoyParameter::EgAttack => self.envelope_attack,
+            SoyBoyParameter::EgDecay => self.envelope_decay,
+            SoyBoyParameter::EgSustain => self.envelope_sustain,
+            SoyBoyParameter::EgRelease => self.envelope_release,
+            SoyBoyParameter::OscSqDuty => self.osc_sq_duty,
+            SoyBoyParameter::OscNsInterval => self.osc_noise_interval,
+            SoyBoyParameter::DacFreq => self.dac_freq,
+            SoyBoyParameter::DacQ => self.dac_q,
+            SoyBoyParameter::SweepSync => self.sweep_sync,
+            SoyBoyParameter::SweepDivision => self.sweep_division,
+            SoyBoyParameter::StutterSync => self.stutter_sync,
+            SoyBoyParameter::StutterDivision => self.stutter_division,
+            SoyBoyParameter::StutterCount => self.stutter_count,
+            SoyBoyParameter::StutterPitch => self.stutter_pitch,
+            SoyBoyParameter::StutterPingPong => self.stutter_ping_pong,
+            SoyBoyParameter::StutterProbability => self.stutter_probability,
+            SoyBoyParameter::DelayMix => self.delay_mix,
+            SoyBoyParameter::DelayTime => self.delay_time,
+            SoyBoyParameter::DelaySync => self.delay_sync,
+            SoyBoyParameter::DelayDivision => self.delay_division,
+            SoyBoyParameter::DelayFeedback => self.delay_feedback,
+            SoyBoyParameter::DelayPingPong => self.delay_ping_pong,
+            SoyBoyParameter::DelayQuantize => self.delay_quantize,
+            SoyBoyParameter::CrushBits => self.crush_bits,
+            SoyBoyParameter::CrushRate => self.crush_rate,
+            SoyBoyParameter::CrushAntiAlias => self.crush_anti_alias,
+            SoyBoyParameter::MasterClip => self.master_clip,
+            SoyBoyParameter::FilterEnvAmount => self.filter_env_amount,
+            SoyBoyParameter::FilterEnvAttack => self.filter_env_attack,
+            SoyBoyParameter::FilterEnvDecay => self.filter_env_decay,
+            SoyBoyParameter::FilterKeyTracking => self.filter_key_tracking,
+            SoyBoyParameter::FilterVelocity => self.filter_velocity,
+            SoyBoyParameter::FilterType => self.filter_type,
+            SoyBoyParameter::EgMode => self.envelope_mode,
+            SoyBoyParameter::EgHwVolume => self.envelope_hw_volume,
+            SoyBoyParameter::EgHwDirection => self.envelope_hw_direction,
+            SoyBoyParameter::EgHwPeriod => self.envelope_hw_period,
+            SoyBoyParameter::EgAttackCurve => self.envelope_attack_curve,
+            SoyBoyParameter::EgAttackTension => self.envelope_attack_tension,
+            SoyBoyParameter::EgDecayCurve => self.envelope_decay_curve,
+            SoyBoyParameter::EgDecayTension => self.envelope_decay_tension,
+            SoyBoyParameter::EgReleaseCurve => self.envelope_release_curve,
+            SoyBoyParameter::EgReleaseTension => self.envelope_release_tension,
+            SoyBoyParameter::EgHold => self.envelope_hold,
+            SoyBoyParameter::EgLoop => self.envelope_loop,
+            SoyBoyParameter::VelocityAmount => self.velocity_amount,
+            SoyBoyParameter::VelocityCurve => self.velocity_curve,
+            SoyBoyParameter::VelocityToAttack => self.velocity_to_attack,
+            SoyBoyParameter::VelocityToStutter => self.velocity_to_stutter,
+            SoyBoyParameter::Lfo1Rate => self.lfo1_rate,
+            SoyBoyParameter::Lfo1Shape => self.lfo1_shape,
+            SoyBoyParameter::Lfo2Rate => self.lfo2_rate,
+            SoyBoyParameter::Lfo2Shape => self.lfo2_shape,
+            SoyBoyParameter::ModEnvAttack => self.mod_env_attack,
+            SoyBoyParameter::ModEnvDecay => self.mod_env_decay,
+            SoyBoyParameter::ModWheel => self.mod_wheel,
+            SoyBoyParameter::Aftertouch => self.aftertouch,
+            SoyBoyParameter::ModSlot1Source => self.mod_slot1_source,
+            SoyBoyParameter::ModSlot1Destination => self.mod_slot1_destination,
+            SoyBoyParameter::ModSlot1Amount => self.mod_slot1_amount,
+            SoyBoyParameter::ModSlot2Source => self.mod_slot2_source,
+            SoyBoyParameter::ModSlot2Destination => self.mod_slot2_destination,
+            SoyBoyParameter::ModSlot2Amount => self.mod_slot2_amount,
+            SoyBoyParameter::ModSlot3Source => self.mod_slot3_source,
+            SoyBoyParameter::ModSlot3Destination => self.mod_slot3_destination,
+            SoyBoyParameter::ModSlot3Amount => self.mod_slot3_amount,
+            SoyBoyParameter::ModSlot4Source => self.mod_slot4_source,
+            SoyBoyParameter::ModSlot4Destination => self.mod_slot4_destination,
+            SoyBoyParameter::ModSlot4Amount => self.mod_slot4_amount,
+            SoyBoyParameter::ModSlot5Source => self.mod_slot5_source,
+            SoyBoyParameter::ModSlot5Destination => self.mod_slot5_destination,
+            SoyBoyParameter::ModSlot5Amount => self.mod_slot5_amount,
+            SoyBoyParameter::ModSlot6Source => self.mod_slot6_source,
+            SoyBoyParameter::ModSlot6Destination => self.mod_slot6_destination,
+            SoyBoyParameter::ModSlot6Amount => self.mod_slot6_amount,
+            SoyBoyParameter::ModSlot7Source => self.mod_slot7_source,
+            SoyBoyParameter::ModSlot7Destination => self.mod_slot7_destination,
+            SoyBoyParameter::ModSlot7Amount => self.mod_slot7_amount,
+            SoyBoyParameter::ModSlot8Source => self.mod_slot8_source,
+            SoyBoyParameter::ModSlot8Destination => self.mod_slot8_destination,
+            SoyBoyParameter::ModSlot8Amount => self.mod_slot8_amount,
+            SoyBoyParameter::KitMode => self.kit_mode,
+            SoyBoyParameter::KitPad => self.kit_pad,
+            SoyBoyParameter::KitNoteLow => self.kit_note_low,
+            SoyBoyParameter::KitNoteHigh => self.kit_note_high,
+            SoyBoyParameter::KitChokeGroup => self.kit_choke_group,
+            SoyBoyParameter::KitPitchMode => self.kit_pitch_mode,
+            SoyBoyParameter::KitFixedNote => self.kit_fixed_note,
+            SoyBoyParameter::PartMode => self.part_mode,
+            SoyBoyParameter::PartEdit => self.part_edit,
+            SoyBoyParameter::PartVoices => self.part_voices,
+            SoyBoyParameter::PartLevel => self.part_level,
+            SoyBoyParameter::PartOutput => self.part_output,
+            SoyBoyParameter::SplitMode => self.split_mode,
+            SoyBoyParameter::Zone1KeyLow => self.zone1_key_low,
+            SoyBoyParameter::Zone1KeyHigh => self.zone1_key_high,
+            SoyBoyParameter::Zone1VelocityLow => self.zone1_velocity_low,
+            SoyBoyParameter::Zone1VelocityHigh => self.zone1_velocity_high,
+            SoyBoyParameter::Zone1Oscillator => self.zone1_oscillator,
+            SoyBoyParameter::Zone1Transpose => self.zone1_transpose,
+            SoyBoyParameter::Zone1Duty => self.zone1_duty,
+            SoyBoyParameter::Zone1Wavetable => self.zone1_wavetable,
+            SoyBoyParameter::Zone2KeyLow => self.zone2_key_low,
+            SoyBoyParameter::Zone2KeyHigh => self.zone2_key_high,
+            SoyBoyParameter::Zone2VelocityLow => self.zone2_velocity_low,
+            SoyBoyParameter::Zone2VelocityHigh => self.zone2_velocity_high,
+            SoyBoyParameter::Zone2Oscillator => self.zone2_oscillator,
+            SoyBoyParameter::Zone2Transpose => self.zone2_transpose,
+            SoyBoyParameter::Zone2Duty => self.zone2_duty,
+            SoyBoyParameter::Zone2Wavetable => self.zone2_wavetable,
+            SoyBoyParameter::Zone3KeyLow => self.zone3_key_low,
+            SoyBoyParameter::Zone3KeyHigh => self.zone3_key_high,
+            SoyBoyParameter::Zone3VelocityLow => self.zone3_velocity_low,
+            SoyBoyParameter::Zone3VelocityHigh => self.zone3_velocity_high,
+            SoyBoyParameter::Zone3Oscillator => self.zone3_oscillator,
+            SoyBoyParameter::Zone3Transpose => self.zone3_transpose,
+            SoyBoyParameter::Zone3Duty => self.zone3_duty,
+            SoyBoyParameter::Zone3Wavetable => self.zone3_wavetable,
+            SoyBoyParameter::Zone4KeyLow => self.zone4_key_low,
+            SoyBoyParameter::Zone4KeyHigh => self.zone4_key_high,
+            SoyBoyParameter::Zone4VelocityLow => self.zone4_velocity_low,
+            SoyBoyParameter::Zone4VelocityHigh => self.zone4_velocity_high,
+            SoyBoyParameter::Zone4Oscillator => self.zone4_oscillator,
+            SoyBoyParameter::Zone4Transpose => self.zone4_transpose,
+            SoyBoyParameter::Zone4Duty => self.zone4_duty,
+            SoyBoyParameter::Zone4Wavetable => self.zone4_wavetable,
+            SoyBoyParameter::Sustain => self.sustain,
+            SoyBoyParameter::Sostenuto => self.sostenuto,
+            SoyBoyParameter::Breath => self.breath,
+            SoyBoyParameter::ModWheelDestination => self.mod_wheel_destination,
+            SoyBoyParameter::ModWheelAmount => self.mod_wheel_amount,
+            SoyBoyParameter::AftertouchDestination => self.aftertouch_destination,
+            SoyBoyParameter::AftertouchAmount => self.aftertouch_amount,
+            SoyBoyParameter::BreathDestination => self.breath_destination,
+            SoyBoyParameter::BreathAmount => self.breath_amount,
+            SoyBoyParameter::BendUp => self.bend_up,
+            SoyBoyParameter::BendDown => self.bend_down,
+            SoyBoyParameter::BendQuantize => self.bend_quantize,
+            SoyBoyParameter::MpeZone => self.mpe_zone,
+            SoyBoyParameter::MpeChannels => self.mpe_channels,
+            SoyBoyParameter::MpeBendRange => self.mpe_bend_range,
+            SoyBoyParameter::TimbreDestination => self.timbre_destination,
+            SoyBoyParameter::TimbreAmount => self.timbre_amount,
+            SoyBoyParameter::PolyPressureDestination => self.poly_pressure_destination,
+            SoyBoyParameter::PolyPressureAmount => self.poly_pressure_amount,
+            SoyBoyParameter::SmoothingTime => self.smoothing_time,
+            SoyBoyParameter::MasterHeadroom => self.master_headroom,
+        }
+    }
+}
+
+impl Default for PluginConfigV05 {
+    fn default() -> Self {
+        Self {
+            waveform_view_enabled: false,
+            master_volume: 0.0,
+            pitch_bend: 0.0,
+            detune: 0.0,
+            oscillator_type: 0.0,
+            num_voices: 0.0,
+            sweep_type: 0.0,
+            sweep_amount: 0.0,
+            sweep_period: 0.0,
+            stutter_time: 0.0,
+            stutter_depth: 0.0,
+            stutter_when: 0.0,
+            envelope_attack: 0.0,
+            envelope_decay: 0.0,
+            envelope_sustain: 0.0,
+            envelope_release: 0.0,
+            osc_sq_duty: 0.0,
+            osc_noise_interval: 0.0,
+            dac_freq: 0.0,
+            dac_q: 0.0,
+            sweep_sync: 0.0,
+            sweep_division: 0.0,
+            stutter_sync: 0.0,
+            stutter_division: 0.0,
+            stutter_count: 0.0,
+            stutter_pitch: 0.0,
+            stutter_ping_pong: 0.0,
+            stutter_probability: 0.0,
+            delay_mix: 0.0,
+            delay_time: 0.0,
+            delay_sync: 0.0,
+            delay_division: 0.0,
+            delay_feedback: 0.0,
+            delay_ping_pong: 0.0,
+            delay_quantize: 0.0,
+            crush_bits: 0.0,
+            crush_rate: 0.0,
+            crush_anti_alias: 0.0,
+            master_clip: 0.0,
+            filter_env_amount: 0.0,
+            filter_env_attack: 0.0,
+            filter_env_decay: 0.0,
+            filter_key_tracking: 0.0,
+            filter_velocity: 0.0,
+            filter_type: 0.0,
+            envelope_mode: 0.0,
+            envelope_hw_volume: 0.0,
+            envelope_hw_direction: 0.0,
+            envelope_hw_period: 0.0,
+            envelope_attack_curve: 0.0,
+            envelope_attack_tension: 0.0,
+            envelope_decay_curve: 0.0,
+            envelope_decay_tension: 0.0,
+            envelope_release_curve: 0.0,
+            envelope_release_tension: 0.0,
+            envelope_hold: 0.0,
+            envelope_loop: 0.0,
+            velocity_amount: 0.0,
+            velocity_curve: 0.0,
+            velocity_to_attack: 0.0,
+            velocity_to_stutter: 0.0,
+            lfo1_rate: 0.0,
+            lfo1_shape: 0.0,
+            lfo2_rate: 0.0,
+            lfo2_shape: 0.0,
+            mod_env_attack: 0.0,
+            mod_env_decay: 0.0,
+            mod_wheel: 0.0,
+            aftertouch: 0.0,
+            mod_slot1_source: 0.0,
+            mod_slot1_destination: 0.0,
+            mod_slot1_amount: 0.0,
+            mod_slot2_source: 0.0,
+            mod_slot2_destination: 0.0,
+            mod_slot2_amount: 0.0,
+            mod_slot3_source: 0.0,
+            mod_slot3_destination: 0.0,
+            mod_slot3_amount: 0.0,
+            mod_slot4_source: 0.0,
+            mod_slot4_destination: 0.0,
+            mod_slot4_amount: 0.0,
+            mod_slot5_source: 0.0,
+            mod_slot5_destination: 0.0,
+            mod_slot5_amount: 0.0,
+            mod_slot6_source: 0.0,
+            mod_slot6_destination: 0.0,
+            mod_slot6_amount: 0.0,
+            mod_slot7_source: 0.0,
+            mod_slot7_destination: 0.0,
+            mod_slot7_amount: 0.0,
+            mod_slot8_source: 0.0,
+            mod_slot8_destination: 0.0,
+            mod_slot8_amount: 0.0,
+            kit_mode: 0.0,
+            kit_pad: 0.0,
+            kit_note_low: 0.0,
+            kit_note_high: 0.0,
+            kit_choke_group: 0.0,
+            kit_pitch_mode: 0.0,
+            kit_fixed_note: 0.0,
+            part_mode: 0.0,
+            part_edit: 0.0,
+            part_voices: 0.0,
+            part_level: 0.0,
+            part_output: 0.0,
+            split_mode: 0.0,
+            zone1_key_low: 0.0,
+            zone1_key_high: 0.0,
+            zone1_velocity_low: 0.0,
+            zone1_velocity_high: 0.0,
+            zone1_oscillator: 0.0,
+            zone1_transpose: 0.0,
+            zone1_duty: 0.0,
+            zone1_wavetable: 0.0,
+            zone2_key_low: 0.0,
+            zone2_key_high: 0.0,
+            zone2_velocity_low: 0.0,
+            zone2_velocity_high: 0.0,
+            zone2_oscillator: 0.0,
+            zone2_transpose: 0.0,
+            zone2_duty: 0.0,
+            zone2_wavetable: 0.0,
+            zone3_key_low: 0.0,
+            zone3_key_high: 0.0,
+            zone3_velocity_low: 0.0,
+            zone3_velocity_high: 0.0,
+            zone3_oscillator: 0.0,
+            zone3_transpose: 0.0,
+            zone3_duty: 0.0,
+            zone3_wavetable: 0.0,
+            zone4_key_low: 0.0,
+            zone4_key_high: 0.0,
+            zone4_velocity_low: 0.0,
+            zone4_velocity_high: 0.0,
+            zone4_oscillator: 0.0,
+            zone4_transpose: 0.0,
+            zone4_duty: 0.0,
+            zone4_wavetable: 0.0,
+            sustain: 0.0,
+            sostenuto: 0.0,
+            breath: 0.0,
+            mod_wheel_destination: 0.0,
+            mod_wheel_amount: 0.0,
+            aftertouch_destination: 0.0,
+            aftertouch_amount: 0.0,
+            breath_destination: 0.0,
+            breath_amount: 0.0,
+            bend_up: 0.0,
+            bend_down: 0.0,
+            bend_quantize: 0.0,
+            mpe_zone: 0.0,
+            mpe_channels: 0.0,
+            mpe_bend_range: 0.0,
+            timbre_destination: 0.0,
+            timbre_amount: 0.0,
+            poly_pressure_destination: 0.0,
+            poly_pressure_amount: 0.0,
+            smoothing_time: 0.0,
+            master_headroom: 0.0,
+            wavetable: [i4::from(0i8); constants::WAVETABLE_SIZE],
+            kit: Vec::new(),
+            kit_wavetables: Vec::new(),
+            parts: Vec::new(),
+            part_wavetables: Vec::new(),
+            midi_mappings: Vec::new(),
+        }
+    }
+}
+
+impl PartialEq for PluginConfigV05 {
+    fn eq(&self, _other: &Self) -> bool {
+        false
+    }
+
+    fn ne(&self, _other: &Self) -> bool {
+        true
+    }
+}
+impl Eq for PluginConfigV05 {}
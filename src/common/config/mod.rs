@@ -1,5 +1,11 @@
 mod config_v01;
 mod config_v02;
+mod config_v03;
+mod config_v04;
+mod config_v05;
 
 pub use config_v01::PluginConfigV01;
 pub use config_v02::PluginConfigV02;
+pub use config_v03::PluginConfigV03;
+pub use config_v04::PluginConfigV04;
+pub use config_v05::PluginConfigV05;
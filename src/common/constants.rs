@@ -2,8 +2,8 @@ pub const MAX_NUMBER_OF_VOICES: usize = 8;
 pub const OSCILLOSCOPE_SAIMPLE_SIZE: usize = 512;
 pub const WAVETABLE_SIZE: usize = 32;
 
-pub const SCREEN_WIDTH: u32 = 680;
-pub const SCREEN_HEIGHT: u32 = 560;
+pub const SCREEN_WIDTH: u32 = 1336;
+pub const SCREEN_HEIGHT: u32 = 760;
 
 // high frame rate for waveform view (fps ~= 30)
 pub const WAVEFORM_UPDATE_INTERVAL_IN_MILLIS: u64 = 35;
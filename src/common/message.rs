@@ -1,6 +1,6 @@
 use std::fmt;
 
-use super::config::PluginConfigV02;
+use super::config::PluginConfigV05;
 use super::waveform::Waveform;
 use crate::common::{constants, i4};
 use crate::soyboy::parameters::SoyBoyParameter;
@@ -14,8 +14,9 @@ pub enum GUIEvent {
     NoteOn,
     WaveTableData([i4; constants::WAVETABLE_SIZE]),
     WaveformData(Waveform),
-    Configure(PluginConfigV02),
+    Configure(PluginConfigV05),
     SetParam(SoyBoyParameter, f64),
+    Clipped,
 }
 
 pub enum Vst3Message {
@@ -23,12 +24,15 @@ pub enum Vst3Message {
     InitializeWaveTable,
     RandomizeWaveTable,
     ConfigurationRequested,
-    ConfigurationData(PluginConfigV02),
+    ConfigurationData(PluginConfigV05),
     WaveTableData([i4; constants::WAVETABLE_SIZE]),
     SetWaveTable(usize, i4),
+    LearnMidiCc(SoyBoyParameter),
+    MidiLearning(bool),
     WaveformData(Waveform),
     EnableWaveform,
     DisableWaveform,
+    Clipped,
 }
 
 impl fmt::Display for Vst3Message {
@@ -41,9 +45,12 @@ impl fmt::Display for Vst3Message {
             Vst3Message::ConfigurationData(_) => "vst3:config-data",
             Vst3Message::WaveTableData(_) => "vst3:wavetable-data",
             Vst3Message::SetWaveTable(_, _) => "vst3:set-wavetable-sample",
+            Vst3Message::LearnMidiCc(_) => "vst3:learn-midi-cc",
+            Vst3Message::MidiLearning(_) => "vst3:midi-learning",
             Vst3Message::WaveformData(_) => "vst3:waveform-data",
             Vst3Message::EnableWaveform => "vst3:enable-waveform",
             Vst3Message::DisableWaveform => "vst3:disable-waveform",
+            Vst3Message::Clipped => "vst3:clipped",
         };
 
         write!(f, "{}", s)
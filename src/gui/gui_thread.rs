@@ -188,6 +188,21 @@ impl GUIThread {
                     let _ = ui.add(self.ui.label_envelope.clone());
                     let _ = ui.add(self.ui.label_sweep.clone());
                     let _ = ui.add(self.ui.label_stutter.clone());
+
+                    // lo-fi side
+                    let _ = ui.add(self.ui.label_lofi.clone());
+                    let _ = ui.add(self.ui.label_master.clone());
+                    let _ = ui.add(self.ui.label_filter.clone());
+
+                    // envelope details side
+                    let _ = ui.add(self.ui.label_hw_envelope.clone());
+                    let _ = ui.add(self.ui.label_eg_curves.clone());
+
+                    // drum kit side
+                    let _ = ui.add(self.ui.label_kit.clone());
+
+                    // multitimbral side
+                    let _ = ui.add(self.ui.label_multi.clone());
                 });
 
             // params
@@ -232,6 +247,49 @@ impl GUIThread {
                     let _ = self.ui.param_sweep_type.show(ui);
                     let _ = self.ui.param_stutter_timing.show(ui);
 
+                    let _ = self.ui.param_crush_bits.show(ui);
+                    let _ = self.ui.param_crush_rate.show(ui);
+                    let _ = self.ui.param_crush_anti_alias.show(ui);
+
+                    let _ = self.ui.param_master_clip.show(ui);
+                    let _ = self.ui.clip_indicator.show(ui);
+
+                    let _ = self.ui.param_filter_freq.show(ui);
+                    let _ = self.ui.param_filter_q.show(ui);
+                    let _ = self.ui.param_filter_env_amount.show(ui);
+                    let _ = self.ui.param_filter_env_attack.show(ui);
+                    let _ = self.ui.param_filter_env_decay.show(ui);
+                    let _ = self.ui.param_filter_key_tracking.show(ui);
+                    let _ = self.ui.param_filter_velocity.show(ui);
+                    let _ = self.ui.param_filter_type.show(ui);
+
+                    let _ = self.ui.param_eg_mode.show(ui);
+                    let _ = self.ui.param_eg_hw_volume.show(ui);
+                    let _ = self.ui.param_eg_hw_direction.show(ui);
+                    let _ = self.ui.param_eg_hw_period.show(ui);
+                    let _ = self.ui.param_eg_attack_curve.show(ui);
+                    let _ = self.ui.param_eg_attack_tension.show(ui);
+                    let _ = self.ui.param_eg_decay_curve.show(ui);
+                    let _ = self.ui.param_eg_decay_tension.show(ui);
+                    let _ = self.ui.param_eg_release_curve.show(ui);
+                    let _ = self.ui.param_eg_release_tension.show(ui);
+                    let _ = self.ui.param_eg_hold.show(ui);
+                    let _ = self.ui.param_eg_loop.show(ui);
+
+                    let _ = self.ui.param_kit_mode.show(ui);
+                    let _ = self.ui.param_kit_pad.show(ui);
+                    let _ = self.ui.param_kit_choke_group.show(ui);
+                    let _ = self.ui.param_kit_note_low.show(ui);
+                    let _ = self.ui.param_kit_note_high.show(ui);
+                    let _ = self.ui.param_kit_pitch_mode.show(ui);
+                    let _ = self.ui.param_kit_fixed_note.show(ui);
+
+                    let _ = self.ui.param_part_mode.show(ui);
+                    let _ = self.ui.param_part_edit.show(ui);
+                    let _ = self.ui.param_part_voices.show(ui);
+                    let _ = self.ui.param_part_level.show(ui);
+                    let _ = self.ui.param_part_output.show(ui);
+
                     let _ = self.ui.param_voices.show(ui);
 
                     let _ = self.ui.param_wavetable.show(ui);
@@ -264,6 +322,7 @@ impl GUIThread {
             &mut self.ui.button_reset_random as &mut dyn Behavior,
             &mut self.ui.button_reset_sine as &mut dyn Behavior,
             &mut self.ui.param_voices as &mut dyn Behavior,
+            &mut self.ui.clip_indicator as &mut dyn Behavior,
         ];
 
         for widget in behaviors.iter_mut() {
@@ -293,6 +352,10 @@ impl GUIThread {
                     self.ui.set_value(param, v);
                     self.needs_redraw = true;
                 }
+                GUIEvent::Clipped => {
+                    self.ui.clip_indicator.clip();
+                    self.needs_redraw = true;
+                }
             }
         }
 
@@ -10,6 +10,8 @@ unsafe impl Sync for ParentWindow {}
 
 pub trait EventHandler {
     fn change_parameter(&self, p: SoyBoyParameter, value_normalized: f64);
+    /// Starts MIDI learn for the parameter, or forgets its MIDI CC while learning it.
+    fn learn_midi_cc(&self, p: SoyBoyParameter);
 }
 
 pub trait Behavior {
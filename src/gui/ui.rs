@@ -5,7 +5,7 @@ use std::sync::{Arc, Mutex};
 
 use egui_glow::egui_winit::egui;
 
-use crate::common::PluginConfigV02;
+use crate::common::PluginConfigV05;
 use crate::gui::images::{Image, Images};
 use crate::soyboy::parameters::{ParameterDef, Parametric, SoyBoyParameter};
 use crate::vst3::ControllerConnection;
@@ -24,6 +24,14 @@ pub struct UI {
     pub label_envelope: ImageLabel,
     pub label_sweep: ImageLabel,
     pub label_stutter: ImageLabel,
+    pub label_lofi: TextLabel,
+    pub label_master: TextLabel,
+    pub label_filter: TextLabel,
+    pub label_hw_envelope: TextLabel,
+    pub label_eg_curves: TextLabel,
+    pub label_kit: TextLabel,
+    pub label_multi: TextLabel,
+    pub clip_indicator: ClipIndicator,
     pub oscilloscope: Oscilloscope,
     pub button_reset_random: ButtonBehavior,
     pub button_reset_sine: ButtonBehavior,
@@ -42,6 +50,42 @@ pub struct UI {
     pub param_osc_sq_duty: ParameterSelector,
     pub param_sweep_type: ParameterSelector,
     pub param_stutter_timing: ParameterSelector,
+    pub param_crush_bits: ParameterSlider,
+    pub param_crush_rate: ParameterSlider,
+    pub param_crush_anti_alias: TextSelector,
+    pub param_master_clip: TextSelector,
+    pub param_filter_freq: ParameterSlider,
+    pub param_filter_q: ParameterSlider,
+    pub param_filter_env_amount: ParameterSlider,
+    pub param_filter_env_attack: ParameterSlider,
+    pub param_filter_env_decay: ParameterSlider,
+    pub param_filter_key_tracking: ParameterSlider,
+    pub param_filter_velocity: ParameterSlider,
+    pub param_filter_type: TextSelector,
+    pub param_eg_mode: TextSelector,
+    pub param_eg_hw_volume: ParameterSlider,
+    pub param_eg_hw_direction: TextSelector,
+    pub param_eg_hw_period: ParameterSlider,
+    pub param_eg_attack_curve: TextSelector,
+    pub param_eg_attack_tension: ParameterSlider,
+    pub param_eg_decay_curve: TextSelector,
+    pub param_eg_decay_tension: ParameterSlider,
+    pub param_eg_release_curve: TextSelector,
+    pub param_eg_release_tension: ParameterSlider,
+    pub param_eg_hold: ParameterSlider,
+    pub param_eg_loop: TextSelector,
+    pub param_kit_mode: TextSelector,
+    pub param_kit_pad: PadSelector,
+    pub param_kit_choke_group: ParameterSlider,
+    pub param_kit_note_low: ParameterSlider,
+    pub param_kit_note_high: ParameterSlider,
+    pub param_kit_pitch_mode: TextSelector,
+    pub param_kit_fixed_note: ParameterSlider,
+    pub param_part_mode: TextSelector,
+    pub param_part_edit: PadSelector,
+    pub param_part_voices: ParameterSlider,
+    pub param_part_level: ParameterSlider,
+    pub param_part_output: TextSelector,
     pub param_voices: ParameterVoices,
     pub param_wavetable: WaveTableEditor,
 }
@@ -92,6 +136,14 @@ impl UI {
                 352.0,
                 316.0,
             ),
+            label_lofi: TextLabel::new("LO-FI", 18.0, 716.0, 12.0),
+            label_master: TextLabel::new("MASTER", 18.0, 716.0, 172.0),
+            clip_indicator: ClipIndicator::new(922.0, 170.0),
+            label_filter: TextLabel::new("FILTER", 18.0, 716.0, 252.0),
+            label_hw_envelope: TextLabel::new("HW ENVELOPE", 18.0, 1044.0, 12.0),
+            label_eg_curves: TextLabel::new("ENVELOPE CURVES", 18.0, 1044.0, 252.0),
+            label_kit: TextLabel::new("DRUM KIT", 18.0, 716.0, 572.0),
+            label_multi: TextLabel::new("MULTITIMBRAL", 18.0, 24.0, 572.0),
             oscilloscope: Oscilloscope::new(
                 waveform_view_enabled.clone(),
                 Image::new(egui_ctx, &images.oscilloscope_border),
@@ -352,6 +404,563 @@ impl UI {
                 324.0,
                 event_handler.clone(),
             ),
+            param_crush_bits: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::CrushBits,
+                    param_def: param_defs.get(&SoyBoyParameter::CrushBits).unwrap().clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::CrushBits as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                716.0,
+                42.0,
+                event_handler.clone(),
+            ),
+            param_crush_rate: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::CrushRate,
+                    param_def: param_defs.get(&SoyBoyParameter::CrushRate).unwrap().clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::CrushRate as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                716.0,
+                78.0,
+                event_handler.clone(),
+            ),
+            param_crush_anti_alias: TextSelector::new(
+                SelectorValue {
+                    param: SoyBoyParameter::CrushAntiAlias,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::CrushAntiAlias)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::CrushAntiAlias as u32))
+                        .unwrap(),
+                },
+                716.0,
+                114.0,
+                event_handler.clone(),
+            ),
+            param_master_clip: TextSelector::new(
+                SelectorValue {
+                    param: SoyBoyParameter::MasterClip,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::MasterClip)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::MasterClip as u32))
+                        .unwrap(),
+                },
+                716.0,
+                202.0,
+                event_handler.clone(),
+            ),
+            param_filter_freq: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::DacFreq,
+                    param_def: param_defs.get(&SoyBoyParameter::DacFreq).unwrap().clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::DacFreq as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                716.0,
+                278.0,
+                event_handler.clone(),
+            ),
+            param_filter_q: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::DacQ,
+                    param_def: param_defs.get(&SoyBoyParameter::DacQ).unwrap().clone(),
+                    value: *param_values.get(&(SoyBoyParameter::DacQ as u32)).unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                716.0,
+                312.0,
+                event_handler.clone(),
+            ),
+            param_filter_env_amount: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::FilterEnvAmount,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::FilterEnvAmount)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::FilterEnvAmount as u32))
+                        .unwrap(),
+                    bipolar: true,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                716.0,
+                346.0,
+                event_handler.clone(),
+            ),
+            param_filter_env_attack: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::FilterEnvAttack,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::FilterEnvAttack)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::FilterEnvAttack as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                716.0,
+                380.0,
+                event_handler.clone(),
+            ),
+            param_filter_env_decay: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::FilterEnvDecay,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::FilterEnvDecay)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::FilterEnvDecay as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                716.0,
+                414.0,
+                event_handler.clone(),
+            ),
+            param_filter_key_tracking: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::FilterKeyTracking,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::FilterKeyTracking)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::FilterKeyTracking as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                716.0,
+                448.0,
+                event_handler.clone(),
+            ),
+            param_filter_velocity: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::FilterVelocity,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::FilterVelocity)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::FilterVelocity as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                716.0,
+                482.0,
+                event_handler.clone(),
+            ),
+            param_filter_type: TextSelector::new(
+                SelectorValue {
+                    param: SoyBoyParameter::FilterType,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::FilterType)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::FilterType as u32))
+                        .unwrap(),
+                },
+                716.0,
+                516.0,
+                event_handler.clone(),
+            ),
+            param_eg_mode: TextSelector::new(
+                SelectorValue {
+                    param: SoyBoyParameter::EgMode,
+                    param_def: param_defs.get(&SoyBoyParameter::EgMode).unwrap().clone(),
+                    value: *param_values.get(&(SoyBoyParameter::EgMode as u32)).unwrap(),
+                },
+                1044.0,
+                42.0,
+                event_handler.clone(),
+            ),
+            param_eg_hw_volume: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::EgHwVolume,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::EgHwVolume)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::EgHwVolume as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                1044.0,
+                90.0,
+                event_handler.clone(),
+            ),
+            param_eg_hw_direction: TextSelector::new(
+                SelectorValue {
+                    param: SoyBoyParameter::EgHwDirection,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::EgHwDirection)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::EgHwDirection as u32))
+                        .unwrap(),
+                },
+                1044.0,
+                126.0,
+                event_handler.clone(),
+            ),
+            param_eg_hw_period: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::EgHwPeriod,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::EgHwPeriod)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::EgHwPeriod as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                1044.0,
+                174.0,
+                event_handler.clone(),
+            ),
+            param_eg_attack_curve: TextSelector::new(
+                SelectorValue {
+                    param: SoyBoyParameter::EgAttackCurve,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::EgAttackCurve)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::EgAttackCurve as u32))
+                        .unwrap(),
+                },
+                1044.0,
+                282.0,
+                event_handler.clone(),
+            ),
+            param_eg_attack_tension: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::EgAttackTension,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::EgAttackTension)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::EgAttackTension as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                1044.0,
+                318.0,
+                event_handler.clone(),
+            ),
+            param_eg_decay_curve: TextSelector::new(
+                SelectorValue {
+                    param: SoyBoyParameter::EgDecayCurve,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::EgDecayCurve)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::EgDecayCurve as u32))
+                        .unwrap(),
+                },
+                1044.0,
+                354.0,
+                event_handler.clone(),
+            ),
+            param_eg_decay_tension: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::EgDecayTension,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::EgDecayTension)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::EgDecayTension as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                1044.0,
+                390.0,
+                event_handler.clone(),
+            ),
+            param_eg_release_curve: TextSelector::new(
+                SelectorValue {
+                    param: SoyBoyParameter::EgReleaseCurve,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::EgReleaseCurve)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::EgReleaseCurve as u32))
+                        .unwrap(),
+                },
+                1044.0,
+                426.0,
+                event_handler.clone(),
+            ),
+            param_eg_release_tension: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::EgReleaseTension,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::EgReleaseTension)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::EgReleaseTension as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                1044.0,
+                462.0,
+                event_handler.clone(),
+            ),
+            param_eg_hold: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::EgHold,
+                    param_def: param_defs.get(&SoyBoyParameter::EgHold).unwrap().clone(),
+                    value: *param_values.get(&(SoyBoyParameter::EgHold as u32)).unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::Sec,
+                },
+                slider_images,
+                1044.0,
+                498.0,
+                event_handler.clone(),
+            ),
+            param_eg_loop: TextSelector::new(
+                SelectorValue {
+                    param: SoyBoyParameter::EgLoop,
+                    param_def: param_defs.get(&SoyBoyParameter::EgLoop).unwrap().clone(),
+                    value: *param_values.get(&(SoyBoyParameter::EgLoop as u32)).unwrap(),
+                },
+                1044.0,
+                532.0,
+                event_handler.clone(),
+            ),
+            param_kit_mode: TextSelector::new(
+                SelectorValue {
+                    param: SoyBoyParameter::KitMode,
+                    param_def: param_defs.get(&SoyBoyParameter::KitMode).unwrap().clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::KitMode as u32))
+                        .unwrap(),
+                },
+                716.0,
+                602.0,
+                event_handler.clone(),
+            ),
+            param_kit_pad: PadSelector::new(
+                SelectorValue {
+                    param: SoyBoyParameter::KitPad,
+                    param_def: param_defs.get(&SoyBoyParameter::KitPad).unwrap().clone(),
+                    value: *param_values.get(&(SoyBoyParameter::KitPad as u32)).unwrap(),
+                },
+                716.0,
+                648.0,
+                event_handler.clone(),
+            ),
+            param_kit_choke_group: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::KitChokeGroup,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::KitChokeGroup)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::KitChokeGroup as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                716.0,
+                716.0,
+                event_handler.clone(),
+            ),
+            param_kit_note_low: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::KitNoteLow,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::KitNoteLow)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::KitNoteLow as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                1044.0,
+                602.0,
+                event_handler.clone(),
+            ),
+            param_kit_note_high: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::KitNoteHigh,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::KitNoteHigh)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::KitNoteHigh as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                1044.0,
+                636.0,
+                event_handler.clone(),
+            ),
+            param_kit_pitch_mode: TextSelector::new(
+                SelectorValue {
+                    param: SoyBoyParameter::KitPitchMode,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::KitPitchMode)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::KitPitchMode as u32))
+                        .unwrap(),
+                },
+                1044.0,
+                670.0,
+                event_handler.clone(),
+            ),
+            param_kit_fixed_note: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::KitFixedNote,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::KitFixedNote)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::KitFixedNote as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                1044.0,
+                716.0,
+                event_handler.clone(),
+            ),
+            param_part_mode: TextSelector::new(
+                SelectorValue {
+                    param: SoyBoyParameter::PartMode,
+                    param_def: param_defs.get(&SoyBoyParameter::PartMode).unwrap().clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::PartMode as u32))
+                        .unwrap(),
+                },
+                24.0,
+                602.0,
+                event_handler.clone(),
+            ),
+            param_part_edit: PadSelector::new(
+                SelectorValue {
+                    param: SoyBoyParameter::PartEdit,
+                    param_def: param_defs.get(&SoyBoyParameter::PartEdit).unwrap().clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::PartEdit as u32))
+                        .unwrap(),
+                },
+                24.0,
+                648.0,
+                event_handler.clone(),
+            ),
+            param_part_voices: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::PartVoices,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::PartVoices)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::PartVoices as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::None,
+                },
+                slider_images,
+                352.0,
+                602.0,
+                event_handler.clone(),
+            ),
+            param_part_level: ParameterSlider::new(
+                SliderValue {
+                    param: SoyBoyParameter::PartLevel,
+                    param_def: param_defs.get(&SoyBoyParameter::PartLevel).unwrap().clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::PartLevel as u32))
+                        .unwrap(),
+                    bipolar: false,
+                    unit: ParameterUnit::Percent,
+                },
+                slider_images,
+                352.0,
+                636.0,
+                event_handler.clone(),
+            ),
+            param_part_output: TextSelector::new(
+                SelectorValue {
+                    param: SoyBoyParameter::PartOutput,
+                    param_def: param_defs
+                        .get(&SoyBoyParameter::PartOutput)
+                        .unwrap()
+                        .clone(),
+                    value: *param_values
+                        .get(&(SoyBoyParameter::PartOutput as u32))
+                        .unwrap(),
+                },
+                352.0,
+                670.0,
+                event_handler.clone(),
+            ),
             param_voices: ParameterVoices::new(
                 *param_values
                     .get(&(SoyBoyParameter::NumVoices as u32))
@@ -393,12 +1002,151 @@ impl UI {
             SoyBoyParameter::EgRelease => self.param_release.set(value),
             SoyBoyParameter::OscSqDuty => self.param_osc_sq_duty.set(value),
             SoyBoyParameter::OscNsInterval => self.param_interval.set(value),
-            SoyBoyParameter::DacFreq => (),
-            SoyBoyParameter::DacQ => (),
+            SoyBoyParameter::DacFreq => self.param_filter_freq.set(value),
+            SoyBoyParameter::DacQ => self.param_filter_q.set(value),
+            SoyBoyParameter::SweepSync => (),
+            SoyBoyParameter::SweepDivision => (),
+            SoyBoyParameter::StutterSync => (),
+            SoyBoyParameter::StutterDivision => (),
+            SoyBoyParameter::StutterCount => (),
+            SoyBoyParameter::StutterPitch => (),
+            SoyBoyParameter::StutterPingPong => (),
+            SoyBoyParameter::StutterProbability => (),
+            SoyBoyParameter::DelayMix => (),
+            SoyBoyParameter::DelayTime => (),
+            SoyBoyParameter::DelaySync => (),
+            SoyBoyParameter::DelayDivision => (),
+            SoyBoyParameter::DelayFeedback => (),
+            SoyBoyParameter::DelayPingPong => (),
+            SoyBoyParameter::DelayQuantize => (),
+            SoyBoyParameter::MasterClip => self.param_master_clip.set(value),
+            SoyBoyParameter::FilterEnvAmount => self.param_filter_env_amount.set(value),
+            SoyBoyParameter::FilterEnvAttack => self.param_filter_env_attack.set(value),
+            SoyBoyParameter::FilterEnvDecay => self.param_filter_env_decay.set(value),
+            SoyBoyParameter::FilterKeyTracking => self.param_filter_key_tracking.set(value),
+            SoyBoyParameter::FilterVelocity => self.param_filter_velocity.set(value),
+            SoyBoyParameter::FilterType => self.param_filter_type.set(value),
+            SoyBoyParameter::EgMode => self.param_eg_mode.set(value),
+            SoyBoyParameter::EgHwVolume => self.param_eg_hw_volume.set(value),
+            SoyBoyParameter::EgHwDirection => self.param_eg_hw_direction.set(value),
+            SoyBoyParameter::EgHwPeriod => self.param_eg_hw_period.set(value),
+            SoyBoyParameter::EgAttackCurve => self.param_eg_attack_curve.set(value),
+            SoyBoyParameter::EgAttackTension => self.param_eg_attack_tension.set(value),
+            SoyBoyParameter::EgDecayCurve => self.param_eg_decay_curve.set(value),
+            SoyBoyParameter::EgDecayTension => self.param_eg_decay_tension.set(value),
+            SoyBoyParameter::EgReleaseCurve => self.param_eg_release_curve.set(value),
+            SoyBoyParameter::EgReleaseTension => self.param_eg_release_tension.set(value),
+            SoyBoyParameter::EgHold => self.param_eg_hold.set(value),
+            SoyBoyParameter::EgLoop => self.param_eg_loop.set(value),
+            SoyBoyParameter::VelocityAmount => (),
+            SoyBoyParameter::VelocityCurve => (),
+            SoyBoyParameter::VelocityToAttack => (),
+            SoyBoyParameter::VelocityToStutter => (),
+            SoyBoyParameter::Lfo1Rate => (),
+            SoyBoyParameter::Lfo1Shape => (),
+            SoyBoyParameter::Lfo2Rate => (),
+            SoyBoyParameter::Lfo2Shape => (),
+            SoyBoyParameter::ModEnvAttack => (),
+            SoyBoyParameter::ModEnvDecay => (),
+            SoyBoyParameter::ModWheel => (),
+            SoyBoyParameter::Aftertouch => (),
+            SoyBoyParameter::ModSlot1Source => (),
+            SoyBoyParameter::ModSlot1Destination => (),
+            SoyBoyParameter::ModSlot1Amount => (),
+            SoyBoyParameter::ModSlot2Source => (),
+            SoyBoyParameter::ModSlot2Destination => (),
+            SoyBoyParameter::ModSlot2Amount => (),
+            SoyBoyParameter::ModSlot3Source => (),
+            SoyBoyParameter::ModSlot3Destination => (),
+            SoyBoyParameter::ModSlot3Amount => (),
+            SoyBoyParameter::ModSlot4Source => (),
+            SoyBoyParameter::ModSlot4Destination => (),
+            SoyBoyParameter::ModSlot4Amount => (),
+            SoyBoyParameter::ModSlot5Source => (),
+            SoyBoyParameter::ModSlot5Destination => (),
+            SoyBoyParameter::ModSlot5Amount => (),
+            SoyBoyParameter::ModSlot6Source => (),
+            SoyBoyParameter::ModSlot6Destination => (),
+            SoyBoyParameter::ModSlot6Amount => (),
+            SoyBoyParameter::ModSlot7Source => (),
+            SoyBoyParameter::ModSlot7Destination => (),
+            SoyBoyParameter::ModSlot7Amount => (),
+            SoyBoyParameter::ModSlot8Source => (),
+            SoyBoyParameter::ModSlot8Destination => (),
+            SoyBoyParameter::ModSlot8Amount => (),
+            SoyBoyParameter::KitMode => self.param_kit_mode.set(value),
+            SoyBoyParameter::KitPad => self.param_kit_pad.set(value),
+            SoyBoyParameter::KitChokeGroup => self.param_kit_choke_group.set(value),
+            SoyBoyParameter::KitNoteLow => self.param_kit_note_low.set(value),
+            SoyBoyParameter::KitNoteHigh => self.param_kit_note_high.set(value),
+            SoyBoyParameter::KitPitchMode => self.param_kit_pitch_mode.set(value),
+            SoyBoyParameter::KitFixedNote => self.param_kit_fixed_note.set(value),
+            SoyBoyParameter::PartMode => self.param_part_mode.set(value),
+            SoyBoyParameter::PartEdit => self.param_part_edit.set(value),
+            SoyBoyParameter::PartVoices => self.param_part_voices.set(value),
+            SoyBoyParameter::PartLevel => self.param_part_level.set(value),
+            SoyBoyParameter::PartOutput => self.param_part_output.set(value),
+            SoyBoyParameter::SplitMode => (),
+            SoyBoyParameter::Zone1KeyLow => (),
+            SoyBoyParameter::Zone1KeyHigh => (),
+            SoyBoyParameter::Zone1VelocityLow => (),
+            SoyBoyParameter::Zone1VelocityHigh => (),
+            SoyBoyParameter::Zone1Oscillator => (),
+            SoyBoyParameter::Zone1Transpose => (),
+            SoyBoyParameter::Zone1Duty => (),
+            SoyBoyParameter::Zone1Wavetable => (),
+            SoyBoyParameter::Zone2KeyLow => (),
+            SoyBoyParameter::Zone2KeyHigh => (),
+            SoyBoyParameter::Zone2VelocityLow => (),
+            SoyBoyParameter::Zone2VelocityHigh => (),
+            SoyBoyParameter::Zone2Oscillator => (),
+            SoyBoyParameter::Zone2Transpose => (),
+            SoyBoyParameter::Zone2Duty => (),
+            SoyBoyParameter::Zone2Wavetable => (),
+            SoyBoyParameter::Zone3KeyLow => (),
+            SoyBoyParameter::Zone3KeyHigh => (),
+            SoyBoyParameter::Zone3VelocityLow => (),
+            SoyBoyParameter::Zone3VelocityHigh => (),
+            SoyBoyParameter::Zone3Oscillator => (),
+            SoyBoyParameter::Zone3Transpose => (),
+            SoyBoyParameter::Zone3Duty => (),
+            SoyBoyParameter::Zone3Wavetable => (),
+            SoyBoyParameter::Zone4KeyLow => (),
+            SoyBoyParameter::Zone4KeyHigh => (),
+            SoyBoyParameter::Zone4VelocityLow => (),
+            SoyBoyParameter::Zone4VelocityHigh => (),
+            SoyBoyParameter::Zone4Oscillator => (),
+            SoyBoyParameter::Zone4Transpose => (),
+            SoyBoyParameter::Zone4Duty => (),
+            SoyBoyParameter::Zone4Wavetable => (),
+            SoyBoyParameter::Sustain => (),
+            SoyBoyParameter::Sostenuto => (),
+            SoyBoyParameter::Breath => (),
+            SoyBoyParameter::ModWheelDestination => (),
+            SoyBoyParameter::ModWheelAmount => (),
+            SoyBoyParameter::AftertouchDestination => (),
+            SoyBoyParameter::AftertouchAmount => (),
+            SoyBoyParameter::BreathDestination => (),
+            SoyBoyParameter::BreathAmount => (),
+            SoyBoyParameter::BendUp => (),
+            SoyBoyParameter::BendDown => (),
+            SoyBoyParameter::BendQuantize => (),
+            SoyBoyParameter::MpeZone => (),
+            SoyBoyParameter::MpeChannels => (),
+            SoyBoyParameter::MpeBendRange => (),
+            SoyBoyParameter::TimbreDestination => (),
+            SoyBoyParameter::TimbreAmount => (),
+            SoyBoyParameter::PolyPressureDestination => (),
+            SoyBoyParameter::PolyPressureAmount => (),
+            SoyBoyParameter::SmoothingTime => (),
+            SoyBoyParameter::MasterHeadroom => (),
+            SoyBoyParameter::CrushBits => self.param_crush_bits.set(value),
+            SoyBoyParameter::CrushRate => self.param_crush_rate.set(value),
+            SoyBoyParameter::CrushAntiAlias => self.param_crush_anti_alias.set(value),
         }
     }
 
-    pub fn configure(&mut self, config: PluginConfigV02) {
+    pub fn configure(&mut self, config: PluginConfigV05) {
         for ref param in SoyBoyParameter::iter() {
             self.set_value(param, config.get_param(param));
         }
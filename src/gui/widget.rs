@@ -20,6 +20,8 @@ fn screen_rect() -> egui::Rect {
     }
 }
 
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(0x33, 0x3f, 0x32);
+
 pub trait SetValue {
     fn set(&mut self, v: f64);
 }
@@ -151,6 +153,38 @@ impl Widget for ParameterName {
     }
 }
 
+/// A label painted with a font, for things that have no image in resources/.
+#[derive(Clone)]
+pub struct TextLabel {
+    text: String,
+    font_id: egui::FontId,
+    pos: egui::Pos2,
+}
+
+impl TextLabel {
+    pub fn new(text: &str, size: f32, x: f32, y: f32) -> Self {
+        Self {
+            text: text.to_string(),
+            font_id: egui::FontId::monospace(size),
+            pos: egui::pos2(x, y),
+        }
+    }
+}
+
+impl Widget for TextLabel {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let rect = ui.painter().text(
+            self.pos,
+            egui::Align2::LEFT_TOP,
+            self.text,
+            self.font_id,
+            TEXT_COLOR,
+        );
+
+        ui.allocate_rect(rect, egui::Sense::focusable_noninteractive())
+    }
+}
+
 #[derive(Clone)]
 pub struct VersionFrame {
     rect: egui::Rect,
@@ -325,6 +359,78 @@ impl Behavior for AnimatedEdamame {
     }
 }
 
+/// A lamp lighting for a while when the master output goes over 0dBFS.
+pub struct ClipIndicator {
+    rect: egui::Rect,
+    clipped_at: Option<time::Instant>,
+    lit: Toggle,
+}
+
+impl ClipIndicator {
+    pub fn new(x: f32, y: f32) -> Self {
+        let pos = egui::pos2(x, y);
+        let rect = egui::Rect::from_min_size(pos, egui::vec2(60.0, 22.0));
+
+        Self {
+            rect,
+            clipped_at: None,
+            lit: Toggle::new(false, false),
+        }
+    }
+
+    pub fn clip(&mut self) {
+        self.clipped_at = Some(time::Instant::now());
+    }
+}
+
+impl Behavior for ClipIndicator {
+    fn update(&mut self) -> bool {
+        match self.clipped_at {
+            Some(t) if t.elapsed() <= time::Duration::from_millis(500) => self.lit.set(true),
+            _ => self.lit.set(false),
+        }
+
+        self.lit.toggled()
+    }
+
+    fn show(&mut self, ui: &mut egui::Ui) -> egui::Response {
+        ui.set_clip_rect(self.rect);
+        let response = ui.allocate_rect(self.rect, egui::Sense::click());
+
+        if response.clicked() {
+            self.clipped_at = None;
+        }
+
+        if ui.is_rect_visible(self.rect) {
+            let rect = self.rect.shrink(1.0);
+            let text_color = if self.lit.val() {
+                ui.painter()
+                    .rect_filled(rect, egui::Rounding::none(), TEXT_COLOR);
+                egui::Color32::from_rgb(0xab, 0xbb, 0xa8)
+            } else {
+                TEXT_COLOR
+            };
+
+            ui.painter().rect_stroke(
+                rect,
+                egui::Rounding::none(),
+                egui::Stroke::new(2.0, TEXT_COLOR),
+            );
+            ui.painter().text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                "CLIP",
+                egui::FontId::monospace(14.0),
+                text_color,
+            );
+        }
+
+        ui.set_clip_rect(screen_rect());
+
+        response
+    }
+}
+
 #[derive(Clone)]
 pub struct Button {
     image: egui::widgets::Image,
@@ -570,6 +676,10 @@ impl Behavior for SliderBehavior {
             }
         }
 
+        if response.secondary_clicked() {
+            self.event_handler.learn_midi_cc(self.parameter);
+        }
+
         response
     }
 }
@@ -643,7 +753,16 @@ impl Behavior for ParameterSlider {
         let rect = egui::Rect::from_two_pos(self.pos, self.pos + egui::vec2(266.0, 30.0));
 
         ui.set_clip_rect(rect);
-        ui.add(ParameterName::new(self.param, self.param_atlas, self.pos));
+        if self.param.get_region().is_some() {
+            ui.add(ParameterName::new(self.param, self.param_atlas, self.pos));
+        } else {
+            ui.add(TextLabel::new(
+                &self.param_def.title,
+                14.0,
+                self.pos.x,
+                self.pos.y,
+            ));
+        }
         ui.set_clip_rect(rect);
 
         let mut value = ParameterValue::new(
@@ -807,6 +926,194 @@ impl Behavior for ParameterSelector {
     }
 }
 
+/// A selector painting its choices with a font, for list parameters
+/// which have no image in resources/.
+pub struct TextSelector {
+    param: SoyBoyParameter,
+    param_def: ParameterDef,
+    value: usize,
+    pos: egui::Pos2,
+    event_handler: Arc<dyn EventHandler>,
+}
+
+impl TextSelector {
+    pub fn new(value: SelectorValue, x: f32, y: f32, event_handler: Arc<dyn EventHandler>) -> Self {
+        let v = value.param_def.denormalize(value.value) as usize;
+        Self {
+            param: value.param,
+            param_def: value.param_def,
+            value: v,
+            pos: egui::pos2(x, y),
+            event_handler,
+        }
+    }
+}
+
+impl SetValue for TextSelector {
+    fn set(&mut self, v: f64) {
+        self.value = v as usize;
+    }
+}
+
+impl Behavior for TextSelector {
+    fn update(&mut self) -> bool {
+        false
+    }
+
+    fn show(&mut self, ui: &mut egui::Ui) -> egui::Response {
+        let rect = egui::Rect::from_min_size(self.pos, egui::vec2(266.0, 40.0));
+        ui.set_clip_rect(rect);
+
+        ui.add(TextLabel::new(
+            &self.param_def.title,
+            14.0,
+            self.pos.x,
+            self.pos.y,
+        ));
+
+        let num = self.param_def.step_count as usize + 1;
+        let width = 266.0 / num as f32;
+        let stroke = egui::Stroke::new(2.0, TEXT_COLOR);
+
+        let responses: Vec<egui::Response> = (0..num)
+            .map(|i| {
+                let topleft = self.pos + egui::vec2(width * i as f32, 16.0);
+                let button_rect =
+                    egui::Rect::from_min_size(topleft, egui::vec2(width, 22.0)).shrink(1.0);
+                let response = ui.allocate_rect(button_rect, egui::Sense::click());
+
+                if ui.is_rect_visible(button_rect) {
+                    ui.painter()
+                        .rect_stroke(button_rect, egui::Rounding::none(), stroke);
+                    ui.painter().text(
+                        button_rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        self.param_def.format(self.param_def.normalize(i as f64)),
+                        egui::FontId::monospace(14.0),
+                        TEXT_COLOR,
+                    );
+
+                    if i == self.value {
+                        ui.painter().rect_filled(
+                            button_rect,
+                            egui::Rounding::none(),
+                            egui::Color32::from_rgba_unmultiplied(0x33, 0x3f, 0x32, 80),
+                        );
+                    }
+                }
+
+                response
+            })
+            .collect();
+
+        ui.set_clip_rect(screen_rect());
+
+        if let Some(pos) = responses.iter().position(|res| res.clicked()) {
+            self.value = pos;
+            self.event_handler
+                .change_parameter(self.param, self.param_def.normalize(self.value as f64));
+        }
+
+        responses[self.value.min(num - 1)].clone()
+    }
+}
+
+/// Selects a drum kit pad to edit from a grid of 2 rows of 8 pads.
+pub struct PadSelector {
+    param: SoyBoyParameter,
+    param_def: ParameterDef,
+    value: usize,
+    pos: egui::Pos2,
+    event_handler: Arc<dyn EventHandler>,
+}
+
+impl PadSelector {
+    const COLUMNS: usize = 8;
+
+    pub fn new(value: SelectorValue, x: f32, y: f32, event_handler: Arc<dyn EventHandler>) -> Self {
+        let v = value.param_def.denormalize(value.value) as usize;
+        Self {
+            param: value.param,
+            param_def: value.param_def,
+            value: v,
+            pos: egui::pos2(x, y),
+            event_handler,
+        }
+    }
+}
+
+impl SetValue for PadSelector {
+    fn set(&mut self, v: f64) {
+        self.value = v as usize;
+    }
+}
+
+impl Behavior for PadSelector {
+    fn update(&mut self) -> bool {
+        false
+    }
+
+    fn show(&mut self, ui: &mut egui::Ui) -> egui::Response {
+        let rect = egui::Rect::from_min_size(self.pos, egui::vec2(266.0, 62.0));
+        ui.set_clip_rect(rect);
+
+        ui.add(TextLabel::new(
+            &self.param_def.title,
+            14.0,
+            self.pos.x,
+            self.pos.y,
+        ));
+
+        let num = self.param_def.step_count as usize + 1;
+        let width = 266.0 / PadSelector::COLUMNS as f32;
+        let stroke = egui::Stroke::new(2.0, TEXT_COLOR);
+
+        // pads are numbered from 1
+        let responses: Vec<egui::Response> = (0..num)
+            .map(|i| {
+                let col = (i % PadSelector::COLUMNS) as f32;
+                let row = (i / PadSelector::COLUMNS) as f32;
+                let topleft = self.pos + egui::vec2(width * col, 16.0 + 22.0 * row);
+                let button_rect =
+                    egui::Rect::from_min_size(topleft, egui::vec2(width, 22.0)).shrink(1.0);
+                let response = ui.allocate_rect(button_rect, egui::Sense::click());
+
+                if ui.is_rect_visible(button_rect) {
+                    ui.painter()
+                        .rect_stroke(button_rect, egui::Rounding::none(), stroke);
+                    ui.painter().text(
+                        button_rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        (i + 1).to_string(),
+                        egui::FontId::monospace(14.0),
+                        TEXT_COLOR,
+                    );
+
+                    if i + 1 == self.value {
+                        ui.painter().rect_filled(
+                            button_rect,
+                            egui::Rounding::none(),
+                            egui::Color32::from_rgba_unmultiplied(0x33, 0x3f, 0x32, 80),
+                        );
+                    }
+                }
+
+                response
+            })
+            .collect();
+
+        ui.set_clip_rect(screen_rect());
+
+        if let Some(pos) = responses.iter().position(|res| res.clicked()) {
+            self.value = pos + 1;
+            self.event_handler
+                .change_parameter(self.param, self.param_def.normalize(self.value as f64));
+        }
+
+        responses[self.value.clamp(1, num) - 1].clone()
+    }
+}
+
 pub struct ParameterVoices {
     value: usize,
     param_def: ParameterDef,
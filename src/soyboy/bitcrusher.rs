@@ -0,0 +1,138 @@
+use std::f64::consts::PI;
+
+use crate::{
+    common::f64_utils,
+    soyboy::{
+        parameters::{ParameterDef, Parametric, SoyBoyParameter},
+        Signal,
+    },
+};
+
+/// 16 bit means no bit reduction.
+const MAX_BITS: u32 = 16;
+
+/// A one-pole low-pass filter used twice as the anti-alias pre-filter.
+#[derive(Clone, Copy)]
+struct OnePole {
+    coefficient: f64,
+    last_value: f64,
+}
+
+impl OnePole {
+    fn new() -> Self {
+        Self {
+            coefficient: 1.0,
+            last_value: 0.0,
+        }
+    }
+
+    fn set_cutoff(&mut self, cutoff: f64, sample_rate: f64) {
+        let w = 2.0 * PI * cutoff / sample_rate;
+        self.coefficient = 1.0 - (-w).exp();
+    }
+
+    fn process(&mut self, input: f64) -> f64 {
+        let v = self.last_value + self.coefficient * (input - self.last_value);
+        self.last_value = f64_utils::normalize(v);
+        self.last_value
+    }
+}
+
+pub struct BitCrusher {
+    bits: u32,
+    rate: f64,
+    anti_alias: bool,
+
+    filters: [[OnePole; 2]; 2],
+    filter_sample_rate: f64,
+    phase: f64,
+    hold: Signal,
+}
+
+impl BitCrusher {
+    pub fn new() -> Self {
+        Self {
+            bits: MAX_BITS,
+            rate: 48_000.0,
+            anti_alias: true,
+
+            filters: [[OnePole::new(); 2]; 2],
+            filter_sample_rate: 0.0,
+            phase: 1.0,
+            hold: (0.0, 0.0),
+        }
+    }
+
+    fn calculate_filters(&mut self, sample_rate: f64) {
+        let cutoff = self.rate / 2.0;
+        for filter in self.filters.iter_mut().flatten() {
+            filter.set_cutoff(cutoff, sample_rate);
+        }
+        self.filter_sample_rate = sample_rate;
+    }
+
+    fn filter(&mut self, input: Signal) -> Signal {
+        let [[l0, l1], [r0, r1]] = &mut self.filters;
+        let vl = l1.process(l0.process(input.0));
+        let vr = r1.process(r0.process(input.1));
+        (vl, vr)
+    }
+
+    /// Rounds to the resolution of `bits`. 4 bit is the one of the GB's channel mixing.
+    fn quantize(&self, v: f64) -> f64 {
+        if self.bits >= MAX_BITS {
+            v
+        } else {
+            let half = 2.0f64.powi(self.bits as i32 - 1);
+            (v * half).round() / half
+        }
+    }
+
+    pub fn process(&mut self, sample_rate: f64, input: Signal) -> Signal {
+        let input = if self.rate < sample_rate {
+            let input = if self.anti_alias {
+                if self.filter_sample_rate != sample_rate {
+                    self.calculate_filters(sample_rate);
+                }
+                self.filter(input)
+            } else {
+                input
+            };
+
+            // sample and hold
+            self.phase += self.rate / sample_rate;
+            if self.phase >= 1.0 {
+                self.phase -= 1.0;
+                self.hold = input;
+            }
+            self.hold
+        } else {
+            input
+        };
+
+        (self.quantize(input.0), self.quantize(input.1))
+    }
+}
+
+impl Parametric<SoyBoyParameter> for BitCrusher {
+    fn set_param(&mut self, param: &SoyBoyParameter, _param_def: &ParameterDef, value: f64) {
+        match param {
+            SoyBoyParameter::CrushBits => self.bits = value as u32,
+            SoyBoyParameter::CrushRate => {
+                self.filter_sample_rate = 0.0;
+                self.rate = value;
+            }
+            SoyBoyParameter::CrushAntiAlias => self.anti_alias = value as u32 != 0,
+            _ => (),
+        }
+    }
+
+    fn get_param(&self, param: &SoyBoyParameter) -> f64 {
+        match param {
+            SoyBoyParameter::CrushBits => self.bits as f64,
+            SoyBoyParameter::CrushRate => self.rate,
+            SoyBoyParameter::CrushAntiAlias => (self.anti_alias as u32).into(),
+            _ => 0.0,
+        }
+    }
+}
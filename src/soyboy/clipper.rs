@@ -0,0 +1,112 @@
+use std::convert::TryFrom;
+
+use crate::{
+    common::f64_utils,
+    soyboy::{
+        parameters::{ParameterDef, Parametric, SoyBoyParameter},
+        Signal,
+    },
+};
+
+/// The time for the limiter to recover its gain.
+const LIMITER_RELEASE_TIME: f64 = 0.05;
+
+#[derive(Debug, Copy, Clone)]
+enum ClipMode {
+    Off = 0,
+    SoftClip,
+    Limiter,
+}
+
+impl TryFrom<u32> for ClipMode {
+    type Error = ();
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        if id == ClipMode::Off as u32 {
+            Ok(ClipMode::Off)
+        } else if id == ClipMode::SoftClip as u32 {
+            Ok(ClipMode::SoftClip)
+        } else if id == ClipMode::Limiter as u32 {
+            Ok(ClipMode::Limiter)
+        } else {
+            Err(())
+        }
+    }
+}
+
+pub struct Clipper {
+    mode: ClipMode,
+
+    peak: f64,
+    clipped: bool,
+}
+
+impl Clipper {
+    pub fn new() -> Self {
+        Self {
+            mode: ClipMode::Off,
+
+            peak: 0.0,
+            clipped: false,
+        }
+    }
+
+    /// Returns whether the input has gone over 0dBFS since the last call.
+    pub fn take_clipped(&mut self) -> bool {
+        let clipped = self.clipped;
+        self.clipped = false;
+        clipped
+    }
+
+    /// A limiter without lookahead: the gain drops instantly and recovers exponentially.
+    fn limit(&mut self, sample_rate: f64, input: Signal) -> Signal {
+        let level = input.0.abs().max(input.1.abs());
+        let release = (-1.0 / (LIMITER_RELEASE_TIME * sample_rate)).exp();
+
+        self.peak = if level > self.peak {
+            level
+        } else {
+            f64_utils::normalize(self.peak * release)
+        };
+
+        let gain = if self.peak > 1.0 {
+            1.0 / self.peak
+        } else {
+            1.0
+        };
+        (input.0 * gain, input.1 * gain)
+    }
+
+    pub fn process(&mut self, sample_rate: f64, input: Signal) -> Signal {
+        if input.0.abs() > 1.0 || input.1.abs() > 1.0 {
+            self.clipped = true;
+        }
+
+        match self.mode {
+            ClipMode::Off => input,
+            ClipMode::SoftClip => (input.0.tanh(), input.1.tanh()),
+            ClipMode::Limiter => {
+                let (l, r) = self.limit(sample_rate, input);
+                (l.clamp(-1.0, 1.0), r.clamp(-1.0, 1.0))
+            }
+        }
+    }
+}
+
+impl Parametric<SoyBoyParameter> for Clipper {
+    fn set_param(&mut self, param: &SoyBoyParameter, _param_def: &ParameterDef, value: f64) {
+        if param == &SoyBoyParameter::MasterClip {
+            if let Ok(mode) = ClipMode::try_from(value as u32) {
+                self.mode = mode;
+                self.peak = 0.0;
+            }
+        }
+    }
+
+    fn get_param(&self, param: &SoyBoyParameter) -> f64 {
+        match param {
+            SoyBoyParameter::MasterClip => (self.mode as u32).into(),
+            _ => 0.0,
+        }
+    }
+}
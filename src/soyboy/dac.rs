@@ -1,11 +1,49 @@
+use std::convert::TryFrom;
+
 use crate::{
     common::{f64_utils, i4},
-    soyboy::{ParameterDef, Parametric, SoyBoyParameter},
+    soyboy::{smoother::Smoother, ParameterDef, Parametric, SoyBoyParameter},
 };
 
+/// Coefficients are recalculated once in this number of samples while the cutoff is modulated
+/// or the cutoff and Q are smoothed.
+const CONTROL_BLOCK_SIZE: u32 = 32;
+
+#[derive(Debug, Copy, Clone)]
+enum FilterType {
+    LowPass = 0,
+    HighPass,
+    BandPass,
+    Notch,
+}
+
+impl TryFrom<u32> for FilterType {
+    type Error = ();
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        if id == FilterType::LowPass as u32 {
+            Ok(FilterType::LowPass)
+        } else if id == FilterType::HighPass as u32 {
+            Ok(FilterType::HighPass)
+        } else if id == FilterType::BandPass as u32 {
+            Ok(FilterType::BandPass)
+        } else if id == FilterType::Notch as u32 {
+            Ok(FilterType::Notch)
+        } else {
+            Err(())
+        }
+    }
+}
+
 pub struct DAConverter {
-    freq: f64,
-    q: f64,
+    freq: Smoother,
+    q: Smoother,
+    filter_type: FilterType,
+    modulation: f64,
+    calculated_modulation: f64,
+    calculated_freq: f64,
+    calculated_q: f64,
+    block_samples: u32,
 
     input_buf: [f64; 2],
     output_buf: [f64; 2],
@@ -16,13 +54,20 @@ pub struct DAConverter {
     b1: f64,
     b2: f64,
     calculated_coefficient: bool,
+    calculated_sample_rate: f64,
 }
 
 impl DAConverter {
     pub fn new(freq: f64, q: f64) -> Self {
         DAConverter {
-            freq,
-            q,
+            freq: Smoother::new(freq),
+            q: Smoother::new(q),
+            filter_type: FilterType::LowPass,
+            modulation: 0.0,
+            calculated_modulation: 0.0,
+            calculated_freq: freq,
+            calculated_q: q,
+            block_samples: 0,
 
             input_buf: [0.0; 2],
             output_buf: [0.0; 2],
@@ -33,21 +78,67 @@ impl DAConverter {
             b1: 0.0,
             b2: 0.0,
             calculated_coefficient: false,
+            calculated_sample_rate: 0.0,
         }
     }
 
+    /// Sets the cutoff modulation in octaves.
+    pub fn set_modulation(&mut self, octaves: f64) {
+        self.modulation = octaves;
+    }
+
+    /// Makes the cutoff and Q reach their targets at once.
+    pub fn finish_smoothing(&mut self) {
+        if self.freq.is_smoothing() || self.q.is_smoothing() {
+            self.freq.finish();
+            self.q.finish();
+            self.calculated_coefficient = false;
+        }
+    }
+
+    fn cutoff(&self, sample_rate: f64) -> f64 {
+        let freq = self.freq.value() * 2.0f64.powf(self.modulation);
+        num::clamp(freq, 20.0, sample_rate * 0.49)
+    }
+
+    /// Calculates biquad coefficients from RBJ's Audio EQ Cookbook.
     fn calculate_coefficient(&mut self, sample_rate: f64) {
-        let w = (2.0 * std::f64::consts::PI * self.freq) / sample_rate;
+        self.calculated_modulation = self.modulation;
+        self.calculated_freq = self.freq.value();
+        self.calculated_q = self.q.value();
+        self.calculated_sample_rate = sample_rate;
+
+        let w = (2.0 * std::f64::consts::PI * self.cutoff(sample_rate)) / sample_rate;
         let w = f64_utils::normalize(w);
 
         let (sw, cw) = (w.sin(), w.cos());
         let (sw, cw) = (f64_utils::normalize(sw), f64_utils::normalize(cw));
 
-        let a = sw / (2.0 * self.q);
+        let a = sw / (2.0 * self.q.value());
 
-        self.b0 = (1.0 - cw) / 2.0;
-        self.b1 = 1.0 - cw;
-        self.b2 = (1.0 - cw) / 2.0;
+        match self.filter_type {
+            FilterType::LowPass => {
+                self.b0 = (1.0 - cw) / 2.0;
+                self.b1 = 1.0 - cw;
+                self.b2 = (1.0 - cw) / 2.0;
+            }
+            FilterType::HighPass => {
+                self.b0 = (1.0 + cw) / 2.0;
+                self.b1 = -(1.0 + cw);
+                self.b2 = (1.0 + cw) / 2.0;
+            }
+            FilterType::BandPass => {
+                // constant 0dB peak gain
+                self.b0 = a;
+                self.b1 = 0.0;
+                self.b2 = -a;
+            }
+            FilterType::Notch => {
+                self.b0 = 1.0;
+                self.b1 = -2.0 * cw;
+                self.b2 = 1.0;
+            }
+        }
 
         self.a0 = 1.0 + a;
         self.a1 = -2.0 * cw;
@@ -55,10 +146,18 @@ impl DAConverter {
     }
 
     pub fn process(&mut self, sample_rate: f64, input: i4) -> f64 {
-        if !self.calculated_coefficient {
+        let freq = self.freq.process(sample_rate);
+        let q = self.q.process(sample_rate);
+        let modulated = self.block_samples == 0
+            && (self.modulation != self.calculated_modulation
+                || freq != self.calculated_freq
+                || q != self.calculated_q);
+        let sample_rate_changed = self.calculated_sample_rate != sample_rate;
+        if !self.calculated_coefficient || modulated || sample_rate_changed {
             self.calculate_coefficient(sample_rate);
             self.calculated_coefficient = true;
         }
+        self.block_samples = (self.block_samples + 1) % CONTROL_BLOCK_SIZE;
 
         let input: f64 = input.into();
         let (in0, in1) = (self.input_buf[0], self.input_buf[1]);
@@ -82,13 +181,28 @@ impl DAConverter {
 impl Parametric<SoyBoyParameter> for DAConverter {
     fn set_param(&mut self, param: &SoyBoyParameter, _param_def: &ParameterDef, value: f64) {
         match param {
+            // without ramps, the coefficients change at once
             SoyBoyParameter::DacFreq => {
-                self.calculated_coefficient = false;
-                self.freq = value;
+                self.freq.set_target(value);
+                if !self.freq.is_smoothing() {
+                    self.calculated_coefficient = false;
+                }
             }
             SoyBoyParameter::DacQ => {
-                self.calculated_coefficient = false;
-                self.q = value;
+                self.q.set_target(value);
+                if !self.q.is_smoothing() {
+                    self.calculated_coefficient = false;
+                }
+            }
+            SoyBoyParameter::SmoothingTime => {
+                self.freq.set_ramp_time(value / 1000.0);
+                self.q.set_ramp_time(value / 1000.0);
+            }
+            SoyBoyParameter::FilterType => {
+                if let Ok(filter_type) = FilterType::try_from(value as u32) {
+                    self.calculated_coefficient = false;
+                    self.filter_type = filter_type;
+                }
             }
             _ => (),
         }
@@ -96,8 +210,9 @@ impl Parametric<SoyBoyParameter> for DAConverter {
 
     fn get_param(&self, param: &SoyBoyParameter) -> f64 {
         match param {
-            SoyBoyParameter::DacFreq => self.freq,
-            SoyBoyParameter::DacQ => self.q,
+            SoyBoyParameter::DacFreq => self.freq.target(),
+            SoyBoyParameter::DacQ => self.q.target(),
+            SoyBoyParameter::FilterType => (self.filter_type as u32).into(),
             _ => 0.0,
         }
     }
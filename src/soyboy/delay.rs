@@ -0,0 +1,203 @@
+use std::convert::TryFrom;
+
+use crate::{
+    common::f64_utils,
+    soyboy::{
+        event::{Event, Triggered},
+        parameters::{ParameterDef, Parametric, SoyBoyParameter},
+        tempo::{NoteDivision, TimeMode, DEFAULT_TEMPO},
+        Signal,
+    },
+};
+
+/// The longest delay time in seconds. Synced times longer than this are cut to it.
+pub const MAX_DELAY_TIME: f64 = 2.0;
+
+/// Repeats quieter than -60dB are regarded as silent when we calculate the tail.
+const SILENCE_RATIO: f64 = 0.001;
+
+/// The most repeats tapped at their own 4-bit levels before the line feeds back.
+const MAX_TAPS: usize = 16;
+
+pub struct Delay {
+    mix: f64,
+    time: f64,
+    sync: TimeMode,
+    division: NoteDivision,
+    feedback: f64,
+    ping_pong: bool,
+    quantize: bool,
+    tempo: f64,
+
+    sample_rate: f64,
+    buffer: Vec<Signal>,
+    position: usize,
+}
+
+impl Delay {
+    pub fn new() -> Self {
+        Delay {
+            mix: 0.0,
+            time: 0.25,
+            sync: TimeMode::Free,
+            division: NoteDivision::EighthDotted,
+            feedback: 40.0,
+            ping_pong: false,
+            quantize: true,
+            tempo: DEFAULT_TEMPO,
+
+            sample_rate: 0.0,
+            buffer: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Allocates the delay line. This should be called out of the audio thread.
+    pub fn set_sample_rate(&mut self, sample_rate: f64) {
+        let len = (MAX_DELAY_TIME * sample_rate) as usize + 1;
+
+        self.sample_rate = sample_rate;
+        self.buffer = vec![(0.0, 0.0); len];
+        self.position = 0;
+    }
+
+    fn interval(&self) -> f64 {
+        let time = match self.sync {
+            TimeMode::Free => self.time,
+            TimeMode::Sync => self.division.seconds(self.tempo),
+        };
+        time.min(MAX_DELAY_TIME)
+    }
+
+    /// Returns how many samples the repeats keep sounding after the input stopped.
+    pub fn tail_samples(&self) -> u32 {
+        if self.mix == 0.0 {
+            return 0;
+        }
+
+        let interval = self.interval();
+        let feedback = self.feedback / 100.0;
+        let repeats = if feedback > 0.0 {
+            SILENCE_RATIO.ln() / feedback.ln()
+        } else {
+            0.0
+        };
+
+        ((repeats + 1.0) * interval * self.sample_rate).ceil() as u32
+    }
+
+    fn read(&self, delay: usize) -> Signal {
+        let len = self.buffer.len();
+        self.buffer[(self.position + len - delay) % len]
+    }
+
+    /// Returns the repeats and the signal fed back into the line.
+    fn repeat(&self, input: Signal, delay: usize, feedback: f64) -> (Signal, Signal) {
+        let (dl, dr) = self.read(delay);
+        let write = if self.ping_pong {
+            ((input.0 + input.1) / 2.0 + dr * feedback, dl * feedback)
+        } else {
+            (input.0 + dl * feedback, input.1 + dr * feedback)
+        };
+
+        ((dl, dr), write)
+    }
+
+    /// Returns the repeats at levels stepped like the GB's 4-bit channel volume, which are
+    /// `feedback^(n-1)` of the n-th repeat rounded to 0..15, and the signal fed back into
+    /// the line. As many repeats as the line holds are tapped at their own levels, and the
+    /// line feeds back after them with the unrounded level.
+    fn repeat_quantized(&self, input: Signal, delay: usize, feedback: f64) -> (Signal, Signal) {
+        let taps = ((self.buffer.len() - 1) / delay).clamp(1, MAX_TAPS);
+
+        let mut repeats = (0.0, 0.0);
+        let mut level: f64 = 1.0;
+        for n in 1..=taps {
+            let (dl, dr) = self.read(n * delay);
+            let gain = (level * 15.0).round() / 15.0;
+            if self.ping_pong && n % 2 == 0 {
+                repeats = (repeats.0 + dr * gain, repeats.1 + dl * gain);
+            } else {
+                repeats = (repeats.0 + dl * gain, repeats.1 + dr * gain);
+            }
+            level *= feedback;
+        }
+
+        let (dl, dr) = self.read(taps * delay);
+        let write = if self.ping_pong {
+            // odd taps have bounced the repeats to the other side
+            let (dl, dr) = if taps % 2 == 1 { (dr, dl) } else { (dl, dr) };
+            ((input.0 + input.1) / 2.0 + dl * level, dr * level)
+        } else {
+            (input.0 + dl * level, input.1 + dr * level)
+        };
+
+        (repeats, write)
+    }
+
+    pub fn process(&mut self, sample_rate: f64, input: Signal) -> Signal {
+        if self.mix == 0.0 || self.buffer.is_empty() {
+            return input;
+        }
+
+        let len = self.buffer.len();
+        let delay = ((self.interval() * sample_rate) as usize).clamp(1, len - 1);
+        let feedback = self.feedback / 100.0;
+
+        let ((rl, rr), (wl, wr)) = if self.quantize {
+            self.repeat_quantized(input, delay, feedback)
+        } else {
+            self.repeat(input, delay, feedback)
+        };
+
+        self.buffer[self.position] = (f64_utils::normalize(wl), f64_utils::normalize(wr));
+        self.position = (self.position + 1) % len;
+
+        let mix = self.mix / 100.0;
+        (input.0 + rl * mix, input.1 + rr * mix)
+    }
+}
+
+impl Triggered for Delay {
+    fn trigger(&mut self, event: &Event) {
+        if let Event::Tempo { bpm } = event {
+            self.tempo = *bpm;
+        }
+    }
+}
+
+impl Parametric<SoyBoyParameter> for Delay {
+    fn set_param(&mut self, param: &SoyBoyParameter, _param_def: &ParameterDef, value: f64) {
+        match param {
+            SoyBoyParameter::DelayMix => self.mix = value,
+            SoyBoyParameter::DelayTime => self.time = value,
+            SoyBoyParameter::DelaySync => {
+                if let Ok(mode) = TimeMode::try_from(value as u32) {
+                    self.sync = mode;
+                }
+            }
+            SoyBoyParameter::DelayDivision => {
+                if let Ok(division) = NoteDivision::try_from(value as u32) {
+                    self.division = division;
+                }
+            }
+            SoyBoyParameter::DelayFeedback => self.feedback = value,
+            SoyBoyParameter::DelayPingPong => self.ping_pong = value as u32 != 0,
+            SoyBoyParameter::DelayQuantize => self.quantize = value as u32 != 0,
+            _ => (),
+        }
+    }
+
+    fn get_param(&self, param: &SoyBoyParameter) -> f64 {
+        match param {
+            SoyBoyParameter::DelayMix => self.mix,
+            SoyBoyParameter::DelayTime => self.time,
+            SoyBoyParameter::DelaySync => (self.sync as u32).into(),
+            SoyBoyParameter::DelayDivision => (self.division as u32).into(),
+            SoyBoyParameter::DelayFeedback => self.feedback,
+            SoyBoyParameter::DelayPingPong => (self.ping_pong as u32).into(),
+            SoyBoyParameter::DelayQuantize => (self.quantize as u32).into(),
+            _ => 0.0,
+        }
+    }
+}
@@ -1,15 +1,117 @@
 use std::convert::TryFrom;
 
+use rand::prelude::*;
+
 use crate::{
     common::f64_utils,
     soyboy::{
         event::{Event, Triggered},
         parameters::{ParameterDef, Parametric, SoyBoyParameter},
+        tempo::{NoteDivision, TimeMode, DEFAULT_TEMPO},
         types::AudioProcessor,
-        utils::{discrete_loudness, linear},
+        utils::{discrete_loudness, exponential, linear, logarithmic},
     },
 };
 
+/// NRx2's envelope steps in 1/64 s units.
+const HW_ENVELOPE_FREQUENCY: f64 = 64.0;
+const HW_MAX_VOLUME: u32 = 15;
+
+#[derive(Debug, Copy, Clone)]
+enum EnvelopeMode {
+    Adsr = 0,
+    Hardware,
+}
+
+impl TryFrom<u32> for EnvelopeMode {
+    type Error = ();
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        if id == EnvelopeMode::Adsr as u32 {
+            Ok(EnvelopeMode::Adsr)
+        } else if id == EnvelopeMode::Hardware as u32 {
+            Ok(EnvelopeMode::Hardware)
+        } else {
+            Err(())
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum CurveType {
+    Linear = 0,
+    Exponential,
+    Logarithmic,
+}
+
+impl TryFrom<u32> for CurveType {
+    type Error = ();
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        if id == CurveType::Linear as u32 {
+            Ok(CurveType::Linear)
+        } else if id == CurveType::Exponential as u32 {
+            Ok(CurveType::Exponential)
+        } else if id == CurveType::Logarithmic as u32 {
+            Ok(CurveType::Logarithmic)
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// The shape of an envelope segment.
+#[derive(Debug, Copy, Clone)]
+struct Curve {
+    r#type: CurveType,
+    tension: f64,
+}
+
+impl Curve {
+    fn new() -> Self {
+        Curve {
+            r#type: CurveType::Linear,
+            tension: 4.0,
+        }
+    }
+
+    /// Maps the segment's progress `x` in 0..1 to the level in 0..1.
+    fn shape(&self, x: f64) -> f64 {
+        let x = x.clamp(0.0, 1.0);
+        match self.r#type {
+            CurveType::Linear => x,
+            CurveType::Exponential => exponential(x, self.tension),
+            CurveType::Logarithmic => logarithmic(x, self.tension),
+        }
+    }
+
+    /// For falling segments. Exponential curves fall fast and have long tails here,
+    /// which the 4-bit quantization turns into stepped percussive decays.
+    fn fall(&self, x: f64) -> f64 {
+        self.shape(1.0 - x.clamp(0.0, 1.0))
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum HwDirection {
+    Down = 0,
+    Up,
+}
+
+impl TryFrom<u32> for HwDirection {
+    type Error = ();
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        if id == HwDirection::Down as u32 {
+            Ok(HwDirection::Down)
+        } else if id == HwDirection::Up as u32 {
+            Ok(HwDirection::Up)
+        } else {
+            Err(())
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 enum StartTiming {
     NoteOff = 0,
@@ -33,6 +135,7 @@ impl TryFrom<u32> for StartTiming {
 #[derive(Debug)]
 pub enum EnvelopeState {
     Attack,
+    Hold,
     Decay,
     Sustain,
     Release,
@@ -40,13 +143,30 @@ pub enum EnvelopeState {
 }
 
 pub struct EnvelopeGenerator {
+    mode: EnvelopeMode,
+    hw_volume: u32,
+    hw_direction: HwDirection,
+    hw_period: u32,
     attack: f64,
+    attack_curve: Curve,
+    attack_scale: f64,
+    hold: f64,
     decay: f64,
+    decay_curve: Curve,
     sustain: f64,
     release: f64,
+    release_curve: Curve,
+    loop_enabled: bool,
     stutter_time: f64,
     stutter_depth: f64,
     stutter_when: StartTiming,
+    stutter_sync: TimeMode,
+    stutter_division: NoteDivision,
+    stutter_count: u32,
+    stutter_pitch: i16,
+    stutter_ping_pong: bool,
+    stutter_probability: f64,
+    tempo: f64,
 
     velocity: f64,
     note: u16,
@@ -54,23 +174,46 @@ pub struct EnvelopeGenerator {
     elapsed_samples: u64,
     last_value: f64,
     last_state_value: f64,
+    // the level which the attack rises from, which is above 0 in the loop
+    attack_start: f64,
 
     note_on: bool,
     stuttering: bool,
     stuttering_samples: u64,
     stutter_velocity: f64,
+    stutter_scale: f64,
+    stutter_time_modulation: f64,
+    stutter_repeats: u32,
+    stutter_muted: bool,
 }
 
 impl EnvelopeGenerator {
     pub fn new() -> EnvelopeGenerator {
         EnvelopeGenerator {
+            mode: EnvelopeMode::Adsr,
+            hw_volume: HW_MAX_VOLUME,
+            hw_direction: HwDirection::Down,
+            hw_period: 3,
             attack: 0.05,
+            attack_curve: Curve::new(),
+            attack_scale: 1.0,
+            hold: 0.0,
             decay: 0.05,
+            decay_curve: Curve::new(),
             sustain: 0.3,
             release: 0.1,
+            release_curve: Curve::new(),
+            loop_enabled: false,
             stutter_time: 0.1,
             stutter_depth: 0.0,
             stutter_when: StartTiming::NoteOn,
+            stutter_sync: TimeMode::Free,
+            stutter_division: NoteDivision::Sixteenth,
+            stutter_count: 0,
+            stutter_pitch: 0,
+            stutter_ping_pong: false,
+            stutter_probability: 100.0,
+            tempo: DEFAULT_TEMPO,
 
             velocity: 0.0,
             note: 0,
@@ -78,25 +221,89 @@ impl EnvelopeGenerator {
             elapsed_samples: 1,
             last_value: 0.0,
             last_state_value: 0.0,
+            attack_start: 0.0,
 
             note_on: false,
             stuttering: false,
             stuttering_samples: 0,
             stutter_velocity: 1.0,
+            stutter_scale: 1.0,
+            stutter_time_modulation: 0.0,
+            stutter_repeats: 0,
+            stutter_muted: false,
         }
     }
 
+    fn repeating(&self) -> bool {
+        self.stuttering && self.stutter_depth != 0.0 && self.stutter_repeats > 0
+    }
+
+    /// Returns the transpose in semitones for the current stutter repeat.
+    pub fn stutter_transpose(&self) -> f64 {
+        if self.repeating() {
+            (self.stutter_pitch as i32 * self.stutter_repeats as i32) as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the pan for the current stutter repeat; -1.0 is left and 1.0 is right.
+    /// With ping-pong, repeats alternate left and right like the hardware's NR51 panning.
+    pub fn stutter_pan(&self) -> f64 {
+        if self.stutter_ping_pong && self.repeating() {
+            if self.stutter_repeats % 2 == 1 {
+                -1.0
+            } else {
+                1.0
+            }
+        } else {
+            0.0
+        }
+    }
+
+    /// Sets the velocity's modulation for the next note as ratios to the attack time
+    /// and the stutter depth.
+    pub fn set_velocity_modulation(&mut self, attack_scale: f64, stutter_scale: f64) {
+        self.attack_scale = attack_scale;
+        self.stutter_scale = stutter_scale;
+    }
+
+    /// Sets the stutter time modulation in octaves. Positive values make stutters faster.
+    pub fn set_stutter_time_modulation(&mut self, octaves: f64) {
+        self.stutter_time_modulation = octaves;
+    }
+
+    fn attack_time(&self) -> f64 {
+        self.attack * self.attack_scale
+    }
+
+    /// The loop restarts the attack after the decay while the key is held.
+    /// Stutter wins over the loop since it retriggers the attack by itself.
+    fn looping(&self) -> bool {
+        self.loop_enabled && self.note_on && !(self.stuttering && self.stutter_depth != 0.0)
+    }
+
+    pub fn note(&self) -> u16 {
+        self.note
+    }
+
     pub fn same_note(&self, note: u16) -> bool {
         self.note == note
     }
 
+    /// Returns whether the key is held.
+    pub fn held(&self) -> bool {
+        self.note_on
+    }
+
+    /// Returns whether the envelope makes any sound.
+    pub fn active(&self) -> bool {
+        !matches!(self.state, EnvelopeState::Off)
+    }
+
     pub fn assignable(&self, note: u16) -> bool {
         let same_note = self.same_note(note);
-        let silent = match self.state {
-            EnvelopeState::Release => true,
-            EnvelopeState::Off => true,
-            _ => false,
-        };
+        let silent = matches!(self.state, EnvelopeState::Release | EnvelopeState::Off);
 
         match self.stutter_when {
             StartTiming::NoteOn => same_note || !self.note_on,
@@ -104,28 +311,50 @@ impl EnvelopeGenerator {
         }
     }
 
+    /// Silences the note at once, including its stutter.
+    pub fn cut(&mut self) {
+        self.note_on = false;
+        self.stuttering = false;
+        self.stutter_repeats = 0;
+        self.set_state(EnvelopeState::Off);
+    }
+
     pub fn set_state(&mut self, state: EnvelopeState) {
         match self.state {
             EnvelopeState::Attack => self.last_state_value = self.last_value,
+            EnvelopeState::Hold => self.last_state_value = self.last_value,
             EnvelopeState::Decay => self.last_state_value = self.last_value,
             EnvelopeState::Sustain => self.last_state_value = self.last_value,
             _ => (),
         }
         self.state = state;
         self.elapsed_samples = 0;
+        self.attack_start = 0.0;
     }
 
     fn update_state(&mut self, s: f64) {
         match self.state {
             EnvelopeState::Attack => {
-                if s > self.attack {
+                if s > self.attack_time() {
+                    self.set_state(EnvelopeState::Hold);
+                    self.last_state_value = 1.0;
+                }
+            }
+            EnvelopeState::Hold => {
+                if s > self.hold {
                     self.set_state(EnvelopeState::Decay);
                     self.last_state_value = 1.0;
                 }
             }
             EnvelopeState::Decay => {
                 if s > self.decay {
-                    self.set_state(EnvelopeState::Sustain);
+                    if self.looping() {
+                        // rise from the decayed level not to click at the loop point
+                        self.set_state(EnvelopeState::Attack);
+                        self.attack_start = self.last_state_value;
+                    } else {
+                        self.set_state(EnvelopeState::Sustain);
+                    }
                 }
             }
             EnvelopeState::Sustain => (),
@@ -140,35 +369,77 @@ impl EnvelopeGenerator {
 
     fn calculate(&mut self, s: f64) -> f64 {
         match self.state {
-            EnvelopeState::Attack => linear(s, 1.0 / self.attack),
+            EnvelopeState::Attack => {
+                let start = self.attack_start;
+                let rise = self.attack_curve.shape(linear(s, 1.0 / self.attack_time()));
+                start + (1.0 - start) * rise
+            }
+            EnvelopeState::Hold => 1.0,
             EnvelopeState::Decay => {
                 let sustain = f64_utils::normalize(self.sustain);
                 let max = self.last_state_value - sustain;
-                self.last_state_value - max * linear(s, 1.0 / self.decay)
+                sustain + max * self.decay_curve.fall(linear(s, 1.0 / self.decay))
             }
             EnvelopeState::Sustain => f64_utils::normalize(self.sustain),
             EnvelopeState::Release => {
                 let max = self.last_state_value;
-                max - max * linear(s, 1.0 / self.release)
+                max * self.release_curve.fall(linear(s, 1.0 / self.release))
             }
             EnvelopeState::Off => 0.0,
         }
     }
 
+    /// Emulates the GB's volume envelope (NRx2): the initial volume steps up or down
+    /// every `hw_period`/64 s and stays there. Velocity scales the initial volume.
+    fn hardware_level(&self, sec: f64) -> f64 {
+        if let EnvelopeState::Off = self.state {
+            return 0.0;
+        }
+
+        let initial = (self.hw_volume as f64 * self.velocity).round() as u32;
+        let volume = if self.hw_period == 0 {
+            initial
+        } else {
+            let steps = (sec * HW_ENVELOPE_FREQUENCY / self.hw_period as f64) as u32;
+            match self.hw_direction {
+                HwDirection::Down => initial.saturating_sub(steps),
+                HwDirection::Up => (initial + steps).min(HW_MAX_VOLUME),
+            }
+        };
+
+        volume as f64 / HW_MAX_VOLUME as f64
+    }
+
+    /// Returns the time between stutters in seconds.
+    /// In sync mode it follows the current tempo so it changes with the host's tempo.
+    fn stutter_interval(&self) -> f64 {
+        let interval = match self.stutter_sync {
+            TimeMode::Free => self.stutter_time,
+            TimeMode::Sync => self.stutter_division.seconds(self.tempo),
+        };
+        interval / 2.0f64.powf(self.stutter_time_modulation)
+    }
+
     fn stutter(&mut self, sample_rate: f64) {
         if self.stuttering {
             self.stuttering_samples += 1;
             let elapsed_sec = self.stuttering_samples as f64 / sample_rate;
 
-            if self.stutter_depth != 0.0 && elapsed_sec > self.stutter_time {
-                self.stutter_velocity -= 1.0 - self.stutter_depth / 100.0;
+            if self.stutter_depth != 0.0 && elapsed_sec > self.stutter_interval() {
+                self.stutter_velocity -= 1.0 - self.stutter_depth * self.stutter_scale / 100.0;
                 self.stuttering_samples = 0;
+                self.stutter_repeats += 1;
 
-                if self.stutter_velocity > 0.05 {
+                let exhausted =
+                    self.stutter_count != 0 && self.stutter_repeats > self.stutter_count;
+                if self.stutter_velocity > 0.05 && !exhausted {
+                    self.stutter_muted = random::<f64>() * 100.0 >= self.stutter_probability;
                     self.set_state(EnvelopeState::Attack);
                 } else {
                     self.set_state(EnvelopeState::Off);
                     self.stutter_velocity = 0.0;
+                    self.stutter_repeats = 0;
+                    self.stutter_muted = false;
                     self.stuttering = false;
                 }
             }
@@ -176,6 +447,9 @@ impl EnvelopeGenerator {
     }
 
     fn start_stutter(&mut self, note_on: bool) {
+        self.stutter_repeats = 0;
+        self.stutter_muted = false;
+
         if self.stutter_depth == 0.0 {
             self.stuttering = false;
             self.stutter_velocity = 0.0;
@@ -211,16 +485,26 @@ impl AudioProcessor<f64> for EnvelopeGenerator {
         let sec = self.elapsed_samples as f64 / sample_rate;
 
         self.stutter(sample_rate);
-        self.update_state(sec);
-        let v = self.calculate(sec);
-        let v = f64_utils::normalize(v);
-        self.last_value = v;
+        let v = match self.mode {
+            EnvelopeMode::Adsr => {
+                self.update_state(sec);
+                // a new state starts from its beginning
+                let sec = self.elapsed_samples as f64 / sample_rate;
+                let v = self.calculate(sec);
+                let v = f64_utils::normalize(v);
+                self.last_value = v;
+                discrete_loudness(v) * self.velocity
+            }
+            EnvelopeMode::Hardware => self.hardware_level(sec),
+        };
         self.elapsed_samples += 1;
 
-        if self.stuttering && self.stutter_depth != 0.0 {
-            discrete_loudness(v) * self.stutter_velocity * self.velocity
+        if self.stuttering && self.stutter_depth != 0.0 && self.stutter_muted {
+            0.0
+        } else if self.stuttering && self.stutter_depth != 0.0 {
+            v * self.stutter_velocity
         } else {
-            discrete_loudness(v) * self.velocity
+            v
         }
     }
 
@@ -230,22 +514,25 @@ impl AudioProcessor<f64> for EnvelopeGenerator {
 impl Triggered for EnvelopeGenerator {
     fn trigger(&mut self, event: &Event) {
         match event {
-            Event::NoteOn { note, velocity } => {
+            Event::NoteOn { note, velocity, .. } => {
                 self.note_on = true;
                 self.note = *note;
                 self.set_state(EnvelopeState::Attack);
                 self.velocity = *velocity;
                 self.start_stutter(true);
             }
-            Event::NoteOff { note } => {
-                if *note == self.note {
-                    self.note_on = false;
-                    self.set_state(EnvelopeState::Release);
-                    if let StartTiming::NoteOff = self.stutter_when {
-                        self.start_stutter(false);
-                    }
+            Event::NoteOff { note, .. } if *note == self.note => {
+                self.note_on = false;
+                match self.mode {
+                    EnvelopeMode::Adsr => self.set_state(EnvelopeState::Release),
+                    // GB drivers cut notes by writing 0 to NRx2 since there is no release
+                    EnvelopeMode::Hardware => self.set_state(EnvelopeState::Off),
+                }
+                if let StartTiming::NoteOff = self.stutter_when {
+                    self.start_stutter(false);
                 }
             }
+            Event::Tempo { bpm } => self.tempo = *bpm,
             _ => (),
         }
     }
@@ -254,10 +541,42 @@ impl Triggered for EnvelopeGenerator {
 impl Parametric<SoyBoyParameter> for EnvelopeGenerator {
     fn set_param(&mut self, param: &SoyBoyParameter, _param_def: &ParameterDef, value: f64) {
         match param {
+            SoyBoyParameter::EgMode => {
+                if let Ok(mode) = EnvelopeMode::try_from(value as u32) {
+                    self.mode = mode;
+                }
+            }
+            SoyBoyParameter::EgHwVolume => self.hw_volume = value as u32,
+            SoyBoyParameter::EgHwDirection => {
+                if let Ok(direction) = HwDirection::try_from(value as u32) {
+                    self.hw_direction = direction;
+                }
+            }
+            SoyBoyParameter::EgHwPeriod => self.hw_period = value as u32,
             SoyBoyParameter::EgAttack => self.attack = value,
             SoyBoyParameter::EgDecay => self.decay = value,
             SoyBoyParameter::EgSustain => self.sustain = value,
             SoyBoyParameter::EgRelease => self.release = value,
+            SoyBoyParameter::EgHold => self.hold = value,
+            SoyBoyParameter::EgLoop => self.loop_enabled = value as u32 != 0,
+            SoyBoyParameter::EgAttackCurve => {
+                if let Ok(r#type) = CurveType::try_from(value as u32) {
+                    self.attack_curve.r#type = r#type;
+                }
+            }
+            SoyBoyParameter::EgAttackTension => self.attack_curve.tension = value,
+            SoyBoyParameter::EgDecayCurve => {
+                if let Ok(r#type) = CurveType::try_from(value as u32) {
+                    self.decay_curve.r#type = r#type;
+                }
+            }
+            SoyBoyParameter::EgDecayTension => self.decay_curve.tension = value,
+            SoyBoyParameter::EgReleaseCurve => {
+                if let Ok(r#type) = CurveType::try_from(value as u32) {
+                    self.release_curve.r#type = r#type;
+                }
+            }
+            SoyBoyParameter::EgReleaseTension => self.release_curve.tension = value,
             SoyBoyParameter::StutterTime => self.stutter_time = value,
             SoyBoyParameter::StutterDepth => self.stutter_depth = value,
             SoyBoyParameter::StutterWhen => {
@@ -265,20 +584,91 @@ impl Parametric<SoyBoyParameter> for EnvelopeGenerator {
                     self.stutter_when = when;
                 }
             }
+            SoyBoyParameter::StutterSync => {
+                if let Ok(mode) = TimeMode::try_from(value as u32) {
+                    self.stutter_sync = mode;
+                }
+            }
+            SoyBoyParameter::StutterDivision => {
+                if let Ok(division) = NoteDivision::try_from(value as u32) {
+                    self.stutter_division = division;
+                }
+            }
+            SoyBoyParameter::StutterCount => self.stutter_count = value as u32,
+            SoyBoyParameter::StutterPitch => self.stutter_pitch = value as i16,
+            SoyBoyParameter::StutterPingPong => self.stutter_ping_pong = value as u32 != 0,
+            SoyBoyParameter::StutterProbability => self.stutter_probability = value,
             _ => (),
         }
     }
 
     fn get_param(&self, param: &SoyBoyParameter) -> f64 {
         match param {
+            SoyBoyParameter::EgMode => (self.mode as u32).into(),
+            SoyBoyParameter::EgHwVolume => self.hw_volume as f64,
+            SoyBoyParameter::EgHwDirection => (self.hw_direction as u32).into(),
+            SoyBoyParameter::EgHwPeriod => self.hw_period as f64,
             SoyBoyParameter::EgAttack => self.attack,
             SoyBoyParameter::EgDecay => self.decay,
             SoyBoyParameter::EgSustain => self.sustain,
             SoyBoyParameter::EgRelease => self.release,
+            SoyBoyParameter::EgHold => self.hold,
+            SoyBoyParameter::EgLoop => (self.loop_enabled as u32).into(),
+            SoyBoyParameter::EgAttackCurve => (self.attack_curve.r#type as u32).into(),
+            SoyBoyParameter::EgAttackTension => self.attack_curve.tension,
+            SoyBoyParameter::EgDecayCurve => (self.decay_curve.r#type as u32).into(),
+            SoyBoyParameter::EgDecayTension => self.decay_curve.tension,
+            SoyBoyParameter::EgReleaseCurve => (self.release_curve.r#type as u32).into(),
+            SoyBoyParameter::EgReleaseTension => self.release_curve.tension,
             SoyBoyParameter::StutterTime => self.stutter_time,
             SoyBoyParameter::StutterDepth => self.stutter_depth,
             SoyBoyParameter::StutterWhen => (self.stutter_when as u32).into(),
+            SoyBoyParameter::StutterSync => (self.stutter_sync as u32).into(),
+            SoyBoyParameter::StutterDivision => (self.stutter_division as u32).into(),
+            SoyBoyParameter::StutterCount => self.stutter_count as f64,
+            SoyBoyParameter::StutterPitch => self.stutter_pitch as f64,
+            SoyBoyParameter::StutterPingPong => (self.stutter_ping_pong as u32).into(),
+            SoyBoyParameter::StutterProbability => self.stutter_probability,
             _ => 0.0,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{EnvelopeGenerator, EnvelopeState};
+    use crate::soyboy::{
+        event::{Event, Triggered},
+        types::AudioProcessor,
+    };
+
+    #[test]
+    fn test_loop_keeps_level_at_loop_point() {
+        let sample_rate = 1000.0;
+        let mut eg = EnvelopeGenerator::new();
+        eg.attack = 0.01;
+        eg.decay = 0.01;
+        eg.sustain = 0.5;
+        eg.loop_enabled = true;
+        eg.trigger(&Event::NoteOn {
+            note: 60,
+            velocity: 1.0,
+            channel: 0,
+            note_id: -1,
+        });
+
+        let mut loops = 0;
+        let mut decaying = false;
+        let mut last = 0.0;
+        for _ in 0..100 {
+            eg.process(sample_rate);
+            if decaying && matches!(eg.state, EnvelopeState::Attack) {
+                assert!((eg.last_value - last).abs() < 0.1);
+                loops += 1;
+            }
+            decaying = matches!(eg.state, EnvelopeState::Decay);
+            last = eg.last_value;
+        }
+        assert!(loops > 0);
+    }
+}
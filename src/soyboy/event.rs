@@ -1,13 +1,60 @@
 use crate::common::i4;
 
+/// The note ID of notes which the host does not identify.
+pub const NO_NOTE_ID: i32 = -1;
+
+/// The per-note expressions which the host sends with note IDs.
+/// Their values are normalized in 0.0..1.0 like VST3's note expressions.
+#[derive(Debug, Copy, Clone)]
+pub enum NoteExpression {
+    /// 0.25 is 0dB and 1.0 is +12dB
+    Volume,
+    /// 0.5 is the center
+    Pan,
+    /// 0.5 is no detune and the whole range is -120..120 semitones
+    Tuning,
+    /// 0.5 is neutral
+    Brightness,
+}
+
 pub enum Event {
-    NoteOn { note: u16, velocity: f64 },
-    NoteOff { note: u16 },
-    PitchBend { ratio: f64 },
-    SweepReset { freq: f64 },
-    SetWaveTable { idx: usize, value: i4 },
+    NoteOn {
+        note: u16,
+        velocity: f64,
+        channel: i16,
+        note_id: i32,
+    },
+    NoteOff {
+        note: u16,
+        channel: i16,
+        note_id: i32,
+    },
+    NoteExpression {
+        note_id: i32,
+        expression: NoteExpression,
+        value: f64,
+    },
+    PolyPressure {
+        note: u16,
+        channel: i16,
+        note_id: i32,
+        pressure: f64,
+    },
+    PitchBend {
+        ratio: f64,
+    },
+    SweepReset {
+        freq: f64,
+    },
+    SetWaveTable {
+        idx: usize,
+        value: i4,
+    },
     ResetWaveTableAsSine,
     ResetWaveTableAtRandom,
+    Tempo {
+        bpm: f64,
+    },
 }
 
 impl TryFrom<u32> for Event {
@@ -18,8 +65,14 @@ impl TryFrom<u32> for Event {
             0 => Ok(Event::NoteOn {
                 note: 0,
                 velocity: 0.0,
+                channel: 0,
+                note_id: NO_NOTE_ID,
+            }),
+            1 => Ok(Event::NoteOff {
+                note: 0,
+                channel: 0,
+                note_id: NO_NOTE_ID,
             }),
-            1 => Ok(Event::NoteOff { note: 0 }),
             2 => Ok(Event::PitchBend { ratio: 0.0 }),
             3 => Ok(Event::SweepReset { freq: 0.0 }),
             4 => Ok(Event::SetWaveTable {
@@ -28,6 +81,18 @@ impl TryFrom<u32> for Event {
             }),
             5 => Ok(Event::ResetWaveTableAsSine),
             6 => Ok(Event::ResetWaveTableAtRandom),
+            7 => Ok(Event::Tempo { bpm: 0.0 }),
+            8 => Ok(Event::NoteExpression {
+                note_id: NO_NOTE_ID,
+                expression: NoteExpression::Volume,
+                value: 0.0,
+            }),
+            9 => Ok(Event::PolyPressure {
+                note: 0,
+                channel: 0,
+                note_id: NO_NOTE_ID,
+                pressure: 0.0,
+            }),
             _ => Err(()),
         }
     }
@@ -0,0 +1,106 @@
+use crate::{
+    common::f64_utils,
+    soyboy::{
+        event::{Event, Triggered},
+        parameters::{ParameterDef, Parametric, SoyBoyParameter},
+        types::AudioProcessor,
+        utils::linear,
+    },
+};
+
+/// Key tracking is relative to this note (C4).
+const KEY_TRACKING_CENTER_NOTE: i16 = 60;
+/// How much the velocity lowers the cutoff at most.
+const VELOCITY_RANGE_IN_OCTAVES: f64 = 4.0;
+
+/// Modulates the DAC filter's cutoff for each note.
+/// This returns the modulation amount in octaves.
+pub struct FilterEnvelope {
+    amount: f64,
+    attack: f64,
+    decay: f64,
+    key_tracking: f64,
+    velocity_sensitivity: f64,
+
+    note: u16,
+    velocity: f64,
+    elapsed_samples: u64,
+}
+
+impl FilterEnvelope {
+    pub fn new() -> Self {
+        Self {
+            amount: 0.0,
+            attack: 0.0,
+            decay: 0.3,
+            key_tracking: 0.0,
+            velocity_sensitivity: 0.0,
+
+            note: KEY_TRACKING_CENTER_NOTE as u16,
+            velocity: 1.0,
+            elapsed_samples: 0,
+        }
+    }
+
+    /// The envelope rises in `attack` then falls to zero in `decay`, for plucks and basses.
+    fn envelope(&self, sec: f64) -> f64 {
+        if sec < self.attack {
+            linear(sec, 1.0 / self.attack)
+        } else if sec < self.attack + self.decay {
+            1.0 - linear(sec - self.attack, 1.0 / self.decay)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl AudioProcessor<f64> for FilterEnvelope {
+    fn process(&mut self, sample_rate: f64) -> f64 {
+        let sec = self.elapsed_samples as f64 / sample_rate;
+        self.elapsed_samples += 1;
+
+        let env = self.envelope(sec) * self.amount;
+        let key = (self.note as i16 - KEY_TRACKING_CENTER_NOTE) as f64 / 12.0
+            * (self.key_tracking / 100.0);
+        let velocity =
+            (self.velocity - 1.0) * (self.velocity_sensitivity / 100.0) * VELOCITY_RANGE_IN_OCTAVES;
+
+        f64_utils::normalize(env + key + velocity)
+    }
+
+    fn set_freq(&mut self, _freq: f64) {}
+}
+
+impl Triggered for FilterEnvelope {
+    fn trigger(&mut self, event: &Event) {
+        if let Event::NoteOn { note, velocity, .. } = event {
+            self.note = *note;
+            self.velocity = *velocity;
+            self.elapsed_samples = 0;
+        }
+    }
+}
+
+impl Parametric<SoyBoyParameter> for FilterEnvelope {
+    fn set_param(&mut self, param: &SoyBoyParameter, _param_def: &ParameterDef, value: f64) {
+        match param {
+            SoyBoyParameter::FilterEnvAmount => self.amount = value,
+            SoyBoyParameter::FilterEnvAttack => self.attack = value,
+            SoyBoyParameter::FilterEnvDecay => self.decay = value,
+            SoyBoyParameter::FilterKeyTracking => self.key_tracking = value,
+            SoyBoyParameter::FilterVelocity => self.velocity_sensitivity = value,
+            _ => (),
+        }
+    }
+
+    fn get_param(&self, param: &SoyBoyParameter) -> f64 {
+        match param {
+            SoyBoyParameter::FilterEnvAmount => self.amount,
+            SoyBoyParameter::FilterEnvAttack => self.attack,
+            SoyBoyParameter::FilterEnvDecay => self.decay,
+            SoyBoyParameter::FilterKeyTracking => self.key_tracking,
+            SoyBoyParameter::FilterVelocity => self.velocity_sensitivity,
+            _ => 0.0,
+        }
+    }
+}
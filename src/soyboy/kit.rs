@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::{constants, i4},
+    soyboy::{
+        event::{Event, Triggered},
+        parameters::{make_parameter_info, ParameterDef, SoyBoyParameter},
+        patch::Patch,
+        voice::VoiceUnit,
+        wave_table::WaveTableOscillator,
+    },
+};
+
+pub const NUMBER_OF_PADS: usize = 16;
+/// The first pad is mapped to C1, which is the bass drum in GM.
+const FIRST_PAD_NOTE: u16 = 36;
+
+/// Holds a patch for each pad and maps notes to the pads in the drum kit mode.
+/// While the mode is off, the selected pad's patch is played with all notes.
+pub struct Kit {
+    enabled: bool,
+    selected: usize,
+    pads: Vec<Patch>,
+
+    param_defs: HashMap<SoyBoyParameter, ParameterDef>,
+    wavetable_editor: WaveTableOscillator,
+}
+
+impl Kit {
+    pub fn new() -> Self {
+        let param_defs = make_parameter_info();
+        let pads = (0..NUMBER_OF_PADS)
+            .map(|i| {
+                let mut pad = Patch::new(&param_defs);
+                let note = (FIRST_PAD_NOTE + i as u16) as f64;
+                pad.set(&SoyBoyParameter::KitNoteLow, note);
+                pad.set(&SoyBoyParameter::KitNoteHigh, note);
+                pad
+            })
+            .collect();
+
+        Self {
+            enabled: false,
+            selected: 0,
+            pads,
+
+            param_defs,
+            wavetable_editor: WaveTableOscillator::new(),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn select(&mut self, pad: usize) {
+        self.selected = pad.min(NUMBER_OF_PADS - 1);
+    }
+
+    /// Returns the selected pad's value of `param`.
+    pub fn get(&self, param: &SoyBoyParameter) -> f64 {
+        self.pads[self.selected].get(param)
+    }
+
+    /// Sets `value` of `param` to the selected pad.
+    pub fn set(&mut self, param: &SoyBoyParameter, value: f64) {
+        self.pads[self.selected].set(param, value);
+    }
+
+    pub fn wavetable(&self) -> [i4; constants::WAVETABLE_SIZE] {
+        self.pads[self.selected].wavetable()
+    }
+
+    pub fn set_wavetable(&mut self, wavetable: &[i4; constants::WAVETABLE_SIZE]) {
+        self.pads[self.selected].set_wavetable(wavetable);
+    }
+
+    pub fn pad_wavetable(&self, pad: usize) -> [i4; constants::WAVETABLE_SIZE] {
+        self.pads[pad.min(NUMBER_OF_PADS - 1)].wavetable()
+    }
+
+    /// Returns the first pad whose note range contains `note`.
+    pub fn pad_for_note(&self, note: u16) -> Option<usize> {
+        let note = note as f64;
+        self.pads.iter().position(|pad| {
+            let low = pad.get(&SoyBoyParameter::KitNoteLow);
+            let high = pad.get(&SoyBoyParameter::KitNoteHigh);
+            low <= note && note <= high
+        })
+    }
+
+    /// Returns the pad's choke group. 0 means the pad chokes nothing.
+    pub fn choke_group(&self, pad: usize) -> u32 {
+        self.pads[pad].get(&SoyBoyParameter::KitChokeGroup) as u32
+    }
+
+    /// Returns the note to play if the pad has a fixed pitch.
+    pub fn fixed_note(&self, pad: usize) -> Option<u16> {
+        let pad = &self.pads[pad];
+        if pad.get(&SoyBoyParameter::KitPitchMode) as u32 != 0 {
+            Some(pad.get(&SoyBoyParameter::KitFixedNote) as u16)
+        } else {
+            None
+        }
+    }
+
+    /// Sets the pad's patch to `voice`.
+    pub fn apply(&self, pad: usize, voice: &mut VoiceUnit) {
+        self.pads[pad].apply(voice, &self.param_defs);
+    }
+
+    pub fn get_params(&self) -> Vec<Vec<f64>> {
+        self.pads.iter().map(|pad| pad.params().to_vec()).collect()
+    }
+
+    pub fn get_wavetables(&self) -> Vec<[i4; constants::WAVETABLE_SIZE]> {
+        self.pads.iter().map(|pad| pad.wavetable()).collect()
+    }
+
+    /// Restores the pads from a plugin state. Pads missing in the state are left as they are.
+    pub fn set_kit(&mut self, params: &[Vec<f64>], wavetables: &[[i4; constants::WAVETABLE_SIZE]]) {
+        for (pad, values) in self.pads.iter_mut().zip(params.iter()) {
+            pad.restore(values);
+        }
+        for (pad, wavetable) in self.pads.iter_mut().zip(wavetables.iter()) {
+            pad.set_wavetable(wavetable);
+        }
+    }
+}
+
+impl Triggered for Kit {
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::SetWaveTable { .. }
+            | Event::ResetWaveTableAsSine
+            | Event::ResetWaveTableAtRandom => {
+                self.pads[self.selected].edit_wavetable(event, &mut self.wavetable_editor);
+            }
+            _ => (),
+        }
+    }
+}
@@ -1,12 +1,25 @@
+mod bitcrusher;
+mod clipper;
 mod dac;
+mod delay;
 mod envelope_generator;
+mod filter_envelope;
+mod kit;
+mod modulation;
+pub mod mpe;
 mod noise;
+mod parts;
+mod patch;
+mod smoother;
 mod square_wave;
 mod sweep;
+mod tempo;
 mod types;
 mod utils;
+mod velocity;
 mod voice;
 mod wave_table;
+mod zones;
 
 pub mod event;
 pub mod parameters;
@@ -17,19 +30,49 @@ pub use types::*;
 use crate::{
     common::{constants, i4},
     soyboy::{
-        event::{Event, Triggered},
+        bitcrusher::BitCrusher,
+        clipper::Clipper,
+        delay::Delay,
+        event::{Event, Triggered, NO_NOTE_ID},
+        kit::Kit,
+        mpe::{Mpe, MpeDimension},
+        parts::{Parts, NUMBER_OF_PARTS},
+        patch::is_patch_parameter,
+        smoother::Smoother,
         utils::level,
         voice::VoiceUnit,
+        zones::{is_zone_parameter, SplitZones, ZoneSettings},
     },
 };
 
 pub type Signal = (f64, f64);
 
+/// Finds a voice for the note. When all voices are busy, a note kept only by the pedals
+/// is taken over.
+fn find_voice(voices: &mut [VoiceUnit], note: u16) -> Option<&mut VoiceUnit> {
+    let index = voices
+        .iter()
+        .position(|v| v.assignable(note))
+        .or_else(|| voices.iter().position(|v| v.sustained()))?;
+    Some(&mut voices[index])
+}
+
 pub struct SoyBoy {
     voices: Vec<VoiceUnit>,
+    bitcrusher: BitCrusher,
+    delay: Delay,
+    clipper: Clipper,
+    kit: Kit,
+    parts: Parts,
+    zones: SplitZones,
 
     num_voices: usize,
+    headroom: bool,
     master_volume: f64,
+    master_gain: Smoother,
+    sustain: bool,
+    sostenuto: bool,
+    mpe: Mpe,
 }
 
 impl SoyBoy {
@@ -41,9 +84,20 @@ impl SoyBoy {
 
         Self {
             voices,
+            bitcrusher: BitCrusher::new(),
+            delay: Delay::new(),
+            clipper: Clipper::new(),
+            kit: Kit::new(),
+            parts: Parts::new(),
+            zones: SplitZones::new(),
 
             num_voices: 4,
+            headroom: true,
             master_volume: 1.0,
+            master_gain: Smoother::new(level(1.0)),
+            sustain: false,
+            sostenuto: false,
+            mpe: Mpe::new(),
         }
     }
 
@@ -51,31 +105,313 @@ impl SoyBoy {
         &mut self.voices[0..self.num_voices]
     }
 
+    pub fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.delay.set_sample_rate(sample_rate);
+    }
+
+    pub fn get_tail_samples(&self) -> u32 {
+        self.delay.tail_samples()
+    }
+
+    pub fn take_clipped(&mut self) -> bool {
+        self.clipper.take_clipped()
+    }
+
     pub fn get_wavetable(&self) -> [i4; constants::WAVETABLE_SIZE] {
-        self.voices[0].get_wavetable()
+        if self.parts.enabled() {
+            self.parts.wavetable()
+        } else {
+            self.kit.wavetable()
+        }
     }
 
     pub fn set_wavetable(&mut self, wavetable: &[i4; constants::WAVETABLE_SIZE]) {
-        self.get_voices()
+        if self.parts.enabled() {
+            self.parts.set_wavetable(wavetable);
+            let part = self.parts.selected();
+            self.voices
+                .iter_mut()
+                .filter(|v| v.part() == part)
+                .for_each(|v| v.set_wavetable(wavetable));
+        } else {
+            self.kit.set_wavetable(wavetable);
+            if self.plays_single_patch() {
+                self.voices
+                    .iter_mut()
+                    .for_each(|v| v.set_wavetable(wavetable));
+            }
+        }
+    }
+
+    pub fn get_kit_params(&self) -> Vec<Vec<f64>> {
+        self.kit.get_params()
+    }
+
+    pub fn get_kit_wavetables(&self) -> Vec<[i4; constants::WAVETABLE_SIZE]> {
+        self.kit.get_wavetables()
+    }
+
+    pub fn set_kit(&mut self, params: &[Vec<f64>], wavetables: &[[i4; constants::WAVETABLE_SIZE]]) {
+        self.kit.set_kit(params, wavetables);
+        if !self.kit.enabled() {
+            self.apply_selected_pad();
+        }
+    }
+
+    pub fn get_parts_params(&self) -> Vec<Vec<f64>> {
+        self.parts.get_params()
+    }
+
+    pub fn get_parts_wavetables(&self) -> Vec<[i4; constants::WAVETABLE_SIZE]> {
+        self.parts.get_wavetables()
+    }
+
+    pub fn set_parts(
+        &mut self,
+        params: &[Vec<f64>],
+        wavetables: &[[i4; constants::WAVETABLE_SIZE]],
+    ) {
+        self.parts.set_parts(params, wavetables);
+    }
+
+    fn set_sustain(&mut self, sustain: bool) {
+        self.sustain = sustain;
+        self.release_pedaled_notes();
+    }
+
+    /// The sostenuto pedal keeps only the notes held when it is pressed.
+    fn set_sostenuto(&mut self, sostenuto: bool) {
+        if sostenuto && !self.sostenuto {
+            self.voices
+                .iter_mut()
+                .filter(|v| v.held() && !v.sustained())
+                .for_each(|v| v.set_sostenuto(true));
+        } else if !sostenuto {
+            self.voices.iter_mut().for_each(|v| v.set_sostenuto(false));
+        }
+        self.sostenuto = sostenuto;
+        self.release_pedaled_notes();
+    }
+
+    fn release_pedaled_notes(&mut self) {
+        if self.sustain {
+            return;
+        }
+
+        self.voices
             .iter_mut()
-            .for_each(|v| v.set_wavetable(wavetable));
+            .filter(|v| v.sustained() && !v.sostenuto())
+            .for_each(|v| v.release_sustained());
+    }
+
+    /// Sets an MPE member channel's controller to the voices playing the channel's notes.
+    /// `value` is normalized like MIDI controllers.
+    pub fn set_expression(&mut self, channel: i16, dimension: MpeDimension, value: f64) {
+        if let Some(expression) = self.mpe.set(channel, dimension, value) {
+            let bend_range = self.mpe.bend_range();
+            self.voices
+                .iter_mut()
+                .filter(|v| v.channel() == channel && v.active())
+                .for_each(|v| v.set_expression(&expression, bend_range));
+        }
+    }
+
+    /// Returns whether all voices play one patch, that is, neither the drum kit mode
+    /// nor the multitimbral mode is on.
+    fn plays_single_patch(&self) -> bool {
+        !self.kit.enabled() && !self.parts.enabled()
+    }
+
+    /// Out of the drum kit mode, all voices play the selected pad's patch.
+    fn apply_selected_pad(&mut self) {
+        let pad = self.kit.selected();
+        for voice in self.voices.iter_mut() {
+            self.kit.apply(pad, voice);
+            voice.set_kit_pad(None, 0);
+        }
+    }
+
+    fn trigger_kit(&mut self, note: u16, zone: Option<ZoneSettings>, event: &Event) {
+        let pad = match self.kit.pad_for_note(note) {
+            Some(pad) => pad,
+            None => return,
+        };
+
+        let choke_group = self.kit.choke_group(pad);
+        let voices = &mut self.voices[0..self.num_voices];
+        if choke_group != 0 {
+            voices
+                .iter_mut()
+                .filter(|v| v.choke_group() == choke_group)
+                .for_each(|v| v.choke());
+        }
+
+        if let Some(voice) = find_voice(voices, note) {
+            self.kit.apply(pad, voice);
+            voice.set_kit_pad(self.kit.fixed_note(pad), choke_group);
+            voice.set_zone(zone);
+            voice.trigger(event);
+        }
+    }
+
+    /// Plays a note on the part in the multitimbral mode. All voices are shared by the parts
+    /// and the part's voice limit decides when its own voices are stolen.
+    fn trigger_part(&mut self, part: usize, note: u16, zone: Option<ZoneSettings>, event: &Event) {
+        let voices = &self.voices;
+        let playing = voices
+            .iter()
+            .filter(|v| v.part() == part && v.active())
+            .count();
+
+        let index = if playing < self.parts.voice_limit(part) {
+            // other parts' voices are free once their keys are released
+            voices.iter().position(|v| {
+                if v.part() == part {
+                    v.assignable(note)
+                } else {
+                    !v.held() || v.sustained()
+                }
+            })
+        } else {
+            voices
+                .iter()
+                .position(|v| v.part() == part && v.active() && v.same_note(note))
+                .or_else(|| voices.iter().position(|v| v.part() == part && v.active()))
+        };
+
+        if let Some(index) = index {
+            let voice = &mut self.voices[index];
+            self.parts.apply(part, voice);
+            voice.set_part(part);
+            voice.set_kit_pad(None, 0);
+            voice.set_zone(zone);
+            voice.trigger(event);
+        }
     }
 }
 
 impl Triggered for SoyBoy {
     fn trigger(&mut self, event: &Event) {
         match event {
-            Event::NoteOn { note, velocity: _ } => {
-                if let Some(voice) = self.get_voices().iter_mut().find(|v| v.assignable(*note)) {
+            Event::NoteOn {
+                note,
+                velocity,
+                channel,
+                ..
+            } => {
+                let zone = if self.zones.enabled() {
+                    let kit = &self.kit;
+                    match self
+                        .zones
+                        .zone_for(*note, *velocity, |slot| kit.pad_wavetable(slot))
+                    {
+                        Some(zone) => Some(zone),
+                        None => return,
+                    }
+                } else {
+                    None
+                };
+
+                if self.parts.enabled() {
+                    if let Some(part) = self.parts.part_for_channel(*channel) {
+                        self.trigger_part(part, *note, zone, event);
+                    }
+                } else if self.kit.enabled() {
+                    self.trigger_kit(*note, zone, event);
+                } else if let Some(voice) = find_voice(self.get_voices(), *note) {
+                    voice.set_zone(zone);
                     voice.trigger(event);
                 }
+
+                // MPE controllers may come before the note-on
+                let expression = self.mpe.expression(*channel);
+                let bend_range = self.mpe.bend_range();
+                self.voices
+                    .iter_mut()
+                    .filter(|v| v.channel() == *channel && v.held() && v.same_note(*note))
+                    .for_each(|v| v.set_expression(&expression, bend_range));
+            }
+            Event::NoteOff {
+                note,
+                channel,
+                note_id,
+            } => {
+                let sustain = self.sustain;
+                // note IDs tell which of the notes with the same pitch is released
+                let identified = self.voices.iter_mut().find(|v| {
+                    *note_id != NO_NOTE_ID && v.note_id() == *note_id && v.held() && !v.sustained()
+                });
+
+                if let Some(voice) = identified {
+                    voice.note_off(event, sustain);
+                } else if self.parts.enabled() {
+                    if let Some(part) = self.parts.part_for_channel(*channel) {
+                        self.voices
+                            .iter_mut()
+                            .filter(|v| v.part() == part && v.same_note(*note))
+                            .for_each(|v| v.note_off(event, sustain));
+                    }
+                } else if let Some(voice) = self
+                    .voices
+                    .iter_mut()
+                    .find(|v| v.same_note(*note) && v.channel() == *channel)
+                {
+                    voice.note_off(event, sustain);
+                } else if let Some(voice) = self.voices.iter_mut().find(|v| v.same_note(*note)) {
+                    voice.note_off(event, sustain);
+                }
             }
-            Event::NoteOff { note } => {
-                if let Some(voice) = self.voices.iter_mut().find(|v| v.same_note(*note)) {
+            Event::NoteExpression { note_id, .. } => {
+                if let Some(voice) = self
+                    .voices
+                    .iter_mut()
+                    .find(|v| *note_id != NO_NOTE_ID && v.note_id() == *note_id && v.active())
+                {
                     voice.trigger(event);
                 }
             }
-            event => self.voices.iter_mut().for_each(|v| v.trigger(event)),
+            Event::PolyPressure {
+                note,
+                channel,
+                note_id,
+                ..
+            } => {
+                // the note ID finds the key when the host gives it, otherwise the pitch does
+                self.voices
+                    .iter_mut()
+                    .filter(|v| {
+                        v.held()
+                            && if *note_id != NO_NOTE_ID {
+                                v.note_id() == *note_id
+                            } else {
+                                v.same_note(*note) && v.channel() == *channel
+                            }
+                    })
+                    .for_each(|v| v.trigger(event));
+            }
+            Event::SetWaveTable { .. }
+            | Event::ResetWaveTableAsSine
+            | Event::ResetWaveTableAtRandom => {
+                if self.parts.enabled() {
+                    self.parts.trigger(event);
+                    let part = self.parts.selected();
+                    let table = self.parts.wavetable();
+                    self.voices
+                        .iter_mut()
+                        .filter(|v| v.part() == part)
+                        .for_each(|v| v.set_wavetable(&table));
+                } else {
+                    self.kit.trigger(event);
+                    if self.plays_single_patch() {
+                        let table = self.kit.wavetable();
+                        self.voices.iter_mut().for_each(|v| v.set_wavetable(&table));
+                    }
+                }
+            }
+            event => {
+                self.delay.trigger(event);
+                self.voices.iter_mut().for_each(|v| v.trigger(event));
+            }
         }
     }
 }
@@ -85,8 +421,73 @@ impl Parametric<SoyBoyParameter> for SoyBoy {
         let value = param_def.clamp(value);
 
         match param {
-            SoyBoyParameter::MasterVolume => self.master_volume = value,
+            SoyBoyParameter::MasterVolume => {
+                self.master_volume = value;
+                self.master_gain.set_target(level(value));
+            }
+            SoyBoyParameter::SmoothingTime => {
+                self.master_gain.set_ramp_time(value / 1000.0);
+                self.voices
+                    .iter_mut()
+                    .for_each(|v| v.set_param(param, param_def, value));
+            }
             SoyBoyParameter::NumVoices => self.num_voices = value as usize,
+            SoyBoyParameter::MasterHeadroom => self.headroom = value as u32 != 0,
+            SoyBoyParameter::MasterClip => self.clipper.set_param(param, param_def, value),
+            SoyBoyParameter::CrushBits => self.bitcrusher.set_param(param, param_def, value),
+            SoyBoyParameter::CrushRate => self.bitcrusher.set_param(param, param_def, value),
+            SoyBoyParameter::CrushAntiAlias => self.bitcrusher.set_param(param, param_def, value),
+            SoyBoyParameter::DelayMix => self.delay.set_param(param, param_def, value),
+            SoyBoyParameter::DelayTime => self.delay.set_param(param, param_def, value),
+            SoyBoyParameter::DelaySync => self.delay.set_param(param, param_def, value),
+            SoyBoyParameter::DelayDivision => self.delay.set_param(param, param_def, value),
+            SoyBoyParameter::DelayFeedback => self.delay.set_param(param, param_def, value),
+            SoyBoyParameter::DelayPingPong => self.delay.set_param(param, param_def, value),
+            SoyBoyParameter::DelayQuantize => self.delay.set_param(param, param_def, value),
+            SoyBoyParameter::KitMode => {
+                self.kit.set_enabled(value as u32 != 0);
+                if self.plays_single_patch() {
+                    self.apply_selected_pad();
+                }
+            }
+            SoyBoyParameter::KitPad => {
+                self.kit.select(value as usize - 1);
+                if self.plays_single_patch() {
+                    self.apply_selected_pad();
+                }
+            }
+            SoyBoyParameter::PartMode => {
+                self.parts.set_enabled(value as u32 != 0);
+                if self.plays_single_patch() {
+                    self.apply_selected_pad();
+                }
+            }
+            SoyBoyParameter::PartEdit => self.parts.select(value as usize - 1),
+            SoyBoyParameter::PartVoices => self.parts.set(param, value),
+            SoyBoyParameter::PartLevel => self.parts.set(param, value),
+            SoyBoyParameter::PartOutput => self.parts.set(param, value),
+            param if is_zone_parameter(param) => self.zones.set_param(param, param_def, value),
+            SoyBoyParameter::Sustain => self.set_sustain(value >= 0.5),
+            SoyBoyParameter::Sostenuto => self.set_sostenuto(value >= 0.5),
+            SoyBoyParameter::MpeZone => self.mpe.set_param(param, param_def, value),
+            SoyBoyParameter::MpeChannels => self.mpe.set_param(param, param_def, value),
+            SoyBoyParameter::MpeBendRange => self.mpe.set_param(param, param_def, value),
+            param if is_patch_parameter(param) && self.parts.enabled() => {
+                self.parts.set(param, value);
+                let part = self.parts.selected();
+                self.voices
+                    .iter_mut()
+                    .filter(|v| v.part() == part)
+                    .for_each(|v| v.set_param(param, param_def, value));
+            }
+            param if is_patch_parameter(param) => {
+                self.kit.set(param, value);
+                if self.plays_single_patch() {
+                    self.voices
+                        .iter_mut()
+                        .for_each(|v| v.set_param(param, param_def, value));
+                }
+            }
             param => self
                 .voices
                 .iter_mut()
@@ -97,22 +498,73 @@ impl Parametric<SoyBoyParameter> for SoyBoy {
     fn get_param(&self, param: &SoyBoyParameter) -> f64 {
         match param {
             SoyBoyParameter::MasterVolume => self.master_volume,
+            SoyBoyParameter::SmoothingTime => self.master_gain.ramp_time() * 1000.0,
             SoyBoyParameter::NumVoices => self.num_voices as f64,
+            SoyBoyParameter::MasterHeadroom => (self.headroom as u32).into(),
+            SoyBoyParameter::MasterClip => self.clipper.get_param(param),
+            SoyBoyParameter::CrushBits => self.bitcrusher.get_param(param),
+            SoyBoyParameter::CrushRate => self.bitcrusher.get_param(param),
+            SoyBoyParameter::CrushAntiAlias => self.bitcrusher.get_param(param),
+            SoyBoyParameter::DelayMix => self.delay.get_param(param),
+            SoyBoyParameter::DelayTime => self.delay.get_param(param),
+            SoyBoyParameter::DelaySync => self.delay.get_param(param),
+            SoyBoyParameter::DelayDivision => self.delay.get_param(param),
+            SoyBoyParameter::DelayFeedback => self.delay.get_param(param),
+            SoyBoyParameter::DelayPingPong => self.delay.get_param(param),
+            SoyBoyParameter::DelayQuantize => self.delay.get_param(param),
+            SoyBoyParameter::KitMode => (self.kit.enabled() as u32).into(),
+            SoyBoyParameter::KitPad => (self.kit.selected() + 1) as f64,
+            SoyBoyParameter::PartMode => (self.parts.enabled() as u32).into(),
+            SoyBoyParameter::PartEdit => (self.parts.selected() + 1) as f64,
+            SoyBoyParameter::PartVoices => self.parts.get(param),
+            SoyBoyParameter::PartLevel => self.parts.get(param),
+            SoyBoyParameter::PartOutput => self.parts.get(param),
+            param if is_zone_parameter(param) => self.zones.get_param(param),
+            SoyBoyParameter::Sustain => (self.sustain as u32).into(),
+            SoyBoyParameter::Sostenuto => (self.sostenuto as u32).into(),
+            SoyBoyParameter::MpeZone => self.mpe.get_param(param),
+            SoyBoyParameter::MpeChannels => self.mpe.get_param(param),
+            SoyBoyParameter::MpeBendRange => self.mpe.get_param(param),
+            param if is_patch_parameter(param) && self.parts.enabled() => self.parts.get(param),
+            param if is_patch_parameter(param) => self.kit.get(param),
             param => self.voices[0].get_param(param),
         }
     }
 }
 impl AudioProcessor<Signal> for SoyBoy {
     fn process(&mut self, sample_rate: f64) -> Signal {
-        let mut v = 0.0;
+        let mut l = 0.0;
+        let mut r = 0.0;
 
-        for voice in self.get_voices().iter_mut() {
-            v += voice.process(sample_rate);
-        }
-
-        let v = v * level(self.master_volume);
+        let headroom = if self.parts.enabled() {
+            // the parts share all voices and are mixed like the APU's channels
+            for voice in self.voices.iter_mut() {
+                let s = voice.process(sample_rate);
+                let gain = self.parts.gain(voice.part());
+                l += s.0 * gain.0;
+                r += s.1 * gain.1;
+            }
+            1.0 / (NUMBER_OF_PARTS as f64).sqrt()
+        } else {
+            for voice in self.get_voices().iter_mut() {
+                let s = voice.process(sample_rate);
+                l += s.0;
+                r += s.1;
+            }
+            if self.headroom {
+                // keep headroom for chords: N voices in unison rise only sqrt(N) times in RMS
+                1.0 / (self.num_voices as f64).sqrt()
+            } else {
+                1.0
+            }
+        };
+        let volume = self.master_gain.process(sample_rate) * headroom;
 
-        (v, v)
+        let s = self
+            .bitcrusher
+            .process(sample_rate, (l * volume, r * volume));
+        let s = self.delay.process(sample_rate, s);
+        self.clipper.process(sample_rate, s)
     }
 
     fn set_freq(&mut self, _freq: f64) {}
@@ -0,0 +1,616 @@
+use std::convert::TryFrom;
+
+use rand::prelude::*;
+
+use crate::{
+    common::{constants, f64_utils},
+    soyboy::{
+        event::{Event, Triggered},
+        parameters::{ParameterDef, Parametric, SoyBoyParameter},
+        types::AudioProcessor,
+        utils::linear,
+    },
+};
+
+const NUMBER_OF_SLOTS: usize = 8;
+/// The number of parameters for each slot: source, destination and amount.
+const SLOT_PARAMETERS: u32 = 3;
+
+/// Key modulation is relative to this note (C4).
+const KEY_CENTER_NOTE: i16 = 60;
+
+// the modulation ranges when a slot's amount is 100%
+const PITCH_RANGE_IN_SEMITONES: f64 = 12.0;
+const DUTY_RANGE: f64 = 0.5;
+const WAVETABLE_INDEX_RANGE: f64 = constants::WAVETABLE_SIZE as f64 / 2.0;
+const NOISE_RATE_RANGE_IN_OCTAVES: f64 = 4.0;
+const PAN_RANGE: f64 = 1.0;
+const CUTOFF_RANGE_IN_OCTAVES: f64 = 4.0;
+const STUTTER_TIME_RANGE_IN_OCTAVES: f64 = 2.0;
+// the ranges of the controllers' routes when the amount is 100%
+const VIBRATO_RANGE_IN_SEMITONES: f64 = 1.0;
+const VOLUME_RANGE: f64 = 1.0;
+
+#[derive(Debug, Copy, Clone)]
+enum ModSource {
+    Off = 0,
+    Lfo1,
+    Lfo2,
+    Envelope,
+    Velocity,
+    Key,
+    ModWheel,
+    Aftertouch,
+    Random,
+    Breath,
+    Timbre,
+}
+
+impl TryFrom<u32> for ModSource {
+    type Error = ();
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        if id == ModSource::Off as u32 {
+            Ok(ModSource::Off)
+        } else if id == ModSource::Lfo1 as u32 {
+            Ok(ModSource::Lfo1)
+        } else if id == ModSource::Lfo2 as u32 {
+            Ok(ModSource::Lfo2)
+        } else if id == ModSource::Envelope as u32 {
+            Ok(ModSource::Envelope)
+        } else if id == ModSource::Velocity as u32 {
+            Ok(ModSource::Velocity)
+        } else if id == ModSource::Key as u32 {
+            Ok(ModSource::Key)
+        } else if id == ModSource::ModWheel as u32 {
+            Ok(ModSource::ModWheel)
+        } else if id == ModSource::Aftertouch as u32 {
+            Ok(ModSource::Aftertouch)
+        } else if id == ModSource::Random as u32 {
+            Ok(ModSource::Random)
+        } else if id == ModSource::Breath as u32 {
+            Ok(ModSource::Breath)
+        } else if id == ModSource::Timbre as u32 {
+            Ok(ModSource::Timbre)
+        } else {
+            Err(())
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum ModDestination {
+    Off = 0,
+    Pitch,
+    Duty,
+    WaveTableIndex,
+    NoiseRate,
+    Pan,
+    FilterCutoff,
+    StutterTime,
+}
+
+impl TryFrom<u32> for ModDestination {
+    type Error = ();
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        if id == ModDestination::Off as u32 {
+            Ok(ModDestination::Off)
+        } else if id == ModDestination::Pitch as u32 {
+            Ok(ModDestination::Pitch)
+        } else if id == ModDestination::Duty as u32 {
+            Ok(ModDestination::Duty)
+        } else if id == ModDestination::WaveTableIndex as u32 {
+            Ok(ModDestination::WaveTableIndex)
+        } else if id == ModDestination::NoiseRate as u32 {
+            Ok(ModDestination::NoiseRate)
+        } else if id == ModDestination::Pan as u32 {
+            Ok(ModDestination::Pan)
+        } else if id == ModDestination::FilterCutoff as u32 {
+            Ok(ModDestination::FilterCutoff)
+        } else if id == ModDestination::StutterTime as u32 {
+            Ok(ModDestination::StutterTime)
+        } else {
+            Err(())
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum ControllerDestination {
+    Off = 0,
+    Vibrato,
+    Volume,
+    FilterCutoff,
+    VolumeAndCutoff,
+}
+
+impl TryFrom<u32> for ControllerDestination {
+    type Error = ();
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        if id == ControllerDestination::Off as u32 {
+            Ok(ControllerDestination::Off)
+        } else if id == ControllerDestination::Vibrato as u32 {
+            Ok(ControllerDestination::Vibrato)
+        } else if id == ControllerDestination::Volume as u32 {
+            Ok(ControllerDestination::Volume)
+        } else if id == ControllerDestination::FilterCutoff as u32 {
+            Ok(ControllerDestination::FilterCutoff)
+        } else if id == ControllerDestination::VolumeAndCutoff as u32 {
+            Ok(ControllerDestination::VolumeAndCutoff)
+        } else {
+            Err(())
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum PolyPressureDestination {
+    Off = 0,
+    Volume,
+    Vibrato,
+    Duty,
+    WaveTableScan,
+}
+
+impl TryFrom<u32> for PolyPressureDestination {
+    type Error = ();
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        if id == PolyPressureDestination::Off as u32 {
+            Ok(PolyPressureDestination::Off)
+        } else if id == PolyPressureDestination::Volume as u32 {
+            Ok(PolyPressureDestination::Volume)
+        } else if id == PolyPressureDestination::Vibrato as u32 {
+            Ok(PolyPressureDestination::Vibrato)
+        } else if id == PolyPressureDestination::Duty as u32 {
+            Ok(PolyPressureDestination::Duty)
+        } else if id == PolyPressureDestination::WaveTableScan as u32 {
+            Ok(PolyPressureDestination::WaveTableScan)
+        } else {
+            Err(())
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum LfoShape {
+    Triangle = 0,
+    Square,
+    Saw,
+    SampleAndHold,
+}
+
+impl TryFrom<u32> for LfoShape {
+    type Error = ();
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        if id == LfoShape::Triangle as u32 {
+            Ok(LfoShape::Triangle)
+        } else if id == LfoShape::Square as u32 {
+            Ok(LfoShape::Square)
+        } else if id == LfoShape::Saw as u32 {
+            Ok(LfoShape::Saw)
+        } else if id == LfoShape::SampleAndHold as u32 {
+            Ok(LfoShape::SampleAndHold)
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// A bipolar LFO. Its phase is reset at each note-on.
+struct Lfo {
+    rate: f64,
+    shape: LfoShape,
+
+    phase: f64,
+    hold: f64,
+}
+
+impl Lfo {
+    fn new() -> Self {
+        Self {
+            rate: 5.0,
+            shape: LfoShape::Triangle,
+
+            phase: 0.0,
+            hold: 0.0,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.phase = 0.0;
+        self.hold = random::<f64>() * 2.0 - 1.0;
+    }
+
+    fn process(&mut self, sample_rate: f64) -> f64 {
+        let v = match self.shape {
+            LfoShape::Triangle => 1.0 - 4.0 * (self.phase - 0.5).abs(),
+            LfoShape::Square => {
+                if self.phase < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            LfoShape::Saw => 1.0 - 2.0 * self.phase,
+            LfoShape::SampleAndHold => self.hold,
+        };
+
+        self.phase += self.rate / sample_rate;
+        if self.phase >= 1.0 {
+            self.phase %= 1.0;
+            self.hold = random::<f64>() * 2.0 - 1.0;
+        }
+
+        v
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Slot {
+    source: ModSource,
+    destination: ModDestination,
+    amount: f64,
+}
+
+impl Slot {
+    fn new() -> Self {
+        Self {
+            source: ModSource::Off,
+            destination: ModDestination::Off,
+            amount: 0.0,
+        }
+    }
+}
+
+/// Routes a MIDI controller directly to a destination besides the slots.
+#[derive(Debug, Copy, Clone)]
+struct ControllerRoute {
+    destination: ControllerDestination,
+    amount: f64,
+}
+
+impl ControllerRoute {
+    fn new(destination: ControllerDestination, amount: f64) -> Self {
+        Self {
+            destination,
+            amount,
+        }
+    }
+
+    fn set_destination(&mut self, value: f64) {
+        if let Ok(destination) = ControllerDestination::try_from(value as u32) {
+            self.destination = destination;
+        }
+    }
+
+    /// Adds the controller's `value` to `modulation`. Vibrato is made with LFO 1.
+    fn apply(&self, value: f64, lfo1: f64, modulation: &mut Modulation) {
+        let v = value * self.amount / 100.0;
+
+        match self.destination {
+            ControllerDestination::Off => (),
+            ControllerDestination::Vibrato => {
+                modulation.pitch += v * lfo1 * VIBRATO_RANGE_IN_SEMITONES
+            }
+            ControllerDestination::Volume => modulation.volume += v * VOLUME_RANGE,
+            ControllerDestination::FilterCutoff => modulation.cutoff += v * CUTOFF_RANGE_IN_OCTAVES,
+            ControllerDestination::VolumeAndCutoff => {
+                modulation.volume += v * VOLUME_RANGE;
+                modulation.cutoff += v * CUTOFF_RANGE_IN_OCTAVES;
+            }
+        }
+    }
+}
+
+/// The modulation values for each destination, in the units the destinations use.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Modulation {
+    /// in semitones
+    pub pitch: f64,
+    /// added to the duty ratio
+    pub duty: f64,
+    /// in wavetable samples
+    pub wavetable_index: f64,
+    /// in octaves
+    pub noise_rate: f64,
+    /// -1.0 is left and 1.0 is right
+    pub pan: f64,
+    /// in octaves
+    pub cutoff: f64,
+    /// in octaves; positive values make stutters faster
+    pub stutter_time: f64,
+    /// added to the voice's gain of 1.0
+    pub volume: f64,
+}
+
+/// Routes the modulation sources to the destinations through 8 slots.
+pub struct ModulationMatrix {
+    slots: [Slot; NUMBER_OF_SLOTS],
+    lfo1: Lfo,
+    lfo2: Lfo,
+    env_attack: f64,
+    env_decay: f64,
+    mod_wheel: f64,
+    aftertouch: f64,
+    breath: f64,
+    mod_wheel_route: ControllerRoute,
+    aftertouch_route: ControllerRoute,
+    breath_route: ControllerRoute,
+    timbre_route: ControllerRoute,
+    poly_pressure_destination: PolyPressureDestination,
+    poly_pressure_amount: f64,
+
+    // the key's own poly pressure
+    poly_pressure: f64,
+    // the note's own MPE pressure and timbre
+    pressure: f64,
+    timbre: f64,
+    // the note expression's brightness in -1.0..1.0, which is added to the timbre
+    brightness: f64,
+    velocity: f64,
+    note: u16,
+    random: f64,
+    elapsed_samples: u64,
+}
+
+impl ModulationMatrix {
+    pub fn new() -> Self {
+        Self {
+            slots: [Slot::new(); NUMBER_OF_SLOTS],
+            lfo1: Lfo::new(),
+            lfo2: Lfo::new(),
+            env_attack: 0.0,
+            env_decay: 0.5,
+            mod_wheel: 0.0,
+            aftertouch: 0.0,
+            breath: 0.0,
+            mod_wheel_route: ControllerRoute::new(ControllerDestination::Vibrato, 100.0),
+            aftertouch_route: ControllerRoute::new(ControllerDestination::VolumeAndCutoff, 50.0),
+            breath_route: ControllerRoute::new(ControllerDestination::Off, 100.0),
+            timbre_route: ControllerRoute::new(ControllerDestination::FilterCutoff, 50.0),
+            poly_pressure_destination: PolyPressureDestination::Volume,
+            poly_pressure_amount: 50.0,
+
+            poly_pressure: 0.0,
+            pressure: 0.0,
+            timbre: 0.0,
+            brightness: 0.0,
+            velocity: 1.0,
+            note: KEY_CENTER_NOTE as u16,
+            random: 0.0,
+            elapsed_samples: 0,
+        }
+    }
+
+    /// Returns the slot index and the position of the parameter in the slot
+    /// if `param` is one of the slot parameters.
+    fn slot_param(param: &SoyBoyParameter) -> Option<(usize, u32)> {
+        let first = SoyBoyParameter::ModSlot1Source as u32;
+        let last = SoyBoyParameter::ModSlot8Amount as u32;
+        let id = *param as u32;
+
+        if (first..=last).contains(&id) {
+            let offset = id - first;
+            Some((
+                (offset / SLOT_PARAMETERS) as usize,
+                offset % SLOT_PARAMETERS,
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Sets the note's own MPE expression. The pressure is added to the aftertouch.
+    pub fn set_expression(&mut self, pressure: f64, timbre: f64) {
+        self.pressure = pressure;
+        self.timbre = timbre;
+    }
+
+    pub fn set_brightness(&mut self, brightness: f64) {
+        self.brightness = brightness;
+    }
+
+    pub fn set_poly_pressure(&mut self, pressure: f64) {
+        self.poly_pressure = pressure.clamp(0.0, 1.0);
+    }
+
+    /// The modulation envelope rises in `env_attack` then falls to zero in `env_decay`.
+    fn envelope(&self, sec: f64) -> f64 {
+        if sec < self.env_attack {
+            linear(sec, 1.0 / self.env_attack)
+        } else if sec < self.env_attack + self.env_decay {
+            1.0 - linear(sec - self.env_attack, 1.0 / self.env_decay)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl AudioProcessor<Modulation> for ModulationMatrix {
+    fn process(&mut self, sample_rate: f64) -> Modulation {
+        let sec = self.elapsed_samples as f64 / sample_rate;
+        self.elapsed_samples += 1;
+
+        let lfo1 = self.lfo1.process(sample_rate);
+        let lfo2 = self.lfo2.process(sample_rate);
+        let envelope = self.envelope(sec);
+        let key = (self.note as i16 - KEY_CENTER_NOTE) as f64 / 64.0;
+        let aftertouch = (self.aftertouch + self.pressure).min(1.0);
+        let timbre = (self.timbre + self.brightness).clamp(-1.0, 1.0);
+
+        let mut modulation = Modulation::default();
+        for slot in self.slots.iter() {
+            let source = match slot.source {
+                ModSource::Off => continue,
+                ModSource::Lfo1 => lfo1,
+                ModSource::Lfo2 => lfo2,
+                ModSource::Envelope => envelope,
+                ModSource::Velocity => self.velocity,
+                ModSource::Key => key,
+                ModSource::ModWheel => self.mod_wheel,
+                ModSource::Aftertouch => aftertouch,
+                ModSource::Random => self.random,
+                ModSource::Breath => self.breath,
+                ModSource::Timbre => timbre,
+            };
+            let v = f64_utils::normalize(source * slot.amount / 100.0);
+
+            match slot.destination {
+                ModDestination::Off => (),
+                ModDestination::Pitch => modulation.pitch += v * PITCH_RANGE_IN_SEMITONES,
+                ModDestination::Duty => modulation.duty += v * DUTY_RANGE,
+                ModDestination::WaveTableIndex => {
+                    modulation.wavetable_index += v * WAVETABLE_INDEX_RANGE
+                }
+                ModDestination::NoiseRate => {
+                    modulation.noise_rate += v * NOISE_RATE_RANGE_IN_OCTAVES
+                }
+                ModDestination::Pan => modulation.pan += v * PAN_RANGE,
+                ModDestination::FilterCutoff => modulation.cutoff += v * CUTOFF_RANGE_IN_OCTAVES,
+                ModDestination::StutterTime => {
+                    modulation.stutter_time += v * STUTTER_TIME_RANGE_IN_OCTAVES
+                }
+            }
+        }
+
+        self.mod_wheel_route
+            .apply(self.mod_wheel, lfo1, &mut modulation);
+        self.aftertouch_route
+            .apply(aftertouch, lfo1, &mut modulation);
+        self.breath_route.apply(self.breath, lfo1, &mut modulation);
+        self.timbre_route.apply(timbre, lfo1, &mut modulation);
+
+        let v = self.poly_pressure * self.poly_pressure_amount / 100.0;
+        match self.poly_pressure_destination {
+            PolyPressureDestination::Off => (),
+            PolyPressureDestination::Volume => modulation.volume += v * VOLUME_RANGE,
+            PolyPressureDestination::Vibrato => {
+                modulation.pitch += v * lfo1 * VIBRATO_RANGE_IN_SEMITONES
+            }
+            PolyPressureDestination::Duty => modulation.duty += v * DUTY_RANGE,
+            PolyPressureDestination::WaveTableScan => {
+                modulation.wavetable_index += v * WAVETABLE_INDEX_RANGE
+            }
+        }
+
+        modulation
+    }
+
+    fn set_freq(&mut self, _freq: f64) {}
+}
+
+impl Triggered for ModulationMatrix {
+    fn trigger(&mut self, event: &Event) {
+        if let Event::NoteOn { note, velocity, .. } = event {
+            self.note = *note;
+            self.velocity = *velocity;
+            self.random = random::<f64>() * 2.0 - 1.0;
+            self.brightness = 0.0;
+            self.poly_pressure = 0.0;
+            self.elapsed_samples = 0;
+            self.lfo1.reset();
+            self.lfo2.reset();
+        }
+    }
+}
+
+impl Parametric<SoyBoyParameter> for ModulationMatrix {
+    fn set_param(&mut self, param: &SoyBoyParameter, _param_def: &ParameterDef, value: f64) {
+        if let Some((idx, field)) = Self::slot_param(param) {
+            let slot = &mut self.slots[idx];
+            match field {
+                0 => {
+                    if let Ok(source) = ModSource::try_from(value as u32) {
+                        slot.source = source;
+                    }
+                }
+                1 => {
+                    if let Ok(destination) = ModDestination::try_from(value as u32) {
+                        slot.destination = destination;
+                    }
+                }
+                _ => slot.amount = value,
+            }
+            return;
+        }
+
+        match param {
+            SoyBoyParameter::Lfo1Rate => self.lfo1.rate = value,
+            SoyBoyParameter::Lfo1Shape => {
+                if let Ok(shape) = LfoShape::try_from(value as u32) {
+                    self.lfo1.shape = shape;
+                }
+            }
+            SoyBoyParameter::Lfo2Rate => self.lfo2.rate = value,
+            SoyBoyParameter::Lfo2Shape => {
+                if let Ok(shape) = LfoShape::try_from(value as u32) {
+                    self.lfo2.shape = shape;
+                }
+            }
+            SoyBoyParameter::ModEnvAttack => self.env_attack = value,
+            SoyBoyParameter::ModEnvDecay => self.env_decay = value,
+            SoyBoyParameter::ModWheel => self.mod_wheel = value,
+            SoyBoyParameter::Aftertouch => self.aftertouch = value,
+            SoyBoyParameter::Breath => self.breath = value,
+            SoyBoyParameter::ModWheelDestination => self.mod_wheel_route.set_destination(value),
+            SoyBoyParameter::ModWheelAmount => self.mod_wheel_route.amount = value,
+            SoyBoyParameter::AftertouchDestination => self.aftertouch_route.set_destination(value),
+            SoyBoyParameter::AftertouchAmount => self.aftertouch_route.amount = value,
+            SoyBoyParameter::BreathDestination => self.breath_route.set_destination(value),
+            SoyBoyParameter::BreathAmount => self.breath_route.amount = value,
+            SoyBoyParameter::TimbreDestination => self.timbre_route.set_destination(value),
+            SoyBoyParameter::TimbreAmount => self.timbre_route.amount = value,
+            SoyBoyParameter::PolyPressureDestination => {
+                if let Ok(destination) = PolyPressureDestination::try_from(value as u32) {
+                    self.poly_pressure_destination = destination;
+                }
+            }
+            SoyBoyParameter::PolyPressureAmount => self.poly_pressure_amount = value,
+            _ => (),
+        }
+    }
+
+    fn get_param(&self, param: &SoyBoyParameter) -> f64 {
+        if let Some((idx, field)) = Self::slot_param(param) {
+            let slot = &self.slots[idx];
+            return match field {
+                0 => (slot.source as u32).into(),
+                1 => (slot.destination as u32).into(),
+                _ => slot.amount,
+            };
+        }
+
+        match param {
+            SoyBoyParameter::Lfo1Rate => self.lfo1.rate,
+            SoyBoyParameter::Lfo1Shape => (self.lfo1.shape as u32).into(),
+            SoyBoyParameter::Lfo2Rate => self.lfo2.rate,
+            SoyBoyParameter::Lfo2Shape => (self.lfo2.shape as u32).into(),
+            SoyBoyParameter::ModEnvAttack => self.env_attack,
+            SoyBoyParameter::ModEnvDecay => self.env_decay,
+            SoyBoyParameter::ModWheel => self.mod_wheel,
+            SoyBoyParameter::Aftertouch => self.aftertouch,
+            SoyBoyParameter::Breath => self.breath,
+            SoyBoyParameter::ModWheelDestination => {
+                (self.mod_wheel_route.destination as u32).into()
+            }
+            SoyBoyParameter::ModWheelAmount => self.mod_wheel_route.amount,
+            SoyBoyParameter::AftertouchDestination => {
+                (self.aftertouch_route.destination as u32).into()
+            }
+            SoyBoyParameter::AftertouchAmount => self.aftertouch_route.amount,
+            SoyBoyParameter::BreathDestination => (self.breath_route.destination as u32).into(),
+            SoyBoyParameter::BreathAmount => self.breath_route.amount,
+            SoyBoyParameter::TimbreDestination => (self.timbre_route.destination as u32).into(),
+            SoyBoyParameter::TimbreAmount => self.timbre_route.amount,
+            SoyBoyParameter::PolyPressureDestination => {
+                (self.poly_pressure_destination as u32).into()
+            }
+            SoyBoyParameter::PolyPressureAmount => self.poly_pressure_amount,
+            _ => 0.0,
+        }
+    }
+}
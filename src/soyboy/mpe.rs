@@ -0,0 +1,184 @@
+use std::convert::TryFrom;
+
+use crate::soyboy::parameters::{ParameterDef, Parametric, SoyBoyParameter};
+
+pub const NUMBER_OF_CHANNELS: usize = 16;
+pub const NUMBER_OF_DIMENSIONS: u32 = 3;
+
+/// Which MPE zone is played. The lower zone's master channel is 1 and the upper zone's is 16.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MpeZone {
+    Off = 0,
+    Lower,
+    Upper,
+}
+
+impl TryFrom<u32> for MpeZone {
+    type Error = ();
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        if id == MpeZone::Off as u32 {
+            Ok(MpeZone::Off)
+        } else if id == MpeZone::Lower as u32 {
+            Ok(MpeZone::Lower)
+        } else if id == MpeZone::Upper as u32 {
+            Ok(MpeZone::Upper)
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// Returns whether the MIDI `channel`, which starts from 0, is a member channel of the zone.
+/// The member channels follow the master channel for `members` channels.
+pub fn is_member_channel(zone: MpeZone, members: u32, channel: i16) -> bool {
+    let members = members as i16;
+    match zone {
+        MpeZone::Off => false,
+        MpeZone::Lower => (1..=members).contains(&channel),
+        MpeZone::Upper => (15 - members..=14).contains(&channel),
+    }
+}
+
+/// The controllers sent to each member channel.
+#[derive(Debug, Copy, Clone)]
+pub enum MpeDimension {
+    PitchBend = 0,
+    Pressure,
+    Timbre,
+}
+
+impl TryFrom<u32> for MpeDimension {
+    type Error = ();
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        if id == MpeDimension::PitchBend as u32 {
+            Ok(MpeDimension::PitchBend)
+        } else if id == MpeDimension::Pressure as u32 {
+            Ok(MpeDimension::Pressure)
+        } else if id == MpeDimension::Timbre as u32 {
+            Ok(MpeDimension::Timbre)
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// A note's own expression. All values are neutral at 0.0.
+#[derive(Debug, Copy, Clone)]
+pub struct Expression {
+    /// in -1.0..1.0, scaled with the member channels' bend range
+    pub bend: f64,
+    /// in 0.0..1.0
+    pub pressure: f64,
+    /// in -1.0..1.0 around CC74's center
+    pub timbre: f64,
+}
+
+impl Expression {
+    pub fn new() -> Self {
+        Self {
+            bend: 0.0,
+            pressure: 0.0,
+            timbre: 0.0,
+        }
+    }
+
+    /// Sets the controller's `value`, which is normalized in 0.0..1.0 like MIDI controllers.
+    pub fn set(&mut self, dimension: MpeDimension, value: f64) {
+        let value = value.clamp(0.0, 1.0);
+        match dimension {
+            MpeDimension::PitchBend => self.bend = value * 2.0 - 1.0,
+            MpeDimension::Pressure => self.pressure = value,
+            MpeDimension::Timbre => self.timbre = value * 2.0 - 1.0,
+        }
+    }
+}
+
+impl Default for Expression {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tracks the MPE zone and each member channel's expression. The master channel's
+/// controllers are the global ones, e.g. "Pitch" and "Aftertouch".
+pub struct Mpe {
+    zone: MpeZone,
+    members: u32,
+    bend_range: u32,
+    expressions: [Expression; NUMBER_OF_CHANNELS],
+}
+
+impl Mpe {
+    pub fn new() -> Self {
+        Self {
+            zone: MpeZone::Off,
+            members: 15,
+            bend_range: 48,
+            expressions: [Expression::new(); NUMBER_OF_CHANNELS],
+        }
+    }
+
+    pub fn is_member(&self, channel: i16) -> bool {
+        is_member_channel(self.zone, self.members, channel)
+    }
+
+    /// Returns the member channels' bend range in semitones.
+    pub fn bend_range(&self) -> u32 {
+        self.bend_range
+    }
+
+    /// Sets the member channel's controller and returns its expression.
+    pub fn set(&mut self, channel: i16, dimension: MpeDimension, value: f64) -> Option<Expression> {
+        if self.is_member(channel) {
+            let expression = &mut self.expressions[channel as usize];
+            expression.set(dimension, value);
+            Some(*expression)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the expression for notes on the channel. It is neutral out of member channels.
+    pub fn expression(&self, channel: i16) -> Expression {
+        if self.is_member(channel) {
+            self.expressions[channel as usize]
+        } else {
+            Expression::new()
+        }
+    }
+}
+
+impl Default for Mpe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parametric<SoyBoyParameter> for Mpe {
+    fn set_param(&mut self, param: &SoyBoyParameter, _param_def: &ParameterDef, value: f64) {
+        match param {
+            SoyBoyParameter::MpeZone => {
+                if let Ok(zone) = MpeZone::try_from(value as u32) {
+                    if zone != self.zone {
+                        self.expressions = [Expression::new(); NUMBER_OF_CHANNELS];
+                    }
+                    self.zone = zone;
+                }
+            }
+            SoyBoyParameter::MpeChannels => self.members = value as u32,
+            SoyBoyParameter::MpeBendRange => self.bend_range = value as u32,
+            _ => (),
+        }
+    }
+
+    fn get_param(&self, param: &SoyBoyParameter) -> f64 {
+        match param {
+            SoyBoyParameter::MpeZone => (self.zone as u32).into(),
+            SoyBoyParameter::MpeChannels => self.members as f64,
+            SoyBoyParameter::MpeBendRange => self.bend_range as f64,
+            _ => 0.0,
+        }
+    }
+}
@@ -13,6 +13,7 @@ const TABLE_SIZE: usize = 1024 * 8;
 
 pub struct NoiseOscillator {
     interval_msec: f64,
+    rate_modulation: f64,
     sec_counter: f64,
     table: [i4; TABLE_SIZE],
     table_index: usize,
@@ -28,16 +29,23 @@ impl NoiseOscillator {
 
         NoiseOscillator {
             interval_msec: 0.1,
+            rate_modulation: 0.0,
             sec_counter: 0.0,
             table,
             table_index: 0,
         }
     }
+
+    /// Sets the modulation in octaves. Positive values make the noise brighter.
+    pub fn set_rate_modulation(&mut self, octaves: f64) {
+        self.rate_modulation = octaves;
+    }
 }
 
 impl AudioProcessor<i4> for NoiseOscillator {
     fn process(&mut self, sample_rate: f64) -> i4 {
-        if self.sec_counter >= self.interval_msec / 1000.0 {
+        let interval = self.interval_msec / 1000.0 / 2.0f64.powf(self.rate_modulation);
+        if self.sec_counter >= interval {
             self.table_index = (self.table_index + 1) % self.table.len();
             self.sec_counter = 0.0;
         }
@@ -51,11 +59,7 @@ impl AudioProcessor<i4> for NoiseOscillator {
 
 impl Triggered for NoiseOscillator {
     fn trigger(&mut self, event: &Event) {
-        if let Event::NoteOn {
-            note: _,
-            velocity: _,
-        } = event
-        {}
+        if let Event::NoteOn { .. } = event {}
     }
 }
 
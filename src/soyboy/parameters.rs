@@ -32,6 +32,167 @@ pub enum SoyBoyParameter {
     // hidden: DAC
     DacFreq,
     DacQ,
+    // tempo sync
+    SweepSync,
+    SweepDivision,
+    StutterSync,
+    StutterDivision,
+    // extended stutter
+    StutterCount,
+    StutterPitch,
+    StutterPingPong,
+    StutterProbability,
+    // delay
+    DelayMix,
+    DelayTime,
+    DelaySync,
+    DelayDivision,
+    DelayFeedback,
+    DelayPingPong,
+    DelayQuantize,
+    // bitcrusher
+    CrushBits,
+    CrushRate,
+    CrushAntiAlias,
+    // master bus
+    MasterClip,
+    // filter envelope
+    FilterEnvAmount,
+    FilterEnvAttack,
+    FilterEnvDecay,
+    FilterKeyTracking,
+    FilterVelocity,
+    FilterType,
+    // hardware envelope
+    EgMode,
+    EgHwVolume,
+    EgHwDirection,
+    EgHwPeriod,
+    // envelope curves
+    EgAttackCurve,
+    EgAttackTension,
+    EgDecayCurve,
+    EgDecayTension,
+    EgReleaseCurve,
+    EgReleaseTension,
+    // envelope hold and loop
+    EgHold,
+    EgLoop,
+    // velocity
+    VelocityAmount,
+    VelocityCurve,
+    VelocityToAttack,
+    VelocityToStutter,
+    // modulation matrix
+    Lfo1Rate,
+    Lfo1Shape,
+    Lfo2Rate,
+    Lfo2Shape,
+    ModEnvAttack,
+    ModEnvDecay,
+    ModWheel,
+    Aftertouch,
+    // the slots' parameters have to be in this order for the modulation matrix
+    ModSlot1Source,
+    ModSlot1Destination,
+    ModSlot1Amount,
+    ModSlot2Source,
+    ModSlot2Destination,
+    ModSlot2Amount,
+    ModSlot3Source,
+    ModSlot3Destination,
+    ModSlot3Amount,
+    ModSlot4Source,
+    ModSlot4Destination,
+    ModSlot4Amount,
+    ModSlot5Source,
+    ModSlot5Destination,
+    ModSlot5Amount,
+    ModSlot6Source,
+    ModSlot6Destination,
+    ModSlot6Amount,
+    ModSlot7Source,
+    ModSlot7Destination,
+    ModSlot7Amount,
+    ModSlot8Source,
+    ModSlot8Destination,
+    ModSlot8Amount,
+    // drum kit
+    KitMode,
+    KitPad,
+    KitNoteLow,
+    KitNoteHigh,
+    KitChokeGroup,
+    KitPitchMode,
+    KitFixedNote,
+    // multitimbral
+    PartMode,
+    PartEdit,
+    PartVoices,
+    PartLevel,
+    PartOutput,
+    // split zones
+    SplitMode,
+    Zone1KeyLow,
+    Zone1KeyHigh,
+    Zone1VelocityLow,
+    Zone1VelocityHigh,
+    Zone1Oscillator,
+    Zone1Transpose,
+    Zone1Duty,
+    Zone1Wavetable,
+    Zone2KeyLow,
+    Zone2KeyHigh,
+    Zone2VelocityLow,
+    Zone2VelocityHigh,
+    Zone2Oscillator,
+    Zone2Transpose,
+    Zone2Duty,
+    Zone2Wavetable,
+    Zone3KeyLow,
+    Zone3KeyHigh,
+    Zone3VelocityLow,
+    Zone3VelocityHigh,
+    Zone3Oscillator,
+    Zone3Transpose,
+    Zone3Duty,
+    Zone3Wavetable,
+    Zone4KeyLow,
+    Zone4KeyHigh,
+    Zone4VelocityLow,
+    Zone4VelocityHigh,
+    Zone4Oscillator,
+    Zone4Transpose,
+    Zone4Duty,
+    Zone4Wavetable,
+    // pedals
+    Sustain,
+    Sostenuto,
+    // controllers
+    Breath,
+    ModWheelDestination,
+    ModWheelAmount,
+    AftertouchDestination,
+    AftertouchAmount,
+    BreathDestination,
+    BreathAmount,
+    // pitch bend
+    BendUp,
+    BendDown,
+    BendQuantize,
+    // MPE
+    MpeZone,
+    MpeChannels,
+    MpeBendRange,
+    TimbreDestination,
+    TimbreAmount,
+    // poly pressure
+    PolyPressureDestination,
+    PolyPressureAmount,
+    // smoothing
+    SmoothingTime,
+    // master bus
+    MasterHeadroom,
 }
 
 impl TryFrom<u32> for SoyBoyParameter {
@@ -76,6 +237,284 @@ impl TryFrom<u32> for SoyBoyParameter {
             Ok(SoyBoyParameter::DacFreq)
         } else if id == SoyBoyParameter::DacQ as u32 {
             Ok(SoyBoyParameter::DacQ)
+        } else if id == SoyBoyParameter::SweepSync as u32 {
+            Ok(SoyBoyParameter::SweepSync)
+        } else if id == SoyBoyParameter::SweepDivision as u32 {
+            Ok(SoyBoyParameter::SweepDivision)
+        } else if id == SoyBoyParameter::StutterSync as u32 {
+            Ok(SoyBoyParameter::StutterSync)
+        } else if id == SoyBoyParameter::StutterDivision as u32 {
+            Ok(SoyBoyParameter::StutterDivision)
+        } else if id == SoyBoyParameter::StutterCount as u32 {
+            Ok(SoyBoyParameter::StutterCount)
+        } else if id == SoyBoyParameter::StutterPitch as u32 {
+            Ok(SoyBoyParameter::StutterPitch)
+        } else if id == SoyBoyParameter::StutterPingPong as u32 {
+            Ok(SoyBoyParameter::StutterPingPong)
+        } else if id == SoyBoyParameter::StutterProbability as u32 {
+            Ok(SoyBoyParameter::StutterProbability)
+        } else if id == SoyBoyParameter::DelayMix as u32 {
+            Ok(SoyBoyParameter::DelayMix)
+        } else if id == SoyBoyParameter::DelayTime as u32 {
+            Ok(SoyBoyParameter::DelayTime)
+        } else if id == SoyBoyParameter::DelaySync as u32 {
+            Ok(SoyBoyParameter::DelaySync)
+        } else if id == SoyBoyParameter::DelayDivision as u32 {
+            Ok(SoyBoyParameter::DelayDivision)
+        } else if id == SoyBoyParameter::DelayFeedback as u32 {
+            Ok(SoyBoyParameter::DelayFeedback)
+        } else if id == SoyBoyParameter::DelayPingPong as u32 {
+            Ok(SoyBoyParameter::DelayPingPong)
+        } else if id == SoyBoyParameter::DelayQuantize as u32 {
+            Ok(SoyBoyParameter::DelayQuantize)
+        } else if id == SoyBoyParameter::CrushBits as u32 {
+            Ok(SoyBoyParameter::CrushBits)
+        } else if id == SoyBoyParameter::CrushRate as u32 {
+            Ok(SoyBoyParameter::CrushRate)
+        } else if id == SoyBoyParameter::CrushAntiAlias as u32 {
+            Ok(SoyBoyParameter::CrushAntiAlias)
+        } else if id == SoyBoyParameter::MasterClip as u32 {
+            Ok(SoyBoyParameter::MasterClip)
+        } else if id == SoyBoyParameter::FilterEnvAmount as u32 {
+            Ok(SoyBoyParameter::FilterEnvAmount)
+        } else if id == SoyBoyParameter::FilterEnvAttack as u32 {
+            Ok(SoyBoyParameter::FilterEnvAttack)
+        } else if id == SoyBoyParameter::FilterEnvDecay as u32 {
+            Ok(SoyBoyParameter::FilterEnvDecay)
+        } else if id == SoyBoyParameter::FilterKeyTracking as u32 {
+            Ok(SoyBoyParameter::FilterKeyTracking)
+        } else if id == SoyBoyParameter::FilterVelocity as u32 {
+            Ok(SoyBoyParameter::FilterVelocity)
+        } else if id == SoyBoyParameter::FilterType as u32 {
+            Ok(SoyBoyParameter::FilterType)
+        } else if id == SoyBoyParameter::EgMode as u32 {
+            Ok(SoyBoyParameter::EgMode)
+        } else if id == SoyBoyParameter::EgHwVolume as u32 {
+            Ok(SoyBoyParameter::EgHwVolume)
+        } else if id == SoyBoyParameter::EgHwDirection as u32 {
+            Ok(SoyBoyParameter::EgHwDirection)
+        } else if id == SoyBoyParameter::EgHwPeriod as u32 {
+            Ok(SoyBoyParameter::EgHwPeriod)
+        } else if id == SoyBoyParameter::EgAttackCurve as u32 {
+            Ok(SoyBoyParameter::EgAttackCurve)
+        } else if id == SoyBoyParameter::EgAttackTension as u32 {
+            Ok(SoyBoyParameter::EgAttackTension)
+        } else if id == SoyBoyParameter::EgDecayCurve as u32 {
+            Ok(SoyBoyParameter::EgDecayCurve)
+        } else if id == SoyBoyParameter::EgDecayTension as u32 {
+            Ok(SoyBoyParameter::EgDecayTension)
+        } else if id == SoyBoyParameter::EgReleaseCurve as u32 {
+            Ok(SoyBoyParameter::EgReleaseCurve)
+        } else if id == SoyBoyParameter::EgReleaseTension as u32 {
+            Ok(SoyBoyParameter::EgReleaseTension)
+        } else if id == SoyBoyParameter::EgHold as u32 {
+            Ok(SoyBoyParameter::EgHold)
+        } else if id == SoyBoyParameter::EgLoop as u32 {
+            Ok(SoyBoyParameter::EgLoop)
+        } else if id == SoyBoyParameter::VelocityAmount as u32 {
+            Ok(SoyBoyParameter::VelocityAmount)
+        } else if id == SoyBoyParameter::VelocityCurve as u32 {
+            Ok(SoyBoyParameter::VelocityCurve)
+        } else if id == SoyBoyParameter::VelocityToAttack as u32 {
+            Ok(SoyBoyParameter::VelocityToAttack)
+        } else if id == SoyBoyParameter::VelocityToStutter as u32 {
+            Ok(SoyBoyParameter::VelocityToStutter)
+        } else if id == SoyBoyParameter::Lfo1Rate as u32 {
+            Ok(SoyBoyParameter::Lfo1Rate)
+        } else if id == SoyBoyParameter::Lfo1Shape as u32 {
+            Ok(SoyBoyParameter::Lfo1Shape)
+        } else if id == SoyBoyParameter::Lfo2Rate as u32 {
+            Ok(SoyBoyParameter::Lfo2Rate)
+        } else if id == SoyBoyParameter::Lfo2Shape as u32 {
+            Ok(SoyBoyParameter::Lfo2Shape)
+        } else if id == SoyBoyParameter::ModEnvAttack as u32 {
+            Ok(SoyBoyParameter::ModEnvAttack)
+        } else if id == SoyBoyParameter::ModEnvDecay as u32 {
+            Ok(SoyBoyParameter::ModEnvDecay)
+        } else if id == SoyBoyParameter::ModWheel as u32 {
+            Ok(SoyBoyParameter::ModWheel)
+        } else if id == SoyBoyParameter::Aftertouch as u32 {
+            Ok(SoyBoyParameter::Aftertouch)
+        } else if id == SoyBoyParameter::ModSlot1Source as u32 {
+            Ok(SoyBoyParameter::ModSlot1Source)
+        } else if id == SoyBoyParameter::ModSlot1Destination as u32 {
+            Ok(SoyBoyParameter::ModSlot1Destination)
+        } else if id == SoyBoyParameter::ModSlot1Amount as u32 {
+            Ok(SoyBoyParameter::ModSlot1Amount)
+        } else if id == SoyBoyParameter::ModSlot2Source as u32 {
+            Ok(SoyBoyParameter::ModSlot2Source)
+        } else if id == SoyBoyParameter::ModSlot2Destination as u32 {
+            Ok(SoyBoyParameter::ModSlot2Destination)
+        } else if id == SoyBoyParameter::ModSlot2Amount as u32 {
+            Ok(SoyBoyParameter::ModSlot2Amount)
+        } else if id == SoyBoyParameter::ModSlot3Source as u32 {
+            Ok(SoyBoyParameter::ModSlot3Source)
+        } else if id == SoyBoyParameter::ModSlot3Destination as u32 {
+            Ok(SoyBoyParameter::ModSlot3Destination)
+        } else if id == SoyBoyParameter::ModSlot3Amount as u32 {
+            Ok(SoyBoyParameter::ModSlot3Amount)
+        } else if id == SoyBoyParameter::ModSlot4Source as u32 {
+            Ok(SoyBoyParameter::ModSlot4Source)
+        } else if id == SoyBoyParameter::ModSlot4Destination as u32 {
+            Ok(SoyBoyParameter::ModSlot4Destination)
+        } else if id == SoyBoyParameter::ModSlot4Amount as u32 {
+            Ok(SoyBoyParameter::ModSlot4Amount)
+        } else if id == SoyBoyParameter::ModSlot5Source as u32 {
+            Ok(SoyBoyParameter::ModSlot5Source)
+        } else if id == SoyBoyParameter::ModSlot5Destination as u32 {
+            Ok(SoyBoyParameter::ModSlot5Destination)
+        } else if id == SoyBoyParameter::ModSlot5Amount as u32 {
+            Ok(SoyBoyParameter::ModSlot5Amount)
+        } else if id == SoyBoyParameter::ModSlot6Source as u32 {
+            Ok(SoyBoyParameter::ModSlot6Source)
+        } else if id == SoyBoyParameter::ModSlot6Destination as u32 {
+            Ok(SoyBoyParameter::ModSlot6Destination)
+        } else if id == SoyBoyParameter::ModSlot6Amount as u32 {
+            Ok(SoyBoyParameter::ModSlot6Amount)
+        } else if id == SoyBoyParameter::ModSlot7Source as u32 {
+            Ok(SoyBoyParameter::ModSlot7Source)
+        } else if id == SoyBoyParameter::ModSlot7Destination as u32 {
+            Ok(SoyBoyParameter::ModSlot7Destination)
+        } else if id == SoyBoyParameter::ModSlot7Amount as u32 {
+            Ok(SoyBoyParameter::ModSlot7Amount)
+        } else if id == SoyBoyParameter::ModSlot8Source as u32 {
+            Ok(SoyBoyParameter::ModSlot8Source)
+        } else if id == SoyBoyParameter::ModSlot8Destination as u32 {
+            Ok(SoyBoyParameter::ModSlot8Destination)
+        } else if id == SoyBoyParameter::ModSlot8Amount as u32 {
+            Ok(SoyBoyParameter::ModSlot8Amount)
+        } else if id == SoyBoyParameter::KitMode as u32 {
+            Ok(SoyBoyParameter::KitMode)
+        } else if id == SoyBoyParameter::KitPad as u32 {
+            Ok(SoyBoyParameter::KitPad)
+        } else if id == SoyBoyParameter::KitNoteLow as u32 {
+            Ok(SoyBoyParameter::KitNoteLow)
+        } else if id == SoyBoyParameter::KitNoteHigh as u32 {
+            Ok(SoyBoyParameter::KitNoteHigh)
+        } else if id == SoyBoyParameter::KitChokeGroup as u32 {
+            Ok(SoyBoyParameter::KitChokeGroup)
+        } else if id == SoyBoyParameter::KitPitchMode as u32 {
+            Ok(SoyBoyParameter::KitPitchMode)
+        } else if id == SoyBoyParameter::KitFixedNote as u32 {
+            Ok(SoyBoyParameter::KitFixedNote)
+        } else if id == SoyBoyParameter::PartMode as u32 {
+            Ok(SoyBoyParameter::PartMode)
+        } else if id == SoyBoyParameter::PartEdit as u32 {
+            Ok(SoyBoyParameter::PartEdit)
+        } else if id == SoyBoyParameter::PartVoices as u32 {
+            Ok(SoyBoyParameter::PartVoices)
+        } else if id == SoyBoyParameter::PartLevel as u32 {
+            Ok(SoyBoyParameter::PartLevel)
+        } else if id == SoyBoyParameter::PartOutput as u32 {
+            Ok(SoyBoyParameter::PartOutput)
+        } else if id == SoyBoyParameter::SplitMode as u32 {
+            Ok(SoyBoyParameter::SplitMode)
+        } else if id == SoyBoyParameter::Zone1KeyLow as u32 {
+            Ok(SoyBoyParameter::Zone1KeyLow)
+        } else if id == SoyBoyParameter::Zone1KeyHigh as u32 {
+            Ok(SoyBoyParameter::Zone1KeyHigh)
+        } else if id == SoyBoyParameter::Zone1VelocityLow as u32 {
+            Ok(SoyBoyParameter::Zone1VelocityLow)
+        } else if id == SoyBoyParameter::Zone1VelocityHigh as u32 {
+            Ok(SoyBoyParameter::Zone1VelocityHigh)
+        } else if id == SoyBoyParameter::Zone1Oscillator as u32 {
+            Ok(SoyBoyParameter::Zone1Oscillator)
+        } else if id == SoyBoyParameter::Zone1Transpose as u32 {
+            Ok(SoyBoyParameter::Zone1Transpose)
+        } else if id == SoyBoyParameter::Zone1Duty as u32 {
+            Ok(SoyBoyParameter::Zone1Duty)
+        } else if id == SoyBoyParameter::Zone1Wavetable as u32 {
+            Ok(SoyBoyParameter::Zone1Wavetable)
+        } else if id == SoyBoyParameter::Zone2KeyLow as u32 {
+            Ok(SoyBoyParameter::Zone2KeyLow)
+        } else if id == SoyBoyParameter::Zone2KeyHigh as u32 {
+            Ok(SoyBoyParameter::Zone2KeyHigh)
+        } else if id == SoyBoyParameter::Zone2VelocityLow as u32 {
+            Ok(SoyBoyParameter::Zone2VelocityLow)
+        } else if id == SoyBoyParameter::Zone2VelocityHigh as u32 {
+            Ok(SoyBoyParameter::Zone2VelocityHigh)
+        } else if id == SoyBoyParameter::Zone2Oscillator as u32 {
+            Ok(SoyBoyParameter::Zone2Oscillator)
+        } else if id == SoyBoyParameter::Zone2Transpose as u32 {
+            Ok(SoyBoyParameter::Zone2Transpose)
+        } else if id == SoyBoyParameter::Zone2Duty as u32 {
+            Ok(SoyBoyParameter::Zone2Duty)
+        } else if id == SoyBoyParameter::Zone2Wavetable as u32 {
+            Ok(SoyBoyParameter::Zone2Wavetable)
+        } else if id == SoyBoyParameter::Zone3KeyLow as u32 {
+            Ok(SoyBoyParameter::Zone3KeyLow)
+        } else if id == SoyBoyParameter::Zone3KeyHigh as u32 {
+            Ok(SoyBoyParameter::Zone3KeyHigh)
+        } else if id == SoyBoyParameter::Zone3VelocityLow as u32 {
+            Ok(SoyBoyParameter::Zone3VelocityLow)
+        } else if id == SoyBoyParameter::Zone3VelocityHigh as u32 {
+            Ok(SoyBoyParameter::Zone3VelocityHigh)
+        } else if id == SoyBoyParameter::Zone3Oscillator as u32 {
+            Ok(SoyBoyParameter::Zone3Oscillator)
+        } else if id == SoyBoyParameter::Zone3Transpose as u32 {
+            Ok(SoyBoyParameter::Zone3Transpose)
+        } else if id == SoyBoyParameter::Zone3Duty as u32 {
+            Ok(SoyBoyParameter::Zone3Duty)
+        } else if id == SoyBoyParameter::Zone3Wavetable as u32 {
+            Ok(SoyBoyParameter::Zone3Wavetable)
+        } else if id == SoyBoyParameter::Zone4KeyLow as u32 {
+            Ok(SoyBoyParameter::Zone4KeyLow)
+        } else if id == SoyBoyParameter::Zone4KeyHigh as u32 {
+            Ok(SoyBoyParameter::Zone4KeyHigh)
+        } else if id == SoyBoyParameter::Zone4VelocityLow as u32 {
+            Ok(SoyBoyParameter::Zone4VelocityLow)
+        } else if id == SoyBoyParameter::Zone4VelocityHigh as u32 {
+            Ok(SoyBoyParameter::Zone4VelocityHigh)
+        } else if id == SoyBoyParameter::Zone4Oscillator as u32 {
+            Ok(SoyBoyParameter::Zone4Oscillator)
+        } else if id == SoyBoyParameter::Zone4Transpose as u32 {
+            Ok(SoyBoyParameter::Zone4Transpose)
+        } else if id == SoyBoyParameter::Zone4Duty as u32 {
+            Ok(SoyBoyParameter::Zone4Duty)
+        } else if id == SoyBoyParameter::Zone4Wavetable as u32 {
+            Ok(SoyBoyParameter::Zone4Wavetable)
+        } else if id == SoyBoyParameter::Sustain as u32 {
+            Ok(SoyBoyParameter::Sustain)
+        } else if id == SoyBoyParameter::Sostenuto as u32 {
+            Ok(SoyBoyParameter::Sostenuto)
+        } else if id == SoyBoyParameter::Breath as u32 {
+            Ok(SoyBoyParameter::Breath)
+        } else if id == SoyBoyParameter::ModWheelDestination as u32 {
+            Ok(SoyBoyParameter::ModWheelDestination)
+        } else if id == SoyBoyParameter::ModWheelAmount as u32 {
+            Ok(SoyBoyParameter::ModWheelAmount)
+        } else if id == SoyBoyParameter::AftertouchDestination as u32 {
+            Ok(SoyBoyParameter::AftertouchDestination)
+        } else if id == SoyBoyParameter::AftertouchAmount as u32 {
+            Ok(SoyBoyParameter::AftertouchAmount)
+        } else if id == SoyBoyParameter::BreathDestination as u32 {
+            Ok(SoyBoyParameter::BreathDestination)
+        } else if id == SoyBoyParameter::BreathAmount as u32 {
+            Ok(SoyBoyParameter::BreathAmount)
+        } else if id == SoyBoyParameter::BendUp as u32 {
+            Ok(SoyBoyParameter::BendUp)
+        } else if id == SoyBoyParameter::BendDown as u32 {
+            Ok(SoyBoyParameter::BendDown)
+        } else if id == SoyBoyParameter::BendQuantize as u32 {
+            Ok(SoyBoyParameter::BendQuantize)
+        } else if id == SoyBoyParameter::MpeZone as u32 {
+            Ok(SoyBoyParameter::MpeZone)
+        } else if id == SoyBoyParameter::MpeChannels as u32 {
+            Ok(SoyBoyParameter::MpeChannels)
+        } else if id == SoyBoyParameter::MpeBendRange as u32 {
+            Ok(SoyBoyParameter::MpeBendRange)
+        } else if id == SoyBoyParameter::TimbreDestination as u32 {
+            Ok(SoyBoyParameter::TimbreDestination)
+        } else if id == SoyBoyParameter::TimbreAmount as u32 {
+            Ok(SoyBoyParameter::TimbreAmount)
+        } else if id == SoyBoyParameter::PolyPressureDestination as u32 {
+            Ok(SoyBoyParameter::PolyPressureDestination)
+        } else if id == SoyBoyParameter::PolyPressureAmount as u32 {
+            Ok(SoyBoyParameter::PolyPressureAmount)
+        } else if id == SoyBoyParameter::SmoothingTime as u32 {
+            Ok(SoyBoyParameter::SmoothingTime)
+        } else if id == SoyBoyParameter::MasterHeadroom as u32 {
+            Ok(SoyBoyParameter::MasterHeadroom)
         } else {
             Err(())
         }
@@ -409,23 +848,126 @@ fn make_global_parameters(params: &mut HashMap<SoyBoyParameter, ParameterDef>) {
             default_value: 0.0,
         },
     );
-    static GLOBAL_PITCH: IntegerParameter = IntegerParameter {
-        min: -4800,
-        max: 4800,
+    // the position of the pitch bend wheel, which is scaled with the bend ranges
+    static GLOBAL_PITCH: LinearParameter = LinearParameter {
+        min: -1.0,
+        max: 1.0,
     };
     params.insert(
         SoyBoyParameter::PitchBend,
         ParameterDef {
-            r#type: ParameterType::Integer,
-            parameter: ParameterInfo { int: GLOBAL_PITCH },
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo {
+                linear: GLOBAL_PITCH,
+            },
             title: "Pitch".to_string(),
             short_title: "Pitch".to_string(),
-            unit_name: "cent".to_string(),
-            step_count: GLOBAL_PITCH.max.abs() + GLOBAL_PITCH.min.abs(),
+            unit_name: "".to_string(),
+            step_count: 0,
+            default_value: 0.0,
+        },
+    );
+    static BEND_RANGE: IntegerParameter = IntegerParameter { min: 0, max: 48 };
+    params.insert(
+        SoyBoyParameter::BendUp,
+        ParameterDef {
+            r#type: ParameterType::Integer,
+            parameter: ParameterInfo { int: BEND_RANGE },
+            title: "Bend up".to_string(),
+            short_title: "Bend up".to_string(),
+            unit_name: "semitone".to_string(),
+            step_count: BEND_RANGE.max - BEND_RANGE.min,
+            default_value: 2.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::BendDown,
+        ParameterDef {
+            r#type: ParameterType::Integer,
+            parameter: ParameterInfo { int: BEND_RANGE },
+            title: "Bend down".to_string(),
+            short_title: "Bend down".to_string(),
+            unit_name: "semitone".to_string(),
+            step_count: BEND_RANGE.max - BEND_RANGE.min,
+            default_value: 2.0,
+        },
+    );
+    static BEND_QUANTIZE_LIST: [&str; 2] = ["Off", "On"];
+    static BEND_QUANTIZE: ListParameter = ListParameter {
+        elements: &BEND_QUANTIZE_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::BendQuantize,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo {
+                list: BEND_QUANTIZE,
+            },
+            title: "Bend quantize".to_string(),
+            short_title: "Bend quantize".to_string(),
+            unit_name: "".to_string(),
+            step_count: (BEND_QUANTIZE.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+
+    // the ramp time of the continuous parameters which would click when they jump
+    static SMOOTHING_TIME: LinearParameter = LinearParameter {
+        min: 0.0,
+        max: 200.0,
+    };
+    params.insert(
+        SoyBoyParameter::SmoothingTime,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo {
+                linear: SMOOTHING_TIME,
+            },
+            title: "Smoothing time".to_string(),
+            short_title: "Smoothing".to_string(),
+            unit_name: "ms".to_string(),
+            step_count: 0,
+            default_value: 10.0,
+        },
+    );
+
+    static MASTER_CLIP_LIST: [&str; 3] = ["Off", "Soft clip", "Limiter"];
+    static MASTER_CLIP: ListParameter = ListParameter {
+        elements: &MASTER_CLIP_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::MasterClip,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: MASTER_CLIP },
+            title: "Master clip".to_string(),
+            short_title: "Clip".to_string(),
+            unit_name: "".to_string(),
+            step_count: (MASTER_CLIP.denormalize(1.0)) as i32,
             default_value: 0.0,
         },
     );
 
+    // scales the master volume down by the number of voices to keep headroom for chords
+    static MASTER_HEADROOM_LIST: [&str; 2] = ["Off", "On"];
+    static MASTER_HEADROOM: ListParameter = ListParameter {
+        elements: &MASTER_HEADROOM_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::MasterHeadroom,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo {
+                list: MASTER_HEADROOM,
+            },
+            title: "Master headroom".to_string(),
+            short_title: "Headroom".to_string(),
+            unit_name: "".to_string(),
+            step_count: (MASTER_HEADROOM.denormalize(1.0)) as i32,
+            default_value: 1.0,
+        },
+    );
+
     static SELECTED_OSCILLATOR_LIST: [&str; 3] = ["Square", "Noise", "Wavetable"];
     static SELECTED_OSC: ListParameter = ListParameter {
         elements: &SELECTED_OSCILLATOR_LIST,
@@ -556,6 +1098,69 @@ fn make_global_parameters(params: &mut HashMap<SoyBoyParameter, ParameterDef>) {
             default_value: 1.0,
         },
     );
+
+    static STUTTER_COUNT: IntegerParameter = IntegerParameter { min: 0, max: 16 };
+    params.insert(
+        SoyBoyParameter::StutterCount,
+        ParameterDef {
+            r#type: ParameterType::Integer,
+            parameter: ParameterInfo { int: STUTTER_COUNT },
+            title: "Stutter count".to_string(),
+            short_title: "Stutter count".to_string(),
+            unit_name: "".to_string(),
+            step_count: STUTTER_COUNT.max - STUTTER_COUNT.min,
+            default_value: 0.0,
+        },
+    );
+    static STUTTER_PITCH: IntegerParameter = IntegerParameter { min: -12, max: 12 };
+    params.insert(
+        SoyBoyParameter::StutterPitch,
+        ParameterDef {
+            r#type: ParameterType::Integer,
+            parameter: ParameterInfo { int: STUTTER_PITCH },
+            title: "Stutter pitch".to_string(),
+            short_title: "Stutter pitch".to_string(),
+            unit_name: "semitone".to_string(),
+            step_count: STUTTER_PITCH.max - STUTTER_PITCH.min,
+            default_value: 0.0,
+        },
+    );
+    static STUTTER_PING_PONG_LIST: [&str; 2] = ["Off", "On"];
+    static STUTTER_PING_PONG: ListParameter = ListParameter {
+        elements: &STUTTER_PING_PONG_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::StutterPingPong,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo {
+                list: STUTTER_PING_PONG,
+            },
+            title: "Stutter ping-pong".to_string(),
+            short_title: "Stutter pp".to_string(),
+            unit_name: "".to_string(),
+            step_count: (STUTTER_PING_PONG.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+    static STUTTER_PROBABILITY: LinearParameter = LinearParameter {
+        min: 0.0,
+        max: 100.0,
+    };
+    params.insert(
+        SoyBoyParameter::StutterProbability,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo {
+                linear: STUTTER_PROBABILITY,
+            },
+            title: "Stutter probability".to_string(),
+            short_title: "Stutter prob".to_string(),
+            unit_name: "%".to_string(),
+            step_count: 0,
+            default_value: 100.0,
+        },
+    );
 }
 
 pub fn make_square_oscillator_parameters(params: &mut HashMap<SoyBoyParameter, ParameterDef>) {
@@ -655,6 +1260,176 @@ pub fn make_envelope_generator_parameters(params: &mut HashMap<SoyBoyParameter,
             default_value: 0.1,
         },
     );
+    params.insert(
+        SoyBoyParameter::EgHold,
+        ParameterDef {
+            r#type: ParameterType::NonLinear,
+            parameter: ParameterInfo {
+                non_linear: EG_TIME,
+            },
+            title: "Eg: Hold".to_string(),
+            short_title: "Hold".to_string(),
+            unit_name: "s".to_string(),
+            step_count: 0,
+            default_value: 0.0,
+        },
+    );
+    static EG_LOOP_LIST: [&str; 2] = ["Off", "On"];
+    static EG_LOOP: ListParameter = ListParameter {
+        elements: &EG_LOOP_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::EgLoop,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: EG_LOOP },
+            title: "Eg: Loop (stutter wins)".to_string(),
+            short_title: "Loop".to_string(),
+            unit_name: "".to_string(),
+            step_count: (EG_LOOP.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+    static EG_CURVE_LIST: [&str; 3] = ["Linear", "Exponential", "Logarithmic"];
+    static EG_CURVE: ListParameter = ListParameter {
+        elements: &EG_CURVE_LIST,
+    };
+    static EG_TENSION: LinearParameter = LinearParameter {
+        min: 1.0,
+        max: 10.0,
+    };
+    params.insert(
+        SoyBoyParameter::EgAttackCurve,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: EG_CURVE },
+            title: "Eg: Attack curve".to_string(),
+            short_title: "Attack curve".to_string(),
+            unit_name: "".to_string(),
+            step_count: (EG_CURVE.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::EgAttackTension,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo { linear: EG_TENSION },
+            title: "Eg: Attack tension".to_string(),
+            short_title: "Attack tension".to_string(),
+            unit_name: "".to_string(),
+            step_count: 0,
+            default_value: 4.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::EgDecayCurve,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: EG_CURVE },
+            title: "Eg: Decay curve".to_string(),
+            short_title: "Decay curve".to_string(),
+            unit_name: "".to_string(),
+            step_count: (EG_CURVE.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::EgDecayTension,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo { linear: EG_TENSION },
+            title: "Eg: Decay tension".to_string(),
+            short_title: "Decay tension".to_string(),
+            unit_name: "".to_string(),
+            step_count: 0,
+            default_value: 4.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::EgReleaseCurve,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: EG_CURVE },
+            title: "Eg: Release curve".to_string(),
+            short_title: "Release curve".to_string(),
+            unit_name: "".to_string(),
+            step_count: (EG_CURVE.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::EgReleaseTension,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo { linear: EG_TENSION },
+            title: "Eg: Release tension".to_string(),
+            short_title: "Release tension".to_string(),
+            unit_name: "".to_string(),
+            step_count: 0,
+            default_value: 4.0,
+        },
+    );
+    static EG_MODE_LIST: [&str; 2] = ["ADSR", "Hardware"];
+    static EG_MODE: ListParameter = ListParameter {
+        elements: &EG_MODE_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::EgMode,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: EG_MODE },
+            title: "Eg: Mode".to_string(),
+            short_title: "Mode".to_string(),
+            unit_name: "".to_string(),
+            step_count: (EG_MODE.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+    static EG_HW_VOLUME: IntegerParameter = IntegerParameter { min: 0, max: 15 };
+    params.insert(
+        SoyBoyParameter::EgHwVolume,
+        ParameterDef {
+            r#type: ParameterType::Integer,
+            parameter: ParameterInfo { int: EG_HW_VOLUME },
+            title: "Eg: HW volume".to_string(),
+            short_title: "HW volume".to_string(),
+            unit_name: "".to_string(),
+            step_count: EG_HW_VOLUME.max - EG_HW_VOLUME.min,
+            default_value: 15.0,
+        },
+    );
+    static EG_HW_DIRECTION_LIST: [&str; 2] = ["Down", "Up"];
+    static EG_HW_DIRECTION: ListParameter = ListParameter {
+        elements: &EG_HW_DIRECTION_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::EgHwDirection,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo {
+                list: EG_HW_DIRECTION,
+            },
+            title: "Eg: HW direction".to_string(),
+            short_title: "HW direction".to_string(),
+            unit_name: "".to_string(),
+            step_count: (EG_HW_DIRECTION.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+    static EG_HW_PERIOD: IntegerParameter = IntegerParameter { min: 0, max: 7 };
+    params.insert(
+        SoyBoyParameter::EgHwPeriod,
+        ParameterDef {
+            r#type: ParameterType::Integer,
+            parameter: ParameterInfo { int: EG_HW_PERIOD },
+            title: "Eg: HW period".to_string(),
+            short_title: "HW period".to_string(),
+            unit_name: "/64s".to_string(),
+            step_count: EG_HW_PERIOD.max - EG_HW_PERIOD.min,
+            default_value: 3.0,
+        },
+    );
     static EG_SUSTAIN: LinearParameter = LinearParameter { min: 0.0, max: 1.0 };
     params.insert(
         SoyBoyParameter::EgSustain,
@@ -713,20 +1488,1214 @@ fn make_dac_parameters(params: &mut HashMap<SoyBoyParameter, ParameterDef>) {
             default_value: 0.5,
         },
     );
+    static FILTER_TYPE_LIST: [&str; 4] = ["LP", "HP", "BP", "Notch"];
+    static FILTER_TYPE: ListParameter = ListParameter {
+        elements: &FILTER_TYPE_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::FilterType,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: FILTER_TYPE },
+            title: "Filter: Type".to_string(),
+            short_title: "Filter type".to_string(),
+            unit_name: "".to_string(),
+            step_count: (FILTER_TYPE.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+}
+
+fn make_filter_envelope_parameters(params: &mut HashMap<SoyBoyParameter, ParameterDef>) {
+    static FILTER_ENV_AMOUNT: LinearParameter = LinearParameter {
+        min: -8.0,
+        max: 8.0,
+    };
+    params.insert(
+        SoyBoyParameter::FilterEnvAmount,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo {
+                linear: FILTER_ENV_AMOUNT,
+            },
+            title: "Filter: Env amount".to_string(),
+            short_title: "Env amount".to_string(),
+            unit_name: "oct".to_string(),
+            step_count: 0,
+            default_value: 0.0,
+        },
+    );
+    static FILTER_ENV_TIME: NonLinearParameter = NonLinearParameter {
+        plain_zero: 0.00,
+        plain_min: 0.01,
+        plain_max: 2.0,
+        plain_one: 2.0,
+        factor: 1.4,
+        diverge: true,
+    };
+    params.insert(
+        SoyBoyParameter::FilterEnvAttack,
+        ParameterDef {
+            r#type: ParameterType::NonLinear,
+            parameter: ParameterInfo {
+                non_linear: FILTER_ENV_TIME,
+            },
+            title: "Filter: Env attack".to_string(),
+            short_title: "Env attack".to_string(),
+            unit_name: "s".to_string(),
+            step_count: 0,
+            default_value: 0.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::FilterEnvDecay,
+        ParameterDef {
+            r#type: ParameterType::NonLinear,
+            parameter: ParameterInfo {
+                non_linear: FILTER_ENV_TIME,
+            },
+            title: "Filter: Env decay".to_string(),
+            short_title: "Env decay".to_string(),
+            unit_name: "s".to_string(),
+            step_count: 0,
+            default_value: 0.3,
+        },
+    );
+    static FILTER_PERCENTAGE: LinearParameter = LinearParameter {
+        min: 0.0,
+        max: 100.0,
+    };
+    params.insert(
+        SoyBoyParameter::FilterKeyTracking,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo {
+                linear: FILTER_PERCENTAGE,
+            },
+            title: "Filter: Key tracking".to_string(),
+            short_title: "Key track".to_string(),
+            unit_name: "%".to_string(),
+            step_count: 0,
+            default_value: 0.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::FilterVelocity,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo {
+                linear: FILTER_PERCENTAGE,
+            },
+            title: "Filter: Velocity".to_string(),
+            short_title: "Velocity".to_string(),
+            unit_name: "%".to_string(),
+            step_count: 0,
+            default_value: 0.0,
+        },
+    );
+}
+
+fn make_velocity_parameters(params: &mut HashMap<SoyBoyParameter, ParameterDef>) {
+    static VELOCITY_PERCENTAGE: LinearParameter = LinearParameter {
+        min: 0.0,
+        max: 100.0,
+    };
+    params.insert(
+        SoyBoyParameter::VelocityAmount,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo {
+                linear: VELOCITY_PERCENTAGE,
+            },
+            title: "Velocity: Amount".to_string(),
+            short_title: "Amount".to_string(),
+            unit_name: "%".to_string(),
+            step_count: 0,
+            default_value: 100.0,
+        },
+    );
+    static VELOCITY_CURVE_LIST: [&str; 3] = ["Linear", "Exponential", "4-bit steps"];
+    static VELOCITY_CURVE: ListParameter = ListParameter {
+        elements: &VELOCITY_CURVE_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::VelocityCurve,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo {
+                list: VELOCITY_CURVE,
+            },
+            title: "Velocity: Curve".to_string(),
+            short_title: "Curve".to_string(),
+            unit_name: "".to_string(),
+            step_count: (VELOCITY_CURVE.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::VelocityToAttack,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo {
+                linear: VELOCITY_PERCENTAGE,
+            },
+            title: "Velocity: To attack".to_string(),
+            short_title: "To attack".to_string(),
+            unit_name: "%".to_string(),
+            step_count: 0,
+            default_value: 0.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::VelocityToStutter,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo {
+                linear: VELOCITY_PERCENTAGE,
+            },
+            title: "Velocity: To stutter".to_string(),
+            short_title: "To stutter".to_string(),
+            unit_name: "%".to_string(),
+            step_count: 0,
+            default_value: 0.0,
+        },
+    );
+}
+
+fn make_modulation_parameters(params: &mut HashMap<SoyBoyParameter, ParameterDef>) {
+    static LFO_RATE: NonLinearParameter = NonLinearParameter {
+        plain_zero: 0.05,
+        plain_min: 0.05,
+        plain_max: 20.0,
+        plain_one: 20.0,
+        factor: 2.0,
+        diverge: true,
+    };
+    static LFO_SHAPE_LIST: [&str; 4] = ["Triangle", "Square", "Saw", "S&H"];
+    static LFO_SHAPE: ListParameter = ListParameter {
+        elements: &LFO_SHAPE_LIST,
+    };
+    let lfos = [
+        (SoyBoyParameter::Lfo1Rate, SoyBoyParameter::Lfo1Shape),
+        (SoyBoyParameter::Lfo2Rate, SoyBoyParameter::Lfo2Shape),
+    ];
+    for (i, (rate, shape)) in lfos.iter().enumerate() {
+        params.insert(
+            *rate,
+            ParameterDef {
+                r#type: ParameterType::NonLinear,
+                parameter: ParameterInfo {
+                    non_linear: LFO_RATE,
+                },
+                title: format!("LFO {}: Rate", i + 1),
+                short_title: "Rate".to_string(),
+                unit_name: "Hz".to_string(),
+                step_count: 0,
+                default_value: 5.0,
+            },
+        );
+        params.insert(
+            *shape,
+            ParameterDef {
+                r#type: ParameterType::List,
+                parameter: ParameterInfo { list: LFO_SHAPE },
+                title: format!("LFO {}: Shape", i + 1),
+                short_title: "Shape".to_string(),
+                unit_name: "".to_string(),
+                step_count: (LFO_SHAPE.denormalize(1.0)) as i32,
+                default_value: 0.0,
+            },
+        );
+    }
+
+    static MOD_ENV_TIME: NonLinearParameter = NonLinearParameter {
+        plain_zero: 0.00,
+        plain_min: 0.01,
+        plain_max: 2.0,
+        plain_one: 2.0,
+        factor: 1.4,
+        diverge: true,
+    };
+    params.insert(
+        SoyBoyParameter::ModEnvAttack,
+        ParameterDef {
+            r#type: ParameterType::NonLinear,
+            parameter: ParameterInfo {
+                non_linear: MOD_ENV_TIME,
+            },
+            title: "Mod env: Attack".to_string(),
+            short_title: "Attack".to_string(),
+            unit_name: "s".to_string(),
+            step_count: 0,
+            default_value: 0.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::ModEnvDecay,
+        ParameterDef {
+            r#type: ParameterType::NonLinear,
+            parameter: ParameterInfo {
+                non_linear: MOD_ENV_TIME,
+            },
+            title: "Mod env: Decay".to_string(),
+            short_title: "Decay".to_string(),
+            unit_name: "s".to_string(),
+            step_count: 0,
+            default_value: 0.5,
+        },
+    );
+
+    static CONTROLLER: LinearParameter = LinearParameter { min: 0.0, max: 1.0 };
+    params.insert(
+        SoyBoyParameter::ModWheel,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo { linear: CONTROLLER },
+            title: "Mod wheel".to_string(),
+            short_title: "Mod wheel".to_string(),
+            unit_name: "".to_string(),
+            step_count: 0,
+            default_value: 0.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::Aftertouch,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo { linear: CONTROLLER },
+            title: "Aftertouch".to_string(),
+            short_title: "Aftertouch".to_string(),
+            unit_name: "".to_string(),
+            step_count: 0,
+            default_value: 0.0,
+        },
+    );
+
+    static MOD_SOURCE_LIST: [&str; 11] = [
+        "Off",
+        "LFO 1",
+        "LFO 2",
+        "Mod env",
+        "Velocity",
+        "Key",
+        "Mod wheel",
+        "Aftertouch",
+        "Random",
+        "Breath",
+        "Timbre",
+    ];
+    static MOD_SOURCE: ListParameter = ListParameter {
+        elements: &MOD_SOURCE_LIST,
+    };
+    static MOD_DESTINATION_LIST: [&str; 8] = [
+        "Off",
+        "Pitch",
+        "Duty",
+        "Wavetable index",
+        "Noise rate",
+        "Pan",
+        "Filter cutoff",
+        "Stutter time",
+    ];
+    static MOD_DESTINATION: ListParameter = ListParameter {
+        elements: &MOD_DESTINATION_LIST,
+    };
+    static MOD_AMOUNT: LinearParameter = LinearParameter {
+        min: -100.0,
+        max: 100.0,
+    };
+    let slots = [
+        (
+            SoyBoyParameter::ModSlot1Source,
+            SoyBoyParameter::ModSlot1Destination,
+            SoyBoyParameter::ModSlot1Amount,
+        ),
+        (
+            SoyBoyParameter::ModSlot2Source,
+            SoyBoyParameter::ModSlot2Destination,
+            SoyBoyParameter::ModSlot2Amount,
+        ),
+        (
+            SoyBoyParameter::ModSlot3Source,
+            SoyBoyParameter::ModSlot3Destination,
+            SoyBoyParameter::ModSlot3Amount,
+        ),
+        (
+            SoyBoyParameter::ModSlot4Source,
+            SoyBoyParameter::ModSlot4Destination,
+            SoyBoyParameter::ModSlot4Amount,
+        ),
+        (
+            SoyBoyParameter::ModSlot5Source,
+            SoyBoyParameter::ModSlot5Destination,
+            SoyBoyParameter::ModSlot5Amount,
+        ),
+        (
+            SoyBoyParameter::ModSlot6Source,
+            SoyBoyParameter::ModSlot6Destination,
+            SoyBoyParameter::ModSlot6Amount,
+        ),
+        (
+            SoyBoyParameter::ModSlot7Source,
+            SoyBoyParameter::ModSlot7Destination,
+            SoyBoyParameter::ModSlot7Amount,
+        ),
+        (
+            SoyBoyParameter::ModSlot8Source,
+            SoyBoyParameter::ModSlot8Destination,
+            SoyBoyParameter::ModSlot8Amount,
+        ),
+    ];
+    for (i, (source, destination, amount)) in slots.iter().enumerate() {
+        params.insert(
+            *source,
+            ParameterDef {
+                r#type: ParameterType::List,
+                parameter: ParameterInfo { list: MOD_SOURCE },
+                title: format!("Mod {}: Source", i + 1),
+                short_title: "Source".to_string(),
+                unit_name: "".to_string(),
+                step_count: (MOD_SOURCE.denormalize(1.0)) as i32,
+                default_value: 0.0,
+            },
+        );
+        params.insert(
+            *destination,
+            ParameterDef {
+                r#type: ParameterType::List,
+                parameter: ParameterInfo {
+                    list: MOD_DESTINATION,
+                },
+                title: format!("Mod {}: Destination", i + 1),
+                short_title: "Destination".to_string(),
+                unit_name: "".to_string(),
+                step_count: (MOD_DESTINATION.denormalize(1.0)) as i32,
+                default_value: 0.0,
+            },
+        );
+        params.insert(
+            *amount,
+            ParameterDef {
+                r#type: ParameterType::Linear,
+                parameter: ParameterInfo { linear: MOD_AMOUNT },
+                title: format!("Mod {}: Amount", i + 1),
+                short_title: "Amount".to_string(),
+                unit_name: "%".to_string(),
+                step_count: 0,
+                default_value: 0.0,
+            },
+        );
+    }
+}
+
+fn make_kit_parameters(params: &mut HashMap<SoyBoyParameter, ParameterDef>) {
+    static KIT_MODE_LIST: [&str; 2] = ["Off", "On"];
+    static KIT_MODE: ListParameter = ListParameter {
+        elements: &KIT_MODE_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::KitMode,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: KIT_MODE },
+            title: "Kit: Mode".to_string(),
+            short_title: "Kit mode".to_string(),
+            unit_name: "".to_string(),
+            step_count: (KIT_MODE.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+    static KIT_PAD: IntegerParameter = IntegerParameter { min: 1, max: 16 };
+    params.insert(
+        SoyBoyParameter::KitPad,
+        ParameterDef {
+            r#type: ParameterType::Integer,
+            parameter: ParameterInfo { int: KIT_PAD },
+            title: "Kit: Pad".to_string(),
+            short_title: "Pad".to_string(),
+            unit_name: "".to_string(),
+            step_count: KIT_PAD.max - KIT_PAD.min,
+            default_value: 1.0,
+        },
+    );
+    static KIT_NOTE: IntegerParameter = IntegerParameter { min: 0, max: 127 };
+    params.insert(
+        SoyBoyParameter::KitNoteLow,
+        ParameterDef {
+            r#type: ParameterType::Integer,
+            parameter: ParameterInfo { int: KIT_NOTE },
+            title: "Kit: Note low".to_string(),
+            short_title: "Note low".to_string(),
+            unit_name: "".to_string(),
+            step_count: KIT_NOTE.max - KIT_NOTE.min,
+            default_value: 36.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::KitNoteHigh,
+        ParameterDef {
+            r#type: ParameterType::Integer,
+            parameter: ParameterInfo { int: KIT_NOTE },
+            title: "Kit: Note high".to_string(),
+            short_title: "Note high".to_string(),
+            unit_name: "".to_string(),
+            step_count: KIT_NOTE.max - KIT_NOTE.min,
+            default_value: 36.0,
+        },
+    );
+    static KIT_CHOKE_GROUP: IntegerParameter = IntegerParameter { min: 0, max: 4 };
+    params.insert(
+        SoyBoyParameter::KitChokeGroup,
+        ParameterDef {
+            r#type: ParameterType::Integer,
+            parameter: ParameterInfo {
+                int: KIT_CHOKE_GROUP,
+            },
+            title: "Kit: Choke group".to_string(),
+            short_title: "Choke".to_string(),
+            unit_name: "".to_string(),
+            step_count: KIT_CHOKE_GROUP.max - KIT_CHOKE_GROUP.min,
+            default_value: 0.0,
+        },
+    );
+    static KIT_PITCH_MODE_LIST: [&str; 2] = ["Key", "Fixed"];
+    static KIT_PITCH_MODE: ListParameter = ListParameter {
+        elements: &KIT_PITCH_MODE_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::KitPitchMode,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo {
+                list: KIT_PITCH_MODE,
+            },
+            title: "Kit: Pitch".to_string(),
+            short_title: "Pitch".to_string(),
+            unit_name: "".to_string(),
+            step_count: (KIT_PITCH_MODE.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::KitFixedNote,
+        ParameterDef {
+            r#type: ParameterType::Integer,
+            parameter: ParameterInfo { int: KIT_NOTE },
+            title: "Kit: Fixed note".to_string(),
+            short_title: "Fixed note".to_string(),
+            unit_name: "".to_string(),
+            step_count: KIT_NOTE.max - KIT_NOTE.min,
+            default_value: 60.0,
+        },
+    );
+}
+
+fn make_multitimbral_parameters(params: &mut HashMap<SoyBoyParameter, ParameterDef>) {
+    static PART_MODE_LIST: [&str; 2] = ["Off", "On"];
+    static PART_MODE: ListParameter = ListParameter {
+        elements: &PART_MODE_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::PartMode,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: PART_MODE },
+            title: "Multi: Mode".to_string(),
+            short_title: "Multi mode".to_string(),
+            unit_name: "".to_string(),
+            step_count: (PART_MODE.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+    static PART_EDIT: IntegerParameter = IntegerParameter { min: 1, max: 4 };
+    params.insert(
+        SoyBoyParameter::PartEdit,
+        ParameterDef {
+            r#type: ParameterType::Integer,
+            parameter: ParameterInfo { int: PART_EDIT },
+            title: "Multi: Part".to_string(),
+            short_title: "Part".to_string(),
+            unit_name: "".to_string(),
+            step_count: PART_EDIT.max - PART_EDIT.min,
+            default_value: 1.0,
+        },
+    );
+    static PART_VOICES: IntegerParameter = IntegerParameter { min: 1, max: 8 };
+    params.insert(
+        SoyBoyParameter::PartVoices,
+        ParameterDef {
+            r#type: ParameterType::Integer,
+            parameter: ParameterInfo { int: PART_VOICES },
+            title: "Multi: Voices".to_string(),
+            short_title: "Voices".to_string(),
+            unit_name: "".to_string(),
+            step_count: PART_VOICES.max - PART_VOICES.min,
+            default_value: 1.0,
+        },
+    );
+    static PART_LEVEL: LinearParameter = LinearParameter {
+        min: 0.0,
+        max: 100.0,
+    };
+    params.insert(
+        SoyBoyParameter::PartLevel,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo { linear: PART_LEVEL },
+            title: "Multi: Level".to_string(),
+            short_title: "Level".to_string(),
+            unit_name: "%".to_string(),
+            step_count: 0,
+            default_value: 100.0,
+        },
+    );
+    static PART_OUTPUT_LIST: [&str; 3] = ["Left", "Both", "Right"];
+    static PART_OUTPUT: ListParameter = ListParameter {
+        elements: &PART_OUTPUT_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::PartOutput,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: PART_OUTPUT },
+            title: "Multi: Output".to_string(),
+            short_title: "Output".to_string(),
+            unit_name: "".to_string(),
+            step_count: (PART_OUTPUT.denormalize(1.0)) as i32,
+            default_value: 1.0,
+        },
+    );
+}
+
+fn make_split_zone_parameters(params: &mut HashMap<SoyBoyParameter, ParameterDef>) {
+    static SPLIT_MODE_LIST: [&str; 2] = ["Off", "On"];
+    static SPLIT_MODE: ListParameter = ListParameter {
+        elements: &SPLIT_MODE_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::SplitMode,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: SPLIT_MODE },
+            title: "Split: Mode".to_string(),
+            short_title: "Split mode".to_string(),
+            unit_name: "".to_string(),
+            step_count: (SPLIT_MODE.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+
+    static ZONE_MIDI_VALUE: IntegerParameter = IntegerParameter { min: 0, max: 127 };
+    static ZONE_OSCILLATOR_LIST: [&str; 4] = ["Patch", "Square", "Noise", "Wavetable"];
+    static ZONE_OSCILLATOR: ListParameter = ListParameter {
+        elements: &ZONE_OSCILLATOR_LIST,
+    };
+    static ZONE_TRANSPOSE: IntegerParameter = IntegerParameter { min: -24, max: 24 };
+    static ZONE_DUTY_LIST: [&str; 5] = ["Patch", "12.5%", "25%", "50%", "75%"];
+    static ZONE_DUTY: ListParameter = ListParameter {
+        elements: &ZONE_DUTY_LIST,
+    };
+    static ZONE_WAVETABLE: IntegerParameter = IntegerParameter { min: 0, max: 16 };
+    let zones = [
+        (
+            SoyBoyParameter::Zone1KeyLow,
+            SoyBoyParameter::Zone1KeyHigh,
+            SoyBoyParameter::Zone1VelocityLow,
+            SoyBoyParameter::Zone1VelocityHigh,
+            SoyBoyParameter::Zone1Oscillator,
+            SoyBoyParameter::Zone1Transpose,
+            SoyBoyParameter::Zone1Duty,
+            SoyBoyParameter::Zone1Wavetable,
+        ),
+        (
+            SoyBoyParameter::Zone2KeyLow,
+            SoyBoyParameter::Zone2KeyHigh,
+            SoyBoyParameter::Zone2VelocityLow,
+            SoyBoyParameter::Zone2VelocityHigh,
+            SoyBoyParameter::Zone2Oscillator,
+            SoyBoyParameter::Zone2Transpose,
+            SoyBoyParameter::Zone2Duty,
+            SoyBoyParameter::Zone2Wavetable,
+        ),
+        (
+            SoyBoyParameter::Zone3KeyLow,
+            SoyBoyParameter::Zone3KeyHigh,
+            SoyBoyParameter::Zone3VelocityLow,
+            SoyBoyParameter::Zone3VelocityHigh,
+            SoyBoyParameter::Zone3Oscillator,
+            SoyBoyParameter::Zone3Transpose,
+            SoyBoyParameter::Zone3Duty,
+            SoyBoyParameter::Zone3Wavetable,
+        ),
+        (
+            SoyBoyParameter::Zone4KeyLow,
+            SoyBoyParameter::Zone4KeyHigh,
+            SoyBoyParameter::Zone4VelocityLow,
+            SoyBoyParameter::Zone4VelocityHigh,
+            SoyBoyParameter::Zone4Oscillator,
+            SoyBoyParameter::Zone4Transpose,
+            SoyBoyParameter::Zone4Duty,
+            SoyBoyParameter::Zone4Wavetable,
+        ),
+    ];
+    for (
+        i,
+        (key_low, key_high, velocity_low, velocity_high, oscillator, transpose, duty, wavetable),
+    ) in zones.iter().enumerate()
+    {
+        let ranges = [
+            (key_low, "Key low", "Key low", 0.0),
+            (key_high, "Key high", "Key high", 127.0),
+            (velocity_low, "Velocity low", "Vel low", 0.0),
+            (velocity_high, "Velocity high", "Vel high", 127.0),
+        ];
+        for (param, title, short_title, default_value) in ranges.iter() {
+            params.insert(
+                **param,
+                ParameterDef {
+                    r#type: ParameterType::Integer,
+                    parameter: ParameterInfo {
+                        int: ZONE_MIDI_VALUE,
+                    },
+                    title: format!("Zone {}: {}", i + 1, title),
+                    short_title: short_title.to_string(),
+                    unit_name: "".to_string(),
+                    step_count: ZONE_MIDI_VALUE.max - ZONE_MIDI_VALUE.min,
+                    default_value: *default_value,
+                },
+            );
+        }
+        params.insert(
+            *oscillator,
+            ParameterDef {
+                r#type: ParameterType::List,
+                parameter: ParameterInfo {
+                    list: ZONE_OSCILLATOR,
+                },
+                title: format!("Zone {}: Osc type", i + 1),
+                short_title: "Osc type".to_string(),
+                unit_name: "".to_string(),
+                step_count: (ZONE_OSCILLATOR.denormalize(1.0)) as i32,
+                default_value: 0.0,
+            },
+        );
+        params.insert(
+            *transpose,
+            ParameterDef {
+                r#type: ParameterType::Integer,
+                parameter: ParameterInfo {
+                    int: ZONE_TRANSPOSE,
+                },
+                title: format!("Zone {}: Transpose", i + 1),
+                short_title: "Transpose".to_string(),
+                unit_name: "semitone".to_string(),
+                step_count: ZONE_TRANSPOSE.max - ZONE_TRANSPOSE.min,
+                default_value: 0.0,
+            },
+        );
+        params.insert(
+            *duty,
+            ParameterDef {
+                r#type: ParameterType::List,
+                parameter: ParameterInfo { list: ZONE_DUTY },
+                title: format!("Zone {}: Duty", i + 1),
+                short_title: "Duty".to_string(),
+                unit_name: "".to_string(),
+                step_count: (ZONE_DUTY.denormalize(1.0)) as i32,
+                default_value: 0.0,
+            },
+        );
+        params.insert(
+            *wavetable,
+            ParameterDef {
+                r#type: ParameterType::Integer,
+                parameter: ParameterInfo {
+                    int: ZONE_WAVETABLE,
+                },
+                title: format!("Zone {}: Wavetable (kit pad)", i + 1),
+                short_title: "Pad wave".to_string(),
+                unit_name: "".to_string(),
+                step_count: ZONE_WAVETABLE.max - ZONE_WAVETABLE.min,
+                default_value: 0.0,
+            },
+        );
+    }
+}
+
+fn make_pedal_parameters(params: &mut HashMap<SoyBoyParameter, ParameterDef>) {
+    // pedals are down at 64 or more in MIDI
+    static PEDAL: LinearParameter = LinearParameter { min: 0.0, max: 1.0 };
+    params.insert(
+        SoyBoyParameter::Sustain,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo { linear: PEDAL },
+            title: "Sustain pedal".to_string(),
+            short_title: "Sustain".to_string(),
+            unit_name: "".to_string(),
+            step_count: 0,
+            default_value: 0.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::Sostenuto,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo { linear: PEDAL },
+            title: "Sostenuto pedal".to_string(),
+            short_title: "Sostenuto".to_string(),
+            unit_name: "".to_string(),
+            step_count: 0,
+            default_value: 0.0,
+        },
+    );
+}
+
+fn make_controller_parameters(params: &mut HashMap<SoyBoyParameter, ParameterDef>) {
+    static CONTROLLER: LinearParameter = LinearParameter { min: 0.0, max: 1.0 };
+    params.insert(
+        SoyBoyParameter::Breath,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo { linear: CONTROLLER },
+            title: "Breath".to_string(),
+            short_title: "Breath".to_string(),
+            unit_name: "".to_string(),
+            step_count: 0,
+            default_value: 0.0,
+        },
+    );
+
+    static CONTROLLER_DESTINATION_LIST: [&str; 5] = [
+        "Off",
+        "Vibrato",
+        "Volume",
+        "Filter cutoff",
+        "Volume + cutoff",
+    ];
+    static CONTROLLER_DESTINATION: ListParameter = ListParameter {
+        elements: &CONTROLLER_DESTINATION_LIST,
+    };
+    static CONTROLLER_AMOUNT: LinearParameter = LinearParameter {
+        min: -100.0,
+        max: 100.0,
+    };
+    // mod wheel to vibrato and aftertouch to volume and cutoff by default, like many leads
+    let routes = [
+        (
+            "Mod wheel",
+            SoyBoyParameter::ModWheelDestination,
+            SoyBoyParameter::ModWheelAmount,
+            1.0,
+            100.0,
+        ),
+        (
+            "Aftertouch",
+            SoyBoyParameter::AftertouchDestination,
+            SoyBoyParameter::AftertouchAmount,
+            4.0,
+            50.0,
+        ),
+        (
+            "Breath",
+            SoyBoyParameter::BreathDestination,
+            SoyBoyParameter::BreathAmount,
+            0.0,
+            100.0,
+        ),
+        (
+            "MPE timbre",
+            SoyBoyParameter::TimbreDestination,
+            SoyBoyParameter::TimbreAmount,
+            3.0,
+            50.0,
+        ),
+    ];
+    for (name, destination, amount, default_destination, default_amount) in routes.iter() {
+        params.insert(
+            *destination,
+            ParameterDef {
+                r#type: ParameterType::List,
+                parameter: ParameterInfo {
+                    list: CONTROLLER_DESTINATION,
+                },
+                title: format!("{}: Destination", name),
+                short_title: "Destination".to_string(),
+                unit_name: "".to_string(),
+                step_count: (CONTROLLER_DESTINATION.denormalize(1.0)) as i32,
+                default_value: *default_destination,
+            },
+        );
+        params.insert(
+            *amount,
+            ParameterDef {
+                r#type: ParameterType::Linear,
+                parameter: ParameterInfo {
+                    linear: CONTROLLER_AMOUNT,
+                },
+                title: format!("{}: Amount", name),
+                short_title: "Amount".to_string(),
+                unit_name: "%".to_string(),
+                step_count: 0,
+                default_value: *default_amount,
+            },
+        );
+    }
+
+    // poly pressure plays only the pressed key, so it has its own destinations
+    static POLY_PRESSURE_DESTINATION_LIST: [&str; 5] =
+        ["Off", "Volume", "Vibrato", "Duty", "Wavetable scan"];
+    static POLY_PRESSURE_DESTINATION: ListParameter = ListParameter {
+        elements: &POLY_PRESSURE_DESTINATION_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::PolyPressureDestination,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo {
+                list: POLY_PRESSURE_DESTINATION,
+            },
+            title: "Poly pressure: Destination".to_string(),
+            short_title: "Destination".to_string(),
+            unit_name: "".to_string(),
+            step_count: (POLY_PRESSURE_DESTINATION.denormalize(1.0)) as i32,
+            default_value: 1.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::PolyPressureAmount,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo {
+                linear: CONTROLLER_AMOUNT,
+            },
+            title: "Poly pressure: Amount".to_string(),
+            short_title: "Amount".to_string(),
+            unit_name: "%".to_string(),
+            step_count: 0,
+            default_value: 50.0,
+        },
+    );
+}
+
+fn make_mpe_parameters(params: &mut HashMap<SoyBoyParameter, ParameterDef>) {
+    static MPE_ZONE_LIST: [&str; 3] = ["Off", "Lower", "Upper"];
+    static MPE_ZONE: ListParameter = ListParameter {
+        elements: &MPE_ZONE_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::MpeZone,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: MPE_ZONE },
+            title: "MPE: Zone".to_string(),
+            short_title: "Zone".to_string(),
+            unit_name: "".to_string(),
+            step_count: (MPE_ZONE.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+    static MPE_CHANNELS: IntegerParameter = IntegerParameter { min: 1, max: 15 };
+    params.insert(
+        SoyBoyParameter::MpeChannels,
+        ParameterDef {
+            r#type: ParameterType::Integer,
+            parameter: ParameterInfo { int: MPE_CHANNELS },
+            title: "MPE: Member channels".to_string(),
+            short_title: "Channels".to_string(),
+            unit_name: "".to_string(),
+            step_count: MPE_CHANNELS.max - MPE_CHANNELS.min,
+            default_value: 15.0,
+        },
+    );
+    static MPE_BEND_RANGE: IntegerParameter = IntegerParameter { min: 0, max: 96 };
+    params.insert(
+        SoyBoyParameter::MpeBendRange,
+        ParameterDef {
+            r#type: ParameterType::Integer,
+            parameter: ParameterInfo {
+                int: MPE_BEND_RANGE,
+            },
+            title: "MPE: Bend range".to_string(),
+            short_title: "Bend range".to_string(),
+            unit_name: "semitone".to_string(),
+            step_count: MPE_BEND_RANGE.max - MPE_BEND_RANGE.min,
+            default_value: 48.0,
+        },
+    );
+}
+
+fn make_tempo_sync_parameters(params: &mut HashMap<SoyBoyParameter, ParameterDef>) {
+    static TIME_MODE_LIST: [&str; 2] = ["Free", "Sync"];
+    static TIME_MODE: ListParameter = ListParameter {
+        elements: &TIME_MODE_LIST,
+    };
+    static NOTE_DIVISION_LIST: [&str; 19] = [
+        "1/1", "1/2D", "1/2", "1/2T", "1/4D", "1/4", "1/4T", "1/8D", "1/8", "1/8T", "1/16D",
+        "1/16", "1/16T", "1/32D", "1/32", "1/32T", "1/64D", "1/64", "1/64T",
+    ];
+    static NOTE_DIVISION: ListParameter = ListParameter {
+        elements: &NOTE_DIVISION_LIST,
+    };
+
+    params.insert(
+        SoyBoyParameter::SweepSync,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: TIME_MODE },
+            title: "Sweep sync".to_string(),
+            short_title: "Sweep sync".to_string(),
+            unit_name: "".to_string(),
+            step_count: (TIME_MODE.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::SweepDivision,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo {
+                list: NOTE_DIVISION,
+            },
+            title: "Sweep division".to_string(),
+            short_title: "Sweep div".to_string(),
+            unit_name: "".to_string(),
+            step_count: (NOTE_DIVISION.denormalize(1.0)) as i32,
+            default_value: 17.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::StutterSync,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: TIME_MODE },
+            title: "Stutter sync".to_string(),
+            short_title: "Stutter sync".to_string(),
+            unit_name: "".to_string(),
+            step_count: (TIME_MODE.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::StutterDivision,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo {
+                list: NOTE_DIVISION,
+            },
+            title: "Stutter division".to_string(),
+            short_title: "Stutter div".to_string(),
+            unit_name: "".to_string(),
+            step_count: (NOTE_DIVISION.denormalize(1.0)) as i32,
+            default_value: 11.0,
+        },
+    );
+}
+
+pub fn make_delay_parameters(params: &mut HashMap<SoyBoyParameter, ParameterDef>) {
+    static DELAY_MIX: LinearParameter = LinearParameter {
+        min: 0.0,
+        max: 100.0,
+    };
+    params.insert(
+        SoyBoyParameter::DelayMix,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo { linear: DELAY_MIX },
+            title: "Delay mix".to_string(),
+            short_title: "Delay mix".to_string(),
+            unit_name: "%".to_string(),
+            step_count: 0,
+            default_value: 0.0,
+        },
+    );
+    static DELAY_TIME: NonLinearParameter = NonLinearParameter {
+        plain_zero: 0.01,
+        plain_min: 0.01,
+        plain_max: 2.0,
+        plain_one: 2.0,
+        factor: 2.0,
+        diverge: true,
+    };
+    params.insert(
+        SoyBoyParameter::DelayTime,
+        ParameterDef {
+            r#type: ParameterType::NonLinear,
+            parameter: ParameterInfo {
+                non_linear: DELAY_TIME,
+            },
+            title: "Delay time".to_string(),
+            short_title: "Delay time".to_string(),
+            unit_name: "s".to_string(),
+            step_count: 0,
+            default_value: 0.25,
+        },
+    );
+    static DELAY_SYNC_LIST: [&str; 2] = ["Free", "Sync"];
+    static DELAY_SYNC: ListParameter = ListParameter {
+        elements: &DELAY_SYNC_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::DelaySync,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: DELAY_SYNC },
+            title: "Delay sync".to_string(),
+            short_title: "Delay sync".to_string(),
+            unit_name: "".to_string(),
+            step_count: (DELAY_SYNC.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+    static DELAY_DIVISION_LIST: [&str; 19] = [
+        "1/1", "1/2D", "1/2", "1/2T", "1/4D", "1/4", "1/4T", "1/8D", "1/8", "1/8T", "1/16D",
+        "1/16", "1/16T", "1/32D", "1/32", "1/32T", "1/64D", "1/64", "1/64T",
+    ];
+    static DELAY_DIVISION: ListParameter = ListParameter {
+        elements: &DELAY_DIVISION_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::DelayDivision,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo {
+                list: DELAY_DIVISION,
+            },
+            title: "Delay division".to_string(),
+            short_title: "Delay div".to_string(),
+            unit_name: "".to_string(),
+            step_count: (DELAY_DIVISION.denormalize(1.0)) as i32,
+            default_value: 7.0,
+        },
+    );
+    static DELAY_FEEDBACK: LinearParameter = LinearParameter {
+        min: 0.0,
+        max: 95.0,
+    };
+    params.insert(
+        SoyBoyParameter::DelayFeedback,
+        ParameterDef {
+            r#type: ParameterType::Linear,
+            parameter: ParameterInfo {
+                linear: DELAY_FEEDBACK,
+            },
+            title: "Delay feedback".to_string(),
+            short_title: "Delay fb".to_string(),
+            unit_name: "%".to_string(),
+            step_count: 0,
+            default_value: 40.0,
+        },
+    );
+    static DELAY_SWITCH_LIST: [&str; 2] = ["Off", "On"];
+    static DELAY_SWITCH: ListParameter = ListParameter {
+        elements: &DELAY_SWITCH_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::DelayPingPong,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: DELAY_SWITCH },
+            title: "Delay ping-pong".to_string(),
+            short_title: "Delay pp".to_string(),
+            unit_name: "".to_string(),
+            step_count: (DELAY_SWITCH.denormalize(1.0)) as i32,
+            default_value: 0.0,
+        },
+    );
+    params.insert(
+        SoyBoyParameter::DelayQuantize,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo { list: DELAY_SWITCH },
+            title: "Delay 4bit".to_string(),
+            short_title: "Delay 4bit".to_string(),
+            unit_name: "".to_string(),
+            step_count: (DELAY_SWITCH.denormalize(1.0)) as i32,
+            default_value: 1.0,
+        },
+    );
+}
+
+pub fn make_bitcrusher_parameters(params: &mut HashMap<SoyBoyParameter, ParameterDef>) {
+    static CRUSH_BITS: IntegerParameter = IntegerParameter { min: 1, max: 16 };
+    params.insert(
+        SoyBoyParameter::CrushBits,
+        ParameterDef {
+            r#type: ParameterType::Integer,
+            parameter: ParameterInfo { int: CRUSH_BITS },
+            title: "Bit depth".to_string(),
+            short_title: "Bits".to_string(),
+            unit_name: "bit".to_string(),
+            step_count: CRUSH_BITS.max - CRUSH_BITS.min,
+            default_value: 16.0,
+        },
+    );
+    static CRUSH_RATE: NonLinearParameter = NonLinearParameter {
+        plain_zero: 500.0,
+        plain_min: 500.0,
+        plain_max: 48_000.0,
+        plain_one: 48_000.0,
+        factor: 2.0,
+        diverge: true,
+    };
+    params.insert(
+        SoyBoyParameter::CrushRate,
+        ParameterDef {
+            r#type: ParameterType::NonLinear,
+            parameter: ParameterInfo {
+                non_linear: CRUSH_RATE,
+            },
+            title: "Sample rate".to_string(),
+            short_title: "Rate".to_string(),
+            unit_name: "Hz".to_string(),
+            step_count: 0,
+            default_value: 48_000.0,
+        },
+    );
+    static CRUSH_ANTI_ALIAS_LIST: [&str; 2] = ["Off", "On"];
+    static CRUSH_ANTI_ALIAS: ListParameter = ListParameter {
+        elements: &CRUSH_ANTI_ALIAS_LIST,
+    };
+    params.insert(
+        SoyBoyParameter::CrushAntiAlias,
+        ParameterDef {
+            r#type: ParameterType::List,
+            parameter: ParameterInfo {
+                list: CRUSH_ANTI_ALIAS,
+            },
+            title: "Anti-alias".to_string(),
+            short_title: "Anti-alias".to_string(),
+            unit_name: "".to_string(),
+            step_count: (CRUSH_ANTI_ALIAS.denormalize(1.0)) as i32,
+            default_value: 1.0,
+        },
+    );
 }
 
 pub fn make_parameter_info() -> HashMap<SoyBoyParameter, ParameterDef> {
     let mut params = HashMap::new();
 
     make_global_parameters(&mut params);
+    make_tempo_sync_parameters(&mut params);
 
     make_square_oscillator_parameters(&mut params);
     make_noise_oscillator_parameters(&mut params);
     make_wavetable_oscillator_parameters(&mut params);
 
     make_envelope_generator_parameters(&mut params);
+    make_velocity_parameters(&mut params);
+    make_modulation_parameters(&mut params);
+    make_kit_parameters(&mut params);
+    make_multitimbral_parameters(&mut params);
+    make_split_zone_parameters(&mut params);
+    make_pedal_parameters(&mut params);
+    make_controller_parameters(&mut params);
+    make_mpe_parameters(&mut params);
 
     make_dac_parameters(&mut params);
+    make_filter_envelope_parameters(&mut params);
+    make_bitcrusher_parameters(&mut params);
+    make_delay_parameters(&mut params);
 
     params
 }
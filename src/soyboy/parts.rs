@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use crate::{
+    common::{constants, i4},
+    soyboy::{
+        event::{Event, Triggered},
+        parameters::{make_parameter_info, ParameterDef, SoyBoyParameter},
+        patch::Patch,
+        voice::VoiceUnit,
+        wave_table::WaveTableOscillator,
+    },
+};
+
+/// Like the GB's APU: pulse with sweep, pulse, wave and noise.
+pub const NUMBER_OF_PARTS: usize = 4;
+/// The oscillator types the parts start with, in the order of the APU's channels.
+const PART_OSCILLATORS: [f64; NUMBER_OF_PARTS] = [0.0, 0.0, 2.0, 1.0];
+
+/// Routes a part to the left, right or both outputs like the GB's NR51 register.
+#[derive(Debug, Copy, Clone)]
+enum PartOutput {
+    Left = 0,
+    Both,
+    Right,
+}
+
+impl TryFrom<u32> for PartOutput {
+    type Error = ();
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        if id == PartOutput::Left as u32 {
+            Ok(PartOutput::Left)
+        } else if id == PartOutput::Both as u32 {
+            Ok(PartOutput::Both)
+        } else if id == PartOutput::Right as u32 {
+            Ok(PartOutput::Right)
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// Holds a patch for each part of the multitimbral mode. MIDI channels 1 to 4 play
+/// the parts 1 to 4, and each part has its own voice limit, level and output.
+pub struct Parts {
+    enabled: bool,
+    selected: usize,
+    parts: Vec<Patch>,
+
+    param_defs: HashMap<SoyBoyParameter, ParameterDef>,
+    wavetable_editor: WaveTableOscillator,
+}
+
+impl Parts {
+    pub fn new() -> Self {
+        let param_defs = make_parameter_info();
+        let parts = PART_OSCILLATORS
+            .iter()
+            .map(|osc| {
+                let mut part = Patch::new(&param_defs);
+                part.set(&SoyBoyParameter::OscillatorType, *osc);
+                part
+            })
+            .collect();
+
+        Self {
+            enabled: false,
+            selected: 0,
+            parts,
+
+            param_defs,
+            wavetable_editor: WaveTableOscillator::new(),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn select(&mut self, part: usize) {
+        self.selected = part.min(NUMBER_OF_PARTS - 1);
+    }
+
+    /// Returns the selected part's value of `param`.
+    pub fn get(&self, param: &SoyBoyParameter) -> f64 {
+        self.parts[self.selected].get(param)
+    }
+
+    /// Sets `value` of `param` to the selected part.
+    pub fn set(&mut self, param: &SoyBoyParameter, value: f64) {
+        self.parts[self.selected].set(param, value);
+    }
+
+    pub fn wavetable(&self) -> [i4; constants::WAVETABLE_SIZE] {
+        self.parts[self.selected].wavetable()
+    }
+
+    pub fn set_wavetable(&mut self, wavetable: &[i4; constants::WAVETABLE_SIZE]) {
+        self.parts[self.selected].set_wavetable(wavetable);
+    }
+
+    /// Returns the part played on the MIDI `channel`, which starts from 0.
+    pub fn part_for_channel(&self, channel: i16) -> Option<usize> {
+        if (0..NUMBER_OF_PARTS as i16).contains(&channel) {
+            Some(channel as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn voice_limit(&self, part: usize) -> usize {
+        self.parts[part].get(&SoyBoyParameter::PartVoices) as usize
+    }
+
+    /// Returns the part's gains to the left and right outputs.
+    pub fn gain(&self, part: usize) -> (f64, f64) {
+        let part = &self.parts[part];
+        let level = part.get(&SoyBoyParameter::PartLevel) / 100.0;
+
+        match PartOutput::try_from(part.get(&SoyBoyParameter::PartOutput) as u32) {
+            Ok(PartOutput::Left) => (level, 0.0),
+            Ok(PartOutput::Right) => (0.0, level),
+            _ => (level, level),
+        }
+    }
+
+    /// Sets the part's patch to `voice`.
+    pub fn apply(&self, part: usize, voice: &mut VoiceUnit) {
+        self.parts[part].apply(voice, &self.param_defs);
+    }
+
+    pub fn get_params(&self) -> Vec<Vec<f64>> {
+        self.parts
+            .iter()
+            .map(|part| part.params().to_vec())
+            .collect()
+    }
+
+    pub fn get_wavetables(&self) -> Vec<[i4; constants::WAVETABLE_SIZE]> {
+        self.parts.iter().map(|part| part.wavetable()).collect()
+    }
+
+    /// Restores the parts from a plugin state. Parts missing in the state are left as they are.
+    pub fn set_parts(
+        &mut self,
+        params: &[Vec<f64>],
+        wavetables: &[[i4; constants::WAVETABLE_SIZE]],
+    ) {
+        for (part, values) in self.parts.iter_mut().zip(params.iter()) {
+            part.restore(values);
+        }
+        for (part, wavetable) in self.parts.iter_mut().zip(wavetables.iter()) {
+            part.set_wavetable(wavetable);
+        }
+    }
+}
+
+impl Triggered for Parts {
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::SetWaveTable { .. }
+            | Event::ResetWaveTableAsSine
+            | Event::ResetWaveTableAtRandom => {
+                self.parts[self.selected].edit_wavetable(event, &mut self.wavetable_editor);
+            }
+            _ => (),
+        }
+    }
+}
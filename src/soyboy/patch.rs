@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{
+    common::{constants, i4},
+    soyboy::{
+        event::{Event, Triggered},
+        parameters::{ParameterDef, Parametric, SoyBoyParameter},
+        voice::VoiceUnit,
+        wave_table::WaveTableOscillator,
+        zones::is_zone_parameter,
+    },
+};
+
+/// Returns whether `param` belongs to a patch which voices play.
+/// The others are shared by the whole instrument, or choose which patch to edit.
+pub fn is_patch_parameter(param: &SoyBoyParameter) -> bool {
+    !is_zone_parameter(param)
+        && !matches!(
+            param,
+            SoyBoyParameter::MasterVolume
+                | SoyBoyParameter::PitchBend
+                | SoyBoyParameter::NumVoices
+                | SoyBoyParameter::MasterClip
+                | SoyBoyParameter::CrushBits
+                | SoyBoyParameter::CrushRate
+                | SoyBoyParameter::CrushAntiAlias
+                | SoyBoyParameter::DelayMix
+                | SoyBoyParameter::DelayTime
+                | SoyBoyParameter::DelaySync
+                | SoyBoyParameter::DelayDivision
+                | SoyBoyParameter::DelayFeedback
+                | SoyBoyParameter::DelayPingPong
+                | SoyBoyParameter::DelayQuantize
+                | SoyBoyParameter::ModWheel
+                | SoyBoyParameter::Aftertouch
+                | SoyBoyParameter::KitMode
+                | SoyBoyParameter::KitPad
+                | SoyBoyParameter::PartMode
+                | SoyBoyParameter::PartEdit
+                | SoyBoyParameter::PartVoices
+                | SoyBoyParameter::PartLevel
+                | SoyBoyParameter::PartOutput
+                | SoyBoyParameter::Sustain
+                | SoyBoyParameter::Sostenuto
+                | SoyBoyParameter::Breath
+                | SoyBoyParameter::MpeZone
+                | SoyBoyParameter::MpeChannels
+                | SoyBoyParameter::MpeBendRange
+                | SoyBoyParameter::SmoothingTime
+                | SoyBoyParameter::MasterHeadroom
+        )
+}
+
+/// Revisions are unique across all patches so that a voice can tell which patch and
+/// which edit of it was applied last.
+static NEXT_REVISION: AtomicU64 = AtomicU64::new(1);
+
+fn next_revision() -> u64 {
+    NEXT_REVISION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// A set of parameter values and a wavetable. `params` is indexed by parameter IDs.
+pub struct Patch {
+    params: Vec<f64>,
+    wavetable: [i4; constants::WAVETABLE_SIZE],
+    // changes at every edit
+    revision: u64,
+}
+
+impl Patch {
+    pub fn new(param_defs: &HashMap<SoyBoyParameter, ParameterDef>) -> Self {
+        let mut params = vec![0.0; SoyBoyParameter::iter().count()];
+        for param in SoyBoyParameter::iter() {
+            params[param as usize] = param_defs.get(&param).unwrap().default_value;
+        }
+
+        Self {
+            params,
+            wavetable: WaveTableOscillator::new().get_wavetable(),
+            revision: next_revision(),
+        }
+    }
+
+    pub fn get(&self, param: &SoyBoyParameter) -> f64 {
+        self.params[*param as usize]
+    }
+
+    pub fn set(&mut self, param: &SoyBoyParameter, value: f64) {
+        self.params[*param as usize] = value;
+        self.revision = next_revision();
+    }
+
+    pub fn params(&self) -> &[f64] {
+        &self.params
+    }
+
+    pub fn wavetable(&self) -> [i4; constants::WAVETABLE_SIZE] {
+        self.wavetable
+    }
+
+    pub fn set_wavetable(&mut self, wavetable: &[i4; constants::WAVETABLE_SIZE]) {
+        self.wavetable = *wavetable;
+        self.revision = next_revision();
+    }
+
+    /// Restores the values from a plugin state. Parameters missing in the state
+    /// keep their values.
+    pub fn restore(&mut self, params: &[f64]) {
+        let len = self.params.len().min(params.len());
+        self.params[..len].copy_from_slice(&params[..len]);
+        self.revision = next_revision();
+    }
+
+    /// Sets the patch to `voice`. It is skipped when the voice already has this revision.
+    pub fn apply(
+        &self,
+        voice: &mut VoiceUnit,
+        param_defs: &HashMap<SoyBoyParameter, ParameterDef>,
+    ) {
+        if voice.patch_revision() == Some(self.revision) {
+            return;
+        }
+
+        for param in SoyBoyParameter::iter().filter(is_patch_parameter) {
+            let param_def = param_defs.get(&param).unwrap();
+            voice.set_param(&param, param_def, self.params[param as usize]);
+        }
+        voice.set_wavetable(&self.wavetable);
+        voice.set_patch_revision(self.revision);
+    }
+
+    /// Edits the wavetable with `editor` for the wavetable events.
+    pub fn edit_wavetable(&mut self, event: &Event, editor: &mut WaveTableOscillator) {
+        editor.set_wavetable(&self.wavetable);
+        editor.trigger(event);
+        self.wavetable = editor.get_wavetable();
+        self.revision = next_revision();
+    }
+}
@@ -0,0 +1,103 @@
+/// Moves a continuous parameter's value to its target linearly in the ramp time
+/// to avoid zipper noise and clicks.
+#[derive(Debug, Copy, Clone)]
+pub struct Smoother {
+    value: f64,
+    target: f64,
+    // in seconds
+    ramp_time: f64,
+    // per second
+    rate: f64,
+}
+
+impl Smoother {
+    pub fn new(value: f64) -> Self {
+        Self {
+            value,
+            target: value,
+            ramp_time: 0.0,
+            rate: 0.0,
+        }
+    }
+
+    /// Sets the ramp time in seconds. It takes effect from the next target.
+    pub fn set_ramp_time(&mut self, seconds: f64) {
+        self.ramp_time = seconds.max(0.0);
+    }
+
+    pub fn ramp_time(&self) -> f64 {
+        self.ramp_time
+    }
+
+    pub fn set_target(&mut self, target: f64) {
+        self.target = target;
+        if self.ramp_time > 0.0 && self.value.is_finite() && target.is_finite() {
+            self.rate = (target - self.value) / self.ramp_time;
+        } else {
+            self.finish();
+        }
+    }
+
+    pub fn target(&self) -> f64 {
+        self.target
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn is_smoothing(&self) -> bool {
+        self.value != self.target
+    }
+
+    /// Reaches the target at once, e.g. when a new note starts.
+    pub fn finish(&mut self) {
+        self.value = self.target;
+        self.rate = 0.0;
+    }
+
+    /// Advances the value by a sample and returns it.
+    pub fn process(&mut self, sample_rate: f64) -> f64 {
+        if self.is_smoothing() {
+            self.value += self.rate / sample_rate;
+
+            let reached = if self.rate > 0.0 {
+                self.value >= self.target
+            } else {
+                self.value <= self.target
+            };
+            if reached {
+                self.finish();
+            }
+        }
+
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Smoother;
+
+    #[test]
+    fn test_smoother_ramps_in_ramp_time() {
+        let mut smoother = Smoother::new(0.0);
+        smoother.set_ramp_time(0.25);
+        smoother.set_target(1.0);
+
+        assert_eq!(0.25, smoother.process(16.0));
+        assert_eq!(0.5, smoother.process(16.0));
+        assert_eq!(0.75, smoother.process(16.0));
+        assert_eq!(1.0, smoother.process(16.0));
+        assert!(!smoother.is_smoothing());
+    }
+
+    #[test]
+    fn test_smoother_without_ramp_time_jumps() {
+        let mut smoother = Smoother::new(0.0);
+        smoother.set_target(-1.0);
+
+        assert!(!smoother.is_smoothing());
+        assert_eq!(-1.0, smoother.process(16.0));
+    }
+}
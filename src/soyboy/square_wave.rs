@@ -52,6 +52,8 @@ pub struct SquareWaveOscillator {
     pub freq: f64,
 
     duty: SquareWaveDuty,
+    duty_override: Option<SquareWaveDuty>,
+    duty_modulation: f64,
     pitch: f64,
 }
 
@@ -62,6 +64,8 @@ impl SquareWaveOscillator {
             freq: 0.0,
 
             duty: SquareWaveDuty::Ratio50,
+            duty_override: None,
+            duty_modulation: 0.0,
             pitch: 0.0,
         }
     }
@@ -69,6 +73,16 @@ impl SquareWaveOscillator {
     pub fn set_duty(&mut self, duty: SquareWaveDuty) {
         self.duty = duty;
     }
+
+    /// Plays with `duty` instead of the parameter's one while it is `Some`.
+    pub fn set_duty_override(&mut self, duty: Option<u32>) {
+        self.duty_override = duty.and_then(|d| SquareWaveDuty::try_from(d).ok());
+    }
+
+    /// Sets the offset added to the duty ratio for PWM.
+    pub fn set_duty_modulation(&mut self, modulation: f64) {
+        self.duty_modulation = modulation;
+    }
 }
 
 impl Triggered for SquareWaveOscillator {
@@ -84,7 +98,9 @@ impl AudioProcessor<i4> for SquareWaveOscillator {
         let signal = if self.freq == 0.0 {
             i4::from(0i8)
         } else {
-            pulse(self.phase, self.duty.to_ratio())
+            let duty = self.duty_override.unwrap_or(self.duty);
+            let duty = (duty.to_ratio() + self.duty_modulation).clamp(0.0, 1.0);
+            pulse(self.phase, duty)
         };
 
         let phase_diff = (self.freq * self.pitch) / sample_rate;
@@ -5,6 +5,7 @@ use crate::{
     soyboy::{
         event::{Event, Triggered},
         parameters::{ParameterDef, Parametric, SoyBoyParameter},
+        tempo::{NoteDivision, TimeMode, DEFAULT_TEMPO},
         types::AudioProcessor,
     },
 };
@@ -43,6 +44,9 @@ pub struct SweepOscillator {
     sweep_type: SweepType,
     sweep_amount: f64,
     sweep_period: f64,
+    sweep_sync: TimeMode,
+    sweep_division: NoteDivision,
+    tempo: f64,
 }
 
 impl SweepOscillator {
@@ -55,6 +59,19 @@ impl SweepOscillator {
             sweep_type: SweepType::None,
             sweep_amount: 0.0,
             sweep_period: 0.0,
+            sweep_sync: TimeMode::Free,
+            sweep_division: NoteDivision::SixtyFourth,
+            tempo: DEFAULT_TEMPO,
+        }
+    }
+
+    /// Returns one sweep step in seconds.
+    /// In free mode it is `sweep_period` in 1/128 s units, and in sync mode it is
+    /// the selected note length at the current tempo.
+    fn interval(&self) -> f64 {
+        match self.sweep_sync {
+            TimeMode::Free => self.sweep_period / SWEEP_TIMER_FREQUENCY,
+            TimeMode::Sync => self.sweep_division.seconds(self.tempo),
         }
     }
 
@@ -73,21 +90,19 @@ const SWEEP_TIMER_FREQUENCY: f64 = 128.0;
 
 impl AudioProcessor<f64> for SweepOscillator {
     fn process(&mut self, sample_rate: f64) -> f64 {
-        if self.sweep_amount == 0.0 || self.sweep_period == 0.0 {
+        let interval = self.interval();
+        if self.sweep_amount == 0.0 || interval == 0.0 {
             return 0.0;
         }
 
         self.sweep_timer_sec += 1.0 / sample_rate;
 
-        let sweep_timer_interval = 1.0 / SWEEP_TIMER_FREQUENCY;
         let fmod = self.shadow_freq * 2.0f64.powf(self.sweep_amount - 8.1);
         let fmod = f64_utils::normalize(fmod);
 
         match self.sweep_type {
             SweepType::None => 0.0,
             SweepType::Up => {
-                let interval = sweep_timer_interval * self.sweep_period;
-
                 if self.sweep_timer_sec > interval {
                     self.sweep_timer_sec = 0.0;
                     self.shadow_freq += fmod;
@@ -99,8 +114,6 @@ impl AudioProcessor<f64> for SweepOscillator {
                 }
             }
             SweepType::Down => {
-                let interval = sweep_timer_interval * self.sweep_period;
-
                 if self.sweep_timer_sec > interval {
                     self.sweep_timer_sec = 0.0;
                     self.shadow_freq -= fmod;
@@ -112,8 +125,9 @@ impl AudioProcessor<f64> for SweepOscillator {
                 }
             }
             SweepType::Triangle => {
-                let quater_period = self.sweep_period * 1.0 / SWEEP_TIMER_FREQUENCY;
-                let fmod = 2.0f64.powf(self.sweep_amount - 8.1) / self.sweep_period;
+                let quater_period = interval;
+                let fmod =
+                    2.0f64.powf(self.sweep_amount - 8.1) / (interval * SWEEP_TIMER_FREQUENCY);
                 let fmod = f64_utils::normalize(fmod);
 
                 self.check_frequency_clip();
@@ -143,6 +157,7 @@ impl Triggered for SweepOscillator {
                 self.sweep_timer_sec = 0.0;
                 self.clipped = false;
             }
+            Event::Tempo { bpm } => self.tempo = *bpm,
             _ => (),
         }
     }
@@ -164,6 +179,16 @@ impl Parametric<SoyBoyParameter> for SweepOscillator {
             SoyBoyParameter::SweepPeriod => {
                 self.sweep_period = value;
             }
+            SoyBoyParameter::SweepSync => {
+                if let Ok(mode) = TimeMode::try_from(value as u32) {
+                    self.sweep_sync = mode;
+                }
+            }
+            SoyBoyParameter::SweepDivision => {
+                if let Ok(division) = NoteDivision::try_from(value as u32) {
+                    self.sweep_division = division;
+                }
+            }
             _ => (),
         }
     }
@@ -173,6 +198,8 @@ impl Parametric<SoyBoyParameter> for SweepOscillator {
             SoyBoyParameter::SweepType => (self.sweep_type as u32).into(),
             SoyBoyParameter::SweepAmount => self.sweep_amount,
             SoyBoyParameter::SweepPeriod => self.sweep_period,
+            SoyBoyParameter::SweepSync => (self.sweep_sync as u32).into(),
+            SoyBoyParameter::SweepDivision => (self.sweep_division as u32).into(),
             _ => 0.0,
         }
     }
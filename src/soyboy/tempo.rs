@@ -0,0 +1,146 @@
+use std::convert::TryFrom;
+
+/// A tempo used until the host tells us its own one.
+pub const DEFAULT_TEMPO: f64 = 120.0;
+
+#[derive(Debug, Copy, Clone)]
+pub enum TimeMode {
+    Free = 0,
+    Sync,
+}
+
+impl TryFrom<u32> for TimeMode {
+    type Error = ();
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        if id == TimeMode::Free as u32 {
+            Ok(TimeMode::Free)
+        } else if id == TimeMode::Sync as u32 {
+            Ok(TimeMode::Sync)
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// Note lengths for tempo-synced times. `D` means dotted and `T` means triplet.
+#[derive(Debug, Copy, Clone)]
+pub enum NoteDivision {
+    Whole = 0,
+    HalfDotted,
+    Half,
+    HalfTriplet,
+    QuarterDotted,
+    Quarter,
+    QuarterTriplet,
+    EighthDotted,
+    Eighth,
+    EighthTriplet,
+    SixteenthDotted,
+    Sixteenth,
+    SixteenthTriplet,
+    ThirtySecondDotted,
+    ThirtySecond,
+    ThirtySecondTriplet,
+    SixtyFourthDotted,
+    SixtyFourth,
+    SixtyFourthTriplet,
+}
+
+impl TryFrom<u32> for NoteDivision {
+    type Error = ();
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        if id == NoteDivision::Whole as u32 {
+            Ok(NoteDivision::Whole)
+        } else if id == NoteDivision::HalfDotted as u32 {
+            Ok(NoteDivision::HalfDotted)
+        } else if id == NoteDivision::Half as u32 {
+            Ok(NoteDivision::Half)
+        } else if id == NoteDivision::HalfTriplet as u32 {
+            Ok(NoteDivision::HalfTriplet)
+        } else if id == NoteDivision::QuarterDotted as u32 {
+            Ok(NoteDivision::QuarterDotted)
+        } else if id == NoteDivision::Quarter as u32 {
+            Ok(NoteDivision::Quarter)
+        } else if id == NoteDivision::QuarterTriplet as u32 {
+            Ok(NoteDivision::QuarterTriplet)
+        } else if id == NoteDivision::EighthDotted as u32 {
+            Ok(NoteDivision::EighthDotted)
+        } else if id == NoteDivision::Eighth as u32 {
+            Ok(NoteDivision::Eighth)
+        } else if id == NoteDivision::EighthTriplet as u32 {
+            Ok(NoteDivision::EighthTriplet)
+        } else if id == NoteDivision::SixteenthDotted as u32 {
+            Ok(NoteDivision::SixteenthDotted)
+        } else if id == NoteDivision::Sixteenth as u32 {
+            Ok(NoteDivision::Sixteenth)
+        } else if id == NoteDivision::SixteenthTriplet as u32 {
+            Ok(NoteDivision::SixteenthTriplet)
+        } else if id == NoteDivision::ThirtySecondDotted as u32 {
+            Ok(NoteDivision::ThirtySecondDotted)
+        } else if id == NoteDivision::ThirtySecond as u32 {
+            Ok(NoteDivision::ThirtySecond)
+        } else if id == NoteDivision::ThirtySecondTriplet as u32 {
+            Ok(NoteDivision::ThirtySecondTriplet)
+        } else if id == NoteDivision::SixtyFourthDotted as u32 {
+            Ok(NoteDivision::SixtyFourthDotted)
+        } else if id == NoteDivision::SixtyFourth as u32 {
+            Ok(NoteDivision::SixtyFourth)
+        } else if id == NoteDivision::SixtyFourthTriplet as u32 {
+            Ok(NoteDivision::SixtyFourthTriplet)
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl NoteDivision {
+    /// Length of this note in quarter notes (beats).
+    pub fn beats(self) -> f64 {
+        match self {
+            NoteDivision::Whole => 4.0,
+            NoteDivision::HalfDotted => 3.0,
+            NoteDivision::Half => 2.0,
+            NoteDivision::HalfTriplet => 4.0 / 3.0,
+            NoteDivision::QuarterDotted => 1.5,
+            NoteDivision::Quarter => 1.0,
+            NoteDivision::QuarterTriplet => 2.0 / 3.0,
+            NoteDivision::EighthDotted => 0.75,
+            NoteDivision::Eighth => 0.5,
+            NoteDivision::EighthTriplet => 1.0 / 3.0,
+            NoteDivision::SixteenthDotted => 0.375,
+            NoteDivision::Sixteenth => 0.25,
+            NoteDivision::SixteenthTriplet => 1.0 / 6.0,
+            NoteDivision::ThirtySecondDotted => 0.1875,
+            NoteDivision::ThirtySecond => 0.125,
+            NoteDivision::ThirtySecondTriplet => 1.0 / 12.0,
+            NoteDivision::SixtyFourthDotted => 0.09375,
+            NoteDivision::SixtyFourth => 0.0625,
+            NoteDivision::SixtyFourthTriplet => 1.0 / 24.0,
+        }
+    }
+
+    pub fn seconds(self, bpm: f64) -> f64 {
+        let bpm = if bpm > 0.0 { bpm } else { DEFAULT_TEMPO };
+        self.beats() * 60.0 / bpm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NoteDivision;
+
+    #[test]
+    fn test_note_division_seconds() {
+        assert_eq!(0.5, NoteDivision::Quarter.seconds(120.0));
+        assert_eq!(0.125, NoteDivision::Sixteenth.seconds(120.0));
+        assert_eq!(0.1875, NoteDivision::SixteenthDotted.seconds(120.0));
+        assert_eq!(1.0, NoteDivision::QuarterTriplet.seconds(40.0));
+    }
+
+    #[test]
+    fn test_note_division_seconds_without_tempo() {
+        assert_eq!(0.5, NoteDivision::Quarter.seconds(0.0));
+    }
+}
@@ -5,6 +5,18 @@ pub fn linear(x: f64, slope: f64) -> f64 {
     f64_utils::normalize(v)
 }
 
+/// Bends a 0..1 ramp `x` to an exponential curve; it rises slowly first.
+/// The larger `tension` is, the steeper the end of the curve is.
+pub fn exponential(x: f64, tension: f64) -> f64 {
+    let v = (tension * x).exp_m1() / tension.exp_m1();
+    f64_utils::normalize(v)
+}
+
+/// Bends a 0..1 ramp `x` to a logarithmic curve; it rises quickly first.
+pub fn logarithmic(x: f64, tension: f64) -> f64 {
+    1.0 - exponential(1.0 - x, tension)
+}
+
 /// This maps from continuous value `x` to discrete value.
 /// This is for getting rough 4bit envelope signals.
 pub fn discrete_loudness(x: f64) -> f64 {
@@ -5,14 +5,22 @@ use crate::{
     soyboy::{
         dac::DAConverter,
         envelope_generator::EnvelopeGenerator,
-        event::{Event, Triggered},
+        event::{Event, NoteExpression, Triggered, NO_NOTE_ID},
+        filter_envelope::FilterEnvelope,
+        modulation::ModulationMatrix,
+        mpe::Expression,
         noise::NoiseOscillator,
         parameters::{ParameterDef, Parametric, SoyBoyParameter},
+        patch::is_patch_parameter,
+        smoother::Smoother,
         square_wave::SquareWaveOscillator,
         sweep::SweepOscillator,
         types::AudioProcessor,
         utils::{frequency_from_note_number, ratio_from_cents},
+        velocity::VelocitySensitivity,
         wave_table::WaveTableOscillator,
+        zones::ZoneSettings,
+        Signal,
     },
 };
 
@@ -49,10 +57,40 @@ pub struct VoiceUnit {
     sweep_osc: SweepOscillator,
     dac: DAConverter,
     envelope_gen: EnvelopeGenerator,
+    filter_env: FilterEnvelope,
+    velocity_sense: VelocitySensitivity,
+    modulation: ModulationMatrix,
 
-    pitch: i16,
-    detune: i16,
+    // the pitch bend wheel's position in -1.0..1.0
+    bend: Smoother,
+    bend_up: u32,
+    bend_down: u32,
+    bend_quantize: bool,
+    // the note's own MPE pitch bend in cents
+    note_bend: i16,
+    // the note expression's tuning in cents
+    tuning: i16,
+    // the note expression's gain and pan
+    expression_volume: f64,
+    expression_pan: f64,
+    // in cents
+    detune: Smoother,
     selected_osc: OscillatorType,
+    osc_override: Option<OscillatorType>,
+    transpose: i16,
+    fixed_note: Option<u16>,
+    choke_group: u32,
+    part: usize,
+    // the MIDI channel of the note
+    channel: i16,
+    // the host's ID of the note
+    note_id: i32,
+    // the key is released but a pedal keeps the note
+    sustained: bool,
+    // the key was held when the sostenuto pedal was pressed
+    sostenuto: bool,
+    // the revision of the patch applied last, which is cleared by editing the voice
+    patch_revision: Option<u64>,
 }
 
 impl VoiceUnit {
@@ -67,19 +105,44 @@ impl VoiceUnit {
             sweep_osc: SweepOscillator::new(),
             dac: DAConverter::new(22_000.0, 0.005),
             envelope_gen: EnvelopeGenerator::new(),
+            filter_env: FilterEnvelope::new(),
+            velocity_sense: VelocitySensitivity::new(),
+            modulation: ModulationMatrix::new(),
 
-            pitch: 0,
-            detune: 0,
+            bend: Smoother::new(0.0),
+            bend_up: 2,
+            bend_down: 2,
+            bend_quantize: false,
+            note_bend: 0,
+            tuning: 0,
+            expression_volume: 1.0,
+            expression_pan: 0.0,
+            detune: Smoother::new(0.0),
             selected_osc: OscillatorType::Square,
+            osc_override: None,
+            transpose: 0,
+            fixed_note: None,
+            choke_group: 0,
+            part: 0,
+            channel: 0,
+            note_id: NO_NOTE_ID,
+            sustained: false,
+            sostenuto: false,
+            patch_revision: None,
         }
     }
 
-    pub fn get_wavetable(&self) -> [i4; constants::WAVETABLE_SIZE] {
-        self.wavetable_osc.get_wavetable()
-    }
-
     pub fn set_wavetable(&mut self, wavetable: &[i4; constants::WAVETABLE_SIZE]) {
         self.wavetable_osc.set_wavetable(wavetable);
+        self.patch_revision = None;
+    }
+
+    pub fn patch_revision(&self) -> Option<u64> {
+        self.patch_revision
+    }
+
+    pub fn set_patch_revision(&mut self, revision: u64) {
+        self.patch_revision = Some(revision);
     }
 
     pub fn same_note(&self, note: u16) -> bool {
@@ -89,28 +152,220 @@ impl VoiceUnit {
     pub fn assignable(&self, note: u16) -> bool {
         self.envelope_gen.assignable(note)
     }
+
+    pub fn held(&self) -> bool {
+        self.envelope_gen.held()
+    }
+
+    pub fn active(&self) -> bool {
+        self.envelope_gen.active()
+    }
+
+    pub fn sustained(&self) -> bool {
+        self.sustained
+    }
+
+    pub fn sostenuto(&self) -> bool {
+        self.sostenuto
+    }
+
+    pub fn set_sostenuto(&mut self, sostenuto: bool) {
+        self.sostenuto = sostenuto;
+    }
+
+    /// Releases the key. The note keeps sounding while the sustain pedal is down
+    /// or the sostenuto pedal holds it.
+    pub fn note_off(&mut self, event: &Event, sustain: bool) {
+        if sustain || self.sostenuto {
+            self.sustained = true;
+        } else {
+            self.trigger(event);
+        }
+    }
+
+    /// Releases the note kept by the pedals.
+    pub fn release_sustained(&mut self) {
+        self.sustained = false;
+        self.envelope_gen.trigger(&Event::NoteOff {
+            note: self.envelope_gen.note(),
+            channel: 0,
+            note_id: NO_NOTE_ID,
+        });
+    }
+
+    /// Sets the split zone's settings for the next note. `None` plays the patch as it is.
+    pub fn set_zone(&mut self, zone: Option<ZoneSettings>) {
+        let zone = zone.as_ref();
+        self.osc_override = zone
+            .and_then(|z| z.oscillator)
+            .and_then(|osc| OscillatorType::try_from(osc).ok());
+        self.transpose = zone.map_or(0, |z| z.transpose);
+        self.square_osc.set_duty_override(zone.and_then(|z| z.duty));
+        self.wavetable_osc
+            .set_table_override(zone.and_then(|z| z.wavetable));
+    }
+
+    /// Sets the multitimbral part which the voice plays.
+    pub fn set_part(&mut self, part: usize) {
+        self.part = part;
+    }
+
+    pub fn part(&self) -> usize {
+        self.part
+    }
+
+    /// Sets the drum kit pad's settings for the next note.
+    pub fn set_kit_pad(&mut self, fixed_note: Option<u16>, choke_group: u32) {
+        self.fixed_note = fixed_note;
+        self.choke_group = choke_group;
+    }
+
+    pub fn choke_group(&self) -> u32 {
+        self.choke_group
+    }
+
+    /// Returns the pitch bend in cents, scaled with the range of the bend's direction.
+    fn bend_cents(&self) -> i16 {
+        let bend = self.bend.value();
+        let range = if bend >= 0.0 {
+            self.bend_up
+        } else {
+            self.bend_down
+        };
+        let semitones = bend * range as f64;
+        let semitones = if self.bend_quantize {
+            semitones.round()
+        } else {
+            semitones
+        };
+        (semitones * 100.0).round() as i16
+    }
+
+    fn update_pitch(&mut self) {
+        let detune = self.detune.value().round() as i16;
+        let ratio = ratio_from_cents(self.bend_cents() + self.note_bend + self.tuning + detune);
+        self.trigger(&Event::PitchBend { ratio });
+    }
+
+    pub fn channel(&self) -> i16 {
+        self.channel
+    }
+
+    pub fn note_id(&self) -> i32 {
+        self.note_id
+    }
+
+    /// Sets the note expression's `value`, which is normalized in 0.0..1.0.
+    fn set_note_expression(&mut self, expression: NoteExpression, value: f64) {
+        let value = value.clamp(0.0, 1.0);
+        match expression {
+            NoteExpression::Volume => self.expression_volume = value * 4.0,
+            NoteExpression::Pan => self.expression_pan = value * 2.0 - 1.0,
+            NoteExpression::Tuning => {
+                self.tuning = ((value - 0.5) * 240.0 * 100.0).round() as i16;
+                self.update_pitch();
+            }
+            NoteExpression::Brightness => self.modulation.set_brightness(value * 2.0 - 1.0),
+        }
+    }
+
+    /// Sets the note's own MPE expression. `bend_range` is in semitones.
+    pub fn set_expression(&mut self, expression: &Expression, bend_range: u32) {
+        self.note_bend = (expression.bend * bend_range as f64 * 100.0).round() as i16;
+        self.update_pitch();
+        self.modulation
+            .set_expression(expression.pressure, expression.timbre);
+    }
+
+    /// Makes the smoothed parameters reach their targets at once, so that a new note
+    /// does not start with the previous note's values.
+    fn finish_smoothing(&mut self) {
+        if self.bend.is_smoothing() || self.detune.is_smoothing() {
+            self.bend.finish();
+            self.detune.finish();
+            self.update_pitch();
+        }
+        self.dac.finish_smoothing();
+    }
+
+    pub fn choke(&mut self) {
+        self.envelope_gen.cut();
+    }
 }
 
 impl Triggered for VoiceUnit {
     fn trigger(&mut self, event: &Event) {
         match event {
-            Event::NoteOn { note, velocity: _ } => {
-                self.note_on_freq = frequency_from_note_number(*note);
+            Event::NoteOn {
+                note,
+                velocity,
+                channel,
+                note_id,
+            } => {
+                self.sustained = false;
+                self.sostenuto = false;
+                self.channel = *channel;
+                self.note_id = *note_id;
+                self.finish_smoothing();
+                self.expression_volume = 1.0;
+                self.expression_pan = 0.0;
+                if self.tuning != 0 {
+                    self.tuning = 0;
+                    self.update_pitch();
+                }
+
+                let pitch_note = self.fixed_note.unwrap_or(*note) as i16 + self.transpose;
+                let pitch_note = pitch_note.clamp(0, 127) as u16;
+                self.note_on_freq = frequency_from_note_number(pitch_note);
                 self.freq = self.note_on_freq;
                 self.sweep_osc
                     .trigger(&Event::SweepReset { freq: self.freq });
-                self.envelope_gen.trigger(event);
+
+                let shaped = self.velocity_sense.shape(*velocity);
+                self.envelope_gen.set_velocity_modulation(
+                    self.velocity_sense.attack_scale(shaped),
+                    self.velocity_sense.stutter_scale(shaped),
+                );
+                self.envelope_gen.trigger(&Event::NoteOn {
+                    note: *note,
+                    velocity: self.velocity_sense.level(shaped),
+                    channel: *channel,
+                    note_id: *note_id,
+                });
+                self.filter_env.trigger(&Event::NoteOn {
+                    note: pitch_note,
+                    velocity: shaped,
+                    channel: *channel,
+                    note_id: *note_id,
+                });
+                self.modulation.trigger(&Event::NoteOn {
+                    note: pitch_note,
+                    velocity: shaped,
+                    channel: *channel,
+                    note_id: *note_id,
+                });
             }
-            Event::NoteOff { note: _ } => {
+            Event::NoteOff { .. } => {
                 self.envelope_gen.trigger(event);
             }
+            Event::NoteExpression {
+                expression, value, ..
+            } => self.set_note_expression(*expression, *value),
+            Event::PolyPressure { pressure, .. } => self.modulation.set_poly_pressure(*pressure),
             Event::PitchBend { ratio: _ } => {
                 self.square_osc.trigger(event);
                 self.wavetable_osc.trigger(event);
             }
-            Event::SetWaveTable { .. } => self.wavetable_osc.trigger(event),
-            Event::ResetWaveTableAsSine => self.wavetable_osc.trigger(event),
-            Event::ResetWaveTableAtRandom => self.wavetable_osc.trigger(event),
+            Event::SetWaveTable { .. }
+            | Event::ResetWaveTableAsSine
+            | Event::ResetWaveTableAtRandom => {
+                self.wavetable_osc.trigger(event);
+                self.patch_revision = None;
+            }
+            Event::Tempo { .. } => {
+                self.sweep_osc.trigger(event);
+                self.envelope_gen.trigger(event);
+            }
             _ => (),
         }
     }
@@ -118,16 +373,35 @@ impl Triggered for VoiceUnit {
 
 impl Parametric<SoyBoyParameter> for VoiceUnit {
     fn set_param(&mut self, param: &SoyBoyParameter, param_def: &ParameterDef, value: f64) {
+        if is_patch_parameter(param) {
+            self.patch_revision = None;
+        }
+
         match param {
             SoyBoyParameter::PitchBend => {
-                self.pitch = value as i16;
-                let ratio = ratio_from_cents(self.pitch + self.detune);
-                self.trigger(&Event::PitchBend { ratio });
+                self.bend.set_target(value);
+                self.update_pitch();
+            }
+            SoyBoyParameter::BendUp => {
+                self.bend_up = value as u32;
+                self.update_pitch();
+            }
+            SoyBoyParameter::BendDown => {
+                self.bend_down = value as u32;
+                self.update_pitch();
+            }
+            SoyBoyParameter::BendQuantize => {
+                self.bend_quantize = value as u32 != 0;
+                self.update_pitch();
             }
             SoyBoyParameter::Detune => {
-                self.detune = value as i16;
-                let ratio = ratio_from_cents(self.pitch + self.detune);
-                self.trigger(&Event::PitchBend { ratio });
+                self.detune.set_target(value);
+                self.update_pitch();
+            }
+            SoyBoyParameter::SmoothingTime => {
+                self.bend.set_ramp_time(value / 1000.0);
+                self.detune.set_ramp_time(value / 1000.0);
+                self.dac.set_param(param, param_def, value);
             }
             SoyBoyParameter::OscillatorType => {
                 if let Ok(r#type) = OscillatorType::try_from(value as u32) {
@@ -140,9 +414,122 @@ impl Parametric<SoyBoyParameter> for VoiceUnit {
             }
             SoyBoyParameter::SweepAmount => self.sweep_osc.set_param(param, param_def, value),
             SoyBoyParameter::SweepPeriod => self.sweep_osc.set_param(param, param_def, value),
+            SoyBoyParameter::SweepSync => self.sweep_osc.set_param(param, param_def, value),
+            SoyBoyParameter::SweepDivision => self.sweep_osc.set_param(param, param_def, value),
             SoyBoyParameter::StutterTime => self.envelope_gen.set_param(param, param_def, value),
             SoyBoyParameter::StutterDepth => self.envelope_gen.set_param(param, param_def, value),
             SoyBoyParameter::StutterWhen => self.envelope_gen.set_param(param, param_def, value),
+            SoyBoyParameter::StutterSync => self.envelope_gen.set_param(param, param_def, value),
+            SoyBoyParameter::StutterDivision => {
+                self.envelope_gen.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::StutterCount => self.envelope_gen.set_param(param, param_def, value),
+            SoyBoyParameter::StutterPitch => self.envelope_gen.set_param(param, param_def, value),
+            SoyBoyParameter::StutterPingPong => {
+                self.envelope_gen.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::StutterProbability => {
+                self.envelope_gen.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::EgAttackCurve => self.envelope_gen.set_param(param, param_def, value),
+            SoyBoyParameter::EgAttackTension => {
+                self.envelope_gen.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::EgDecayCurve => self.envelope_gen.set_param(param, param_def, value),
+            SoyBoyParameter::EgDecayTension => self.envelope_gen.set_param(param, param_def, value),
+            SoyBoyParameter::EgReleaseCurve => self.envelope_gen.set_param(param, param_def, value),
+            SoyBoyParameter::EgReleaseTension => {
+                self.envelope_gen.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::EgHold => self.envelope_gen.set_param(param, param_def, value),
+            SoyBoyParameter::EgLoop => self.envelope_gen.set_param(param, param_def, value),
+            SoyBoyParameter::VelocityAmount => {
+                self.velocity_sense.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::VelocityCurve => {
+                self.velocity_sense.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::VelocityToAttack => {
+                self.velocity_sense.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::VelocityToStutter => {
+                self.velocity_sense.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::Lfo1Rate => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::Lfo1Shape => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::Lfo2Rate => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::Lfo2Shape => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModEnvAttack => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModEnvDecay => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModWheel => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::Aftertouch => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::Breath => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModWheelDestination => {
+                self.modulation.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::ModWheelAmount => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::AftertouchDestination => {
+                self.modulation.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::AftertouchAmount => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::BreathDestination => {
+                self.modulation.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::BreathAmount => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::TimbreDestination => {
+                self.modulation.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::TimbreAmount => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::PolyPressureDestination => {
+                self.modulation.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::PolyPressureAmount => {
+                self.modulation.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::ModSlot1Source => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModSlot1Destination => {
+                self.modulation.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::ModSlot1Amount => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModSlot2Source => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModSlot2Destination => {
+                self.modulation.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::ModSlot2Amount => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModSlot3Source => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModSlot3Destination => {
+                self.modulation.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::ModSlot3Amount => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModSlot4Source => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModSlot4Destination => {
+                self.modulation.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::ModSlot4Amount => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModSlot5Source => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModSlot5Destination => {
+                self.modulation.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::ModSlot5Amount => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModSlot6Source => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModSlot6Destination => {
+                self.modulation.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::ModSlot6Amount => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModSlot7Source => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModSlot7Destination => {
+                self.modulation.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::ModSlot7Amount => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModSlot8Source => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::ModSlot8Destination => {
+                self.modulation.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::ModSlot8Amount => self.modulation.set_param(param, param_def, value),
+            SoyBoyParameter::EgMode => self.envelope_gen.set_param(param, param_def, value),
+            SoyBoyParameter::EgHwVolume => self.envelope_gen.set_param(param, param_def, value),
+            SoyBoyParameter::EgHwDirection => self.envelope_gen.set_param(param, param_def, value),
+            SoyBoyParameter::EgHwPeriod => self.envelope_gen.set_param(param, param_def, value),
             SoyBoyParameter::EgAttack => self.envelope_gen.set_param(param, param_def, value),
             SoyBoyParameter::EgDecay => self.envelope_gen.set_param(param, param_def, value),
             SoyBoyParameter::EgSustain => self.envelope_gen.set_param(param, param_def, value),
@@ -151,14 +538,25 @@ impl Parametric<SoyBoyParameter> for VoiceUnit {
             SoyBoyParameter::OscNsInterval => self.noise_osc.set_param(param, param_def, value),
             SoyBoyParameter::DacFreq => self.dac.set_param(param, param_def, value),
             SoyBoyParameter::DacQ => self.dac.set_param(param, param_def, value),
+            SoyBoyParameter::FilterType => self.dac.set_param(param, param_def, value),
+            SoyBoyParameter::FilterEnvAmount => self.filter_env.set_param(param, param_def, value),
+            SoyBoyParameter::FilterEnvAttack => self.filter_env.set_param(param, param_def, value),
+            SoyBoyParameter::FilterEnvDecay => self.filter_env.set_param(param, param_def, value),
+            SoyBoyParameter::FilterKeyTracking => {
+                self.filter_env.set_param(param, param_def, value)
+            }
+            SoyBoyParameter::FilterVelocity => self.filter_env.set_param(param, param_def, value),
             _ => (),
         }
     }
 
     fn get_param(&self, param: &SoyBoyParameter) -> f64 {
         match param {
-            SoyBoyParameter::PitchBend => self.pitch as f64,
-            SoyBoyParameter::Detune => self.detune as f64,
+            SoyBoyParameter::PitchBend => self.bend.target(),
+            SoyBoyParameter::BendUp => self.bend_up as f64,
+            SoyBoyParameter::BendDown => self.bend_down as f64,
+            SoyBoyParameter::BendQuantize => (self.bend_quantize as u32).into(),
+            SoyBoyParameter::Detune => self.detune.target(),
             SoyBoyParameter::OscillatorType => {
                 let v = self.selected_osc as u32;
                 v.into()
@@ -166,9 +564,76 @@ impl Parametric<SoyBoyParameter> for VoiceUnit {
             SoyBoyParameter::SweepType => self.sweep_osc.get_param(param),
             SoyBoyParameter::SweepAmount => self.sweep_osc.get_param(param),
             SoyBoyParameter::SweepPeriod => self.sweep_osc.get_param(param),
+            SoyBoyParameter::SweepSync => self.sweep_osc.get_param(param),
+            SoyBoyParameter::SweepDivision => self.sweep_osc.get_param(param),
             SoyBoyParameter::StutterTime => self.envelope_gen.get_param(param),
             SoyBoyParameter::StutterDepth => self.envelope_gen.get_param(param),
             SoyBoyParameter::StutterWhen => self.envelope_gen.get_param(param),
+            SoyBoyParameter::StutterSync => self.envelope_gen.get_param(param),
+            SoyBoyParameter::StutterDivision => self.envelope_gen.get_param(param),
+            SoyBoyParameter::StutterCount => self.envelope_gen.get_param(param),
+            SoyBoyParameter::StutterPitch => self.envelope_gen.get_param(param),
+            SoyBoyParameter::StutterPingPong => self.envelope_gen.get_param(param),
+            SoyBoyParameter::StutterProbability => self.envelope_gen.get_param(param),
+            SoyBoyParameter::EgAttackCurve => self.envelope_gen.get_param(param),
+            SoyBoyParameter::EgAttackTension => self.envelope_gen.get_param(param),
+            SoyBoyParameter::EgDecayCurve => self.envelope_gen.get_param(param),
+            SoyBoyParameter::EgDecayTension => self.envelope_gen.get_param(param),
+            SoyBoyParameter::EgReleaseCurve => self.envelope_gen.get_param(param),
+            SoyBoyParameter::EgReleaseTension => self.envelope_gen.get_param(param),
+            SoyBoyParameter::EgHold => self.envelope_gen.get_param(param),
+            SoyBoyParameter::EgLoop => self.envelope_gen.get_param(param),
+            SoyBoyParameter::VelocityAmount => self.velocity_sense.get_param(param),
+            SoyBoyParameter::VelocityCurve => self.velocity_sense.get_param(param),
+            SoyBoyParameter::VelocityToAttack => self.velocity_sense.get_param(param),
+            SoyBoyParameter::VelocityToStutter => self.velocity_sense.get_param(param),
+            SoyBoyParameter::Lfo1Rate => self.modulation.get_param(param),
+            SoyBoyParameter::Lfo1Shape => self.modulation.get_param(param),
+            SoyBoyParameter::Lfo2Rate => self.modulation.get_param(param),
+            SoyBoyParameter::Lfo2Shape => self.modulation.get_param(param),
+            SoyBoyParameter::ModEnvAttack => self.modulation.get_param(param),
+            SoyBoyParameter::ModEnvDecay => self.modulation.get_param(param),
+            SoyBoyParameter::ModWheel => self.modulation.get_param(param),
+            SoyBoyParameter::Aftertouch => self.modulation.get_param(param),
+            SoyBoyParameter::Breath => self.modulation.get_param(param),
+            SoyBoyParameter::ModWheelDestination => self.modulation.get_param(param),
+            SoyBoyParameter::ModWheelAmount => self.modulation.get_param(param),
+            SoyBoyParameter::AftertouchDestination => self.modulation.get_param(param),
+            SoyBoyParameter::AftertouchAmount => self.modulation.get_param(param),
+            SoyBoyParameter::BreathDestination => self.modulation.get_param(param),
+            SoyBoyParameter::BreathAmount => self.modulation.get_param(param),
+            SoyBoyParameter::TimbreDestination => self.modulation.get_param(param),
+            SoyBoyParameter::TimbreAmount => self.modulation.get_param(param),
+            SoyBoyParameter::PolyPressureDestination => self.modulation.get_param(param),
+            SoyBoyParameter::PolyPressureAmount => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot1Source => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot1Destination => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot1Amount => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot2Source => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot2Destination => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot2Amount => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot3Source => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot3Destination => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot3Amount => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot4Source => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot4Destination => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot4Amount => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot5Source => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot5Destination => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot5Amount => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot6Source => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot6Destination => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot6Amount => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot7Source => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot7Destination => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot7Amount => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot8Source => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot8Destination => self.modulation.get_param(param),
+            SoyBoyParameter::ModSlot8Amount => self.modulation.get_param(param),
+            SoyBoyParameter::EgMode => self.envelope_gen.get_param(param),
+            SoyBoyParameter::EgHwVolume => self.envelope_gen.get_param(param),
+            SoyBoyParameter::EgHwDirection => self.envelope_gen.get_param(param),
+            SoyBoyParameter::EgHwPeriod => self.envelope_gen.get_param(param),
             SoyBoyParameter::EgAttack => self.envelope_gen.get_param(param),
             SoyBoyParameter::EgDecay => self.envelope_gen.get_param(param),
             SoyBoyParameter::EgSustain => self.envelope_gen.get_param(param),
@@ -177,39 +642,70 @@ impl Parametric<SoyBoyParameter> for VoiceUnit {
             SoyBoyParameter::OscNsInterval => self.noise_osc.get_param(param),
             SoyBoyParameter::DacFreq => self.dac.get_param(param),
             SoyBoyParameter::DacQ => self.dac.get_param(param),
+            SoyBoyParameter::FilterType => self.dac.get_param(param),
+            SoyBoyParameter::FilterEnvAmount => self.filter_env.get_param(param),
+            SoyBoyParameter::FilterEnvAttack => self.filter_env.get_param(param),
+            SoyBoyParameter::FilterEnvDecay => self.filter_env.get_param(param),
+            SoyBoyParameter::FilterKeyTracking => self.filter_env.get_param(param),
+            SoyBoyParameter::FilterVelocity => self.filter_env.get_param(param),
             _ => 0.0,
         }
     }
 }
 
-impl AudioProcessor<f64> for VoiceUnit {
-    fn process(&mut self, sample_rate: f64) -> f64 {
+impl AudioProcessor<Signal> for VoiceUnit {
+    fn process(&mut self, sample_rate: f64) -> Signal {
+        let modulation = self.modulation.process(sample_rate);
+
+        if self.bend.is_smoothing() || self.detune.is_smoothing() {
+            self.bend.process(sample_rate);
+            self.detune.process(sample_rate);
+            self.update_pitch();
+        }
+
         let osc = if self.sweep_osc.is_clipped() {
             i4::ZERO.into()
         } else {
             let freq_mod = self.sweep_osc.process(sample_rate);
             self.freq += freq_mod;
+            let transpose = self.envelope_gen.stutter_transpose() + modulation.pitch;
+            let freq = self.freq * 2.0f64.powf(transpose / 12.0);
 
-            match self.selected_osc {
+            match self.osc_override.unwrap_or(self.selected_osc) {
                 OscillatorType::Square => {
-                    self.square_osc.set_freq(self.freq);
+                    self.square_osc.set_freq(freq);
+                    self.square_osc.set_duty_modulation(modulation.duty);
                     self.square_osc.process(sample_rate)
                 }
                 OscillatorType::Noise => {
-                    self.noise_osc.set_freq(self.freq);
+                    self.noise_osc.set_freq(freq);
+                    self.noise_osc.set_rate_modulation(modulation.noise_rate);
                     self.noise_osc.process(sample_rate)
                 }
                 OscillatorType::WaveTable => {
-                    self.wavetable_osc.set_freq(self.freq);
+                    self.wavetable_osc.set_freq(freq);
+                    self.wavetable_osc
+                        .set_index_modulation(modulation.wavetable_index);
                     self.wavetable_osc.process(sample_rate)
                 }
             }
         };
 
+        self.envelope_gen
+            .set_stutter_time_modulation(modulation.stutter_time);
         let env = self.envelope_gen.process(sample_rate);
 
-        let v = self.dac.process(sample_rate, osc * env);
-        v
+        let cutoff_mod = self.filter_env.process(sample_rate);
+        self.dac.set_modulation(cutoff_mod + modulation.cutoff);
+        let v = self.dac.process(sample_rate, osc * env)
+            * (1.0 + modulation.volume).max(0.0)
+            * self.expression_volume;
+
+        let pan = (self.envelope_gen.stutter_pan() + modulation.pan + self.expression_pan)
+            .clamp(-1.0, 1.0);
+        let l = (1.0 - pan).min(1.0);
+        let r = (1.0 + pan).min(1.0);
+        (v * l, v * r)
     }
 
     fn set_freq(&mut self, _freq: f64) {}
@@ -13,8 +13,10 @@ pub struct WaveTableOscillator {
     phase: f64,
     pitch: f64,
     pub freq: f64,
+    index_modulation: f64,
 
     table: [i4; constants::WAVETABLE_SIZE],
+    table_override: Option<[i4; constants::WAVETABLE_SIZE]>,
 }
 
 impl WaveTableOscillator {
@@ -23,8 +25,10 @@ impl WaveTableOscillator {
             phase: 0.0,
             freq: 0.0,
             pitch: 0.0,
+            index_modulation: 0.0,
 
             table: [i4::from(0.0); constants::WAVETABLE_SIZE],
+            table_override: None,
         };
 
         osc.initialize_table();
@@ -62,6 +66,16 @@ impl WaveTableOscillator {
     pub fn set_wavetable(&mut self, wavetable: &[i4; constants::WAVETABLE_SIZE]) {
         self.table = wavetable.clone();
     }
+
+    /// Plays `table` instead of the edited one while it is `Some`.
+    pub fn set_table_override(&mut self, table: Option<[i4; constants::WAVETABLE_SIZE]>) {
+        self.table_override = table;
+    }
+
+    /// Sets the offset of the read position in samples.
+    pub fn set_index_modulation(&mut self, samples: f64) {
+        self.index_modulation = samples;
+    }
 }
 
 impl Triggered for WaveTableOscillator {
@@ -97,9 +111,11 @@ impl Parametric<SoyBoyParameter> for WaveTableOscillator {
 
 impl AudioProcessor<i4> for WaveTableOscillator {
     fn process(&mut self, sample_rate: f64) -> i4 {
-        let v = self.table[self.phase as usize];
-
         let wt_size = constants::WAVETABLE_SIZE as f64;
+        let idx = (self.phase + self.index_modulation).rem_euclid(wt_size);
+        let table = self.table_override.as_ref().unwrap_or(&self.table);
+        let v = table[idx as usize % constants::WAVETABLE_SIZE];
+
         let phase_diff = ((self.freq * self.pitch) / sample_rate) * wt_size;
         self.phase = (self.phase + phase_diff) % wt_size;
 
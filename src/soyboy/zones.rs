@@ -0,0 +1,153 @@
+use crate::{
+    common::{constants, i4},
+    soyboy::parameters::{ParameterDef, Parametric, SoyBoyParameter},
+};
+
+const NUMBER_OF_ZONES: usize = 4;
+/// The number of parameters for each zone: key range, velocity range, oscillator,
+/// transpose, duty and wavetable slot.
+const ZONE_PARAMETERS: u32 = 8;
+
+/// Returns whether `param` is the split mode or a zone's parameter.
+pub fn is_zone_parameter(param: &SoyBoyParameter) -> bool {
+    let first = SoyBoyParameter::SplitMode as u32;
+    let last = SoyBoyParameter::Zone4Wavetable as u32;
+    (first..=last).contains(&(*param as u32))
+}
+
+/// What a zone changes on the patch for the notes in it.
+#[derive(Copy, Clone)]
+pub struct ZoneSettings {
+    pub oscillator: Option<u32>,
+    pub transpose: i16,
+    pub duty: Option<u32>,
+    pub wavetable: Option<[i4; constants::WAVETABLE_SIZE]>,
+}
+
+#[derive(Copy, Clone)]
+struct Zone {
+    key_low: u16,
+    key_high: u16,
+    velocity_low: u16,
+    velocity_high: u16,
+    // 0 keeps the patch's one, and the others are shifted by 1
+    oscillator: u32,
+    transpose: i16,
+    duty: u32,
+    // a drum kit pad whose wavetable the zone plays, so editing the pad changes the zone too
+    wavetable_slot: usize,
+}
+
+impl Zone {
+    fn new() -> Self {
+        Self {
+            key_low: 0,
+            key_high: 127,
+            velocity_low: 0,
+            velocity_high: 127,
+            oscillator: 0,
+            transpose: 0,
+            duty: 0,
+            wavetable_slot: 0,
+        }
+    }
+
+    fn contains(&self, note: u16, velocity: u16) -> bool {
+        (self.key_low..=self.key_high).contains(&note)
+            && (self.velocity_low..=self.velocity_high).contains(&velocity)
+    }
+}
+
+/// Splits the keyboard into zones by key and velocity ranges for live playing.
+/// A note plays with the first zone which contains it, and notes out of all zones are ignored.
+pub struct SplitZones {
+    enabled: bool,
+    zones: [Zone; NUMBER_OF_ZONES],
+}
+
+impl SplitZones {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            zones: [Zone::new(); NUMBER_OF_ZONES],
+        }
+    }
+
+    fn zone_param(param: &SoyBoyParameter) -> Option<(usize, u32)> {
+        let first = SoyBoyParameter::Zone1KeyLow as u32;
+        let last = SoyBoyParameter::Zone4Wavetable as u32;
+        let id = *param as u32;
+
+        if (first..=last).contains(&id) {
+            let offset = id - first;
+            Some((
+                (offset / ZONE_PARAMETERS) as usize,
+                offset % ZONE_PARAMETERS,
+            ))
+        } else {
+            None
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the zone's settings for the note. `velocity` is in 0..1.
+    /// `wavetables` gives the wavetable in a slot, which starts from 1.
+    pub fn zone_for<F>(&self, note: u16, velocity: f64, wavetables: F) -> Option<ZoneSettings>
+    where
+        F: Fn(usize) -> [i4; constants::WAVETABLE_SIZE],
+    {
+        let velocity = (velocity.clamp(0.0, 1.0) * 127.0).round() as u16;
+        self.zones
+            .iter()
+            .find(|zone| zone.contains(note, velocity))
+            .map(|zone| ZoneSettings {
+                oscillator: zone.oscillator.checked_sub(1),
+                transpose: zone.transpose,
+                duty: zone.duty.checked_sub(1),
+                wavetable: zone.wavetable_slot.checked_sub(1).map(wavetables),
+            })
+    }
+}
+
+impl Parametric<SoyBoyParameter> for SplitZones {
+    fn set_param(&mut self, param: &SoyBoyParameter, _param_def: &ParameterDef, value: f64) {
+        if let SoyBoyParameter::SplitMode = param {
+            self.enabled = value as u32 != 0;
+        } else if let Some((i, p)) = SplitZones::zone_param(param) {
+            let zone = &mut self.zones[i];
+            match p {
+                0 => zone.key_low = value as u16,
+                1 => zone.key_high = value as u16,
+                2 => zone.velocity_low = value as u16,
+                3 => zone.velocity_high = value as u16,
+                4 => zone.oscillator = value as u32,
+                5 => zone.transpose = value as i16,
+                6 => zone.duty = value as u32,
+                _ => zone.wavetable_slot = value as usize,
+            }
+        }
+    }
+
+    fn get_param(&self, param: &SoyBoyParameter) -> f64 {
+        if let SoyBoyParameter::SplitMode = param {
+            (self.enabled as u32).into()
+        } else if let Some((i, p)) = SplitZones::zone_param(param) {
+            let zone = &self.zones[i];
+            match p {
+                0 => zone.key_low as f64,
+                1 => zone.key_high as f64,
+                2 => zone.velocity_low as f64,
+                3 => zone.velocity_high as f64,
+                4 => zone.oscillator as f64,
+                5 => zone.transpose as f64,
+                6 => zone.duty as f64,
+                _ => zone.wavetable_slot as f64,
+            }
+        } else {
+            0.0
+        }
+    }
+}
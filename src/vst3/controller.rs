@@ -21,28 +21,103 @@ use vst3_sys::{
     utils::SharedVstPtr,
     vst::{
         kRootUnitId, CtrlNumber, IComponentHandler, IConnectionPoint, IEditController, IMessage,
-        IMidiMapping, IUnitInfo, ParamID, ParameterFlags, ParameterInfo, ProgramListInfo, TChar,
-        UnitInfo,
+        IMidiMapping, INoteExpressionController, IUnitInfo, NoteExpressionTypeInfo,
+        NoteExpressionValueDescription, ParamID, ParameterFlags, ParameterInfo, ProgramListInfo,
+        RestartFlags, String128, TChar, UnitInfo,
     },
     VstPtr, VST3,
 };
 
 use crate::common::{
-    config::{PluginConfigV01, PluginConfigV02},
+    config::{PluginConfigV01, PluginConfigV02, PluginConfigV03, PluginConfigV04, PluginConfigV05},
     GUIEvent, Vst3Message,
 };
-use crate::soyboy::parameters::{Normalizable, ParameterDef, Parametric, SoyBoyParameter};
+use crate::soyboy::{
+    mpe::{self, MpeDimension, MpeZone},
+    parameters::{Normalizable, ParameterDef, Parametric, SoyBoyParameter},
+};
 use crate::vst3::{gui::SoyBoyVST3GUI, plugin_data, raw_utils, vst3_utils};
 
-#[VST3(implements(IEditController, IUnitInfo, IMidiMapping, IConnectionPoint))]
+#[VST3(implements(
+    IEditController,
+    IUnitInfo,
+    IMidiMapping,
+    INoteExpressionController,
+    IConnectionPoint
+))]
 pub struct SoyBoyController {
     param_defs: HashMap<SoyBoyParameter, ParameterDef>,
     vst3_params: RefCell<HashMap<u32, ParameterInfo>>,
+    // the parameter IDs in order, as the hidden parameters for MIDI learn are not next to
+    // the others
+    param_ids: RefCell<Vec<u32>>,
     param_values: Arc<Mutex<HashMap<u32, f64>>>,
     processor: RefCell<Option<Arc<dyn IConnectionPoint>>>,
     component_handler: RefCell<Option<Arc<dyn IComponentHandler>>>,
     context: RefCell<Option<VstPtr<dyn IUnknown>>>,
     gui_sender: Mutex<Option<Sender<GUIEvent>>>,
+    midi_mappings: Mutex<Vec<(i16, u32)>>,
+    // whether the processor is waiting for a MIDI CC to be learned
+    midi_learning: Mutex<bool>,
+}
+
+/// The note expressions which voices play: type ID, title, short title, units, default value
+/// and flags. Their values are normalized in 0.0..1.0.
+const NOTE_EXPRESSIONS: [(u32, &str, &str, &str, f64, i32); 4] = [
+    (
+        raw_utils::K_VOLUME_TYPE_ID,
+        "Volume",
+        "Vol",
+        "dB",
+        0.25,
+        raw_utils::K_IS_ABSOLUTE,
+    ),
+    (
+        raw_utils::K_PAN_TYPE_ID,
+        "Pan",
+        "Pan",
+        "",
+        0.5,
+        raw_utils::K_IS_BIPOLAR | raw_utils::K_IS_ABSOLUTE,
+    ),
+    (
+        raw_utils::K_TUNING_TYPE_ID,
+        "Tuning",
+        "Tun",
+        "semitone",
+        0.5,
+        raw_utils::K_IS_BIPOLAR,
+    ),
+    (
+        raw_utils::K_BRIGHTNESS_TYPE_ID,
+        "Brightness",
+        "Brt",
+        "%",
+        0.5,
+        raw_utils::K_IS_BIPOLAR | raw_utils::K_IS_ABSOLUTE,
+    ),
+];
+
+fn format_note_expression(type_id: u32, value: f64) -> Option<String> {
+    match type_id {
+        raw_utils::K_VOLUME_TYPE_ID => Some(format!("{:.1}", 20.0 * (value * 4.0).log10())),
+        raw_utils::K_PAN_TYPE_ID => Some(format!("{:.0}", value * 200.0 - 100.0)),
+        raw_utils::K_TUNING_TYPE_ID => Some(format!("{:.2}", (value - 0.5) * 240.0)),
+        raw_utils::K_BRIGHTNESS_TYPE_ID => Some(format!("{:.0}", value * 200.0 - 100.0)),
+        _ => None,
+    }
+}
+
+fn parse_note_expression(type_id: u32, s: &str) -> Option<f64> {
+    let v = s.trim().parse::<f64>().ok()?;
+    let value = match type_id {
+        raw_utils::K_VOLUME_TYPE_ID => 10.0f64.powf(v / 20.0) / 4.0,
+        raw_utils::K_PAN_TYPE_ID => (v + 100.0) / 200.0,
+        raw_utils::K_TUNING_TYPE_ID => v / 240.0 + 0.5,
+        raw_utils::K_BRIGHTNESS_TYPE_ID => (v + 100.0) / 200.0,
+        _ => return None,
+    };
+    Some(value.clamp(0.0, 1.0))
 }
 
 struct Paraminfo<'a> {
@@ -61,6 +136,7 @@ impl SoyBoyController {
 
     unsafe fn add_parameter(&self, id: u32, paraminfo: Paraminfo) {
         let mut vst3_params = self.vst3_params.borrow_mut();
+        let mut param_ids = self.param_ids.borrow_mut();
         let mut param_vals = self.param_values.lock().unwrap();
 
         let mut param = raw_utils::make_empty_param_info();
@@ -74,27 +150,67 @@ impl SoyBoyController {
         param.flags = paraminfo.flags;
 
         (*vst3_params).insert(id, param);
+        if let Err(index) = param_ids.binary_search(&id) {
+            param_ids.insert(index, id);
+        }
         (*param_vals).insert(id, param.default_normalized_value);
     }
 
     pub unsafe fn new(param_defs: HashMap<SoyBoyParameter, ParameterDef>) -> Box<SoyBoyController> {
         let vst3_params = RefCell::new(HashMap::new());
+        let param_ids = RefCell::new(Vec::new());
         let param_vals = Arc::new(Mutex::new(HashMap::new()));
         let processor = RefCell::new(None);
         let component_handler = RefCell::new(None);
         let context = RefCell::new(None);
         let gui_sender = Mutex::new(None);
+        let midi_mappings = Mutex::new(Vec::new());
+        let midi_learning = Mutex::new(false);
 
         SoyBoyController::allocate(
             param_defs,
             vst3_params,
+            param_ids,
             param_vals,
             processor,
             component_handler,
             context,
             gui_sender,
+            midi_mappings,
+            midi_learning,
         )
     }
+
+    /// Updates the learned MIDI CCs and returns whether they are changed, then the host
+    /// should be told to ask the mappings again.
+    fn set_midi_mappings(&self, mappings: &[(i16, u32)]) -> bool {
+        let mut midi_mappings = self.midi_mappings.lock().unwrap();
+        if midi_mappings.as_slice() == mappings {
+            return false;
+        }
+        *midi_mappings = mappings.to_vec();
+        true
+    }
+
+    unsafe fn restart_midi_mappings(&self) {
+        if let Some(handler) = self.component_handler.borrow().clone() {
+            handler.restart_component(RestartFlags::kMidiCCAssignmentChanged as i32);
+        }
+    }
+
+    /// Returns the MPE zone and the number of its member channels.
+    fn mpe_zone(&self) -> (MpeZone, u32) {
+        let param_vals = self.param_values.lock().unwrap();
+        let value = |param: SoyBoyParameter| {
+            let param_def = self.param_defs.get(&param).unwrap();
+            let norm = param_vals.get(&(param as u32)).copied().unwrap_or(0.0);
+            param_def.denormalize(norm)
+        };
+
+        let zone =
+            MpeZone::try_from(value(SoyBoyParameter::MpeZone) as u32).unwrap_or(MpeZone::Off);
+        (zone, value(SoyBoyParameter::MpeChannels) as u32)
+    }
 }
 
 impl IPluginBase for SoyBoyController {
@@ -108,6 +224,17 @@ impl IPluginBase for SoyBoyController {
 
         let param_defs = self.param_defs.clone();
         for (param, param_def) in param_defs.iter() {
+            // pedals and controllers are played only with MIDI
+            let flags = match param {
+                SoyBoyParameter::Sustain
+                | SoyBoyParameter::Sostenuto
+                | SoyBoyParameter::ModWheel
+                | SoyBoyParameter::Aftertouch
+                | SoyBoyParameter::Breath => {
+                    ParameterFlags::kCanAutomate as i32 | ParameterFlags::kIsHidden as i32
+                }
+                _ => ParameterFlags::kCanAutomate as i32,
+            };
             self.add_parameter(
                 *param as u32,
                 Paraminfo {
@@ -116,11 +243,48 @@ impl IPluginBase for SoyBoyController {
                     unit_name: &param_def.unit_name,
                     step_count: param_def.step_count,
                     default_value: param_def.normalize(param_def.default_value),
-                    flags: ParameterFlags::kCanAutomate as i32,
+                    flags,
+                },
+            );
+        }
+
+        for cc in 0..vst3_utils::NUMBER_OF_MIDI_CCS {
+            let title = format!("MIDI CC {}", cc);
+            self.add_parameter(
+                vst3_utils::MIDI_CC_PARAM_ID_BASE + cc,
+                Paraminfo {
+                    title: &title,
+                    short_title: &title,
+                    unit_name: "",
+                    step_count: 0,
+                    default_value: 0.0,
+                    flags: ParameterFlags::kIsHidden as i32,
                 },
             );
         }
 
+        let dimensions = [
+            (MpeDimension::PitchBend, "bend", 0.5),
+            (MpeDimension::Pressure, "pressure", 0.0),
+            (MpeDimension::Timbre, "timbre", 0.5),
+        ];
+        for channel in 0..mpe::NUMBER_OF_CHANNELS as i16 {
+            for (dimension, name, default_value) in dimensions.iter() {
+                let title = format!("MPE ch {} {}", channel + 1, name);
+                self.add_parameter(
+                    vst3_utils::mpe_param_id(channel, *dimension),
+                    Paraminfo {
+                        title: &title,
+                        short_title: &title,
+                        unit_name: "",
+                        step_count: 0,
+                        default_value: *default_value,
+                        flags: ParameterFlags::kIsHidden as i32,
+                    },
+                );
+            }
+        }
+
         kResultOk
     }
 
@@ -136,10 +300,47 @@ impl IMidiMapping for SoyBoyController {
     unsafe fn get_midi_controller_assignment(
         &self,
         _bus_index: i32,
-        _channel: i16,
+        channel: i16,
         midi_cc_number: CtrlNumber,
         param_id: *mut ParamID,
     ) -> tresult {
+        // member channels' controllers are the notes' own ones
+        let (zone, members) = self.mpe_zone();
+        if mpe::is_member_channel(zone, members, channel) {
+            let dimension = match midi_cc_number {
+                // kPitchBend
+                129 => Some(MpeDimension::PitchBend),
+                // kAfterTouch
+                128 => Some(MpeDimension::Pressure),
+                // kCtrlFilterResonance (Brightness), which MPE uses as the timbre
+                74 => Some(MpeDimension::Timbre),
+                _ => None,
+            };
+            if let Some(dimension) = dimension {
+                *param_id = vst3_utils::mpe_param_id(channel, dimension);
+                return kResultTrue;
+            }
+        }
+
+        // while learning, all CCs are watched so that mapped ones can be learned too
+        if *self.midi_learning.lock().unwrap()
+            && (0..vst3_utils::NUMBER_OF_MIDI_CCS as i16).contains(&midi_cc_number)
+        {
+            *param_id = vst3_utils::MIDI_CC_PARAM_ID_BASE + midi_cc_number as u32;
+            return kResultTrue;
+        }
+
+        if let Some((_, id)) = self
+            .midi_mappings
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(cc, _)| *cc == midi_cc_number)
+        {
+            *param_id = *id;
+            return kResultTrue;
+        }
+
         match midi_cc_number {
             // kPitchBend
             // cf.
@@ -149,11 +350,123 @@ impl IMidiMapping for SoyBoyController {
                 *param_id = SoyBoyParameter::PitchBend as u32;
                 kResultTrue
             }
+            // kCtrlModWheel
+            1 => {
+                *param_id = SoyBoyParameter::ModWheel as u32;
+                kResultTrue
+            }
+            // kCtrlBreath
+            2 => {
+                *param_id = SoyBoyParameter::Breath as u32;
+                kResultTrue
+            }
+            // kAfterTouch
+            128 => {
+                *param_id = SoyBoyParameter::Aftertouch as u32;
+                kResultTrue
+            }
+            // kCtrlSustainOnOff
+            64 => {
+                *param_id = SoyBoyParameter::Sustain as u32;
+                kResultTrue
+            }
+            // kCtrlSustenutoOnOff
+            66 => {
+                *param_id = SoyBoyParameter::Sostenuto as u32;
+                kResultTrue
+            }
+            // the other CCs are watched for MIDI learn
+            cc if (0..vst3_utils::NUMBER_OF_MIDI_CCS as i16).contains(&cc) => {
+                *param_id = vst3_utils::MIDI_CC_PARAM_ID_BASE + cc as u32;
+                kResultTrue
+            }
             _ => kResultFalse,
         }
     }
 }
 
+impl INoteExpressionController for SoyBoyController {
+    unsafe fn get_note_expression_count(&self, bus_index: i32, _channel: i16) -> i32 {
+        if bus_index == 0 {
+            NOTE_EXPRESSIONS.len() as i32
+        } else {
+            0
+        }
+    }
+
+    unsafe fn get_note_expression_info(
+        &self,
+        bus_index: i32,
+        _channel: i16,
+        note_expression_index: i32,
+        info: *mut NoteExpressionTypeInfo,
+    ) -> tresult {
+        if bus_index != 0 || info.is_null() {
+            return kInvalidArgument;
+        }
+
+        let (type_id, title, short_title, unit_name, default_value, flags) =
+            match NOTE_EXPRESSIONS.get(note_expression_index as usize) {
+                Some(expression) => *expression,
+                None => return kInvalidArgument,
+            };
+
+        let info = &mut *info;
+        info.type_id = type_id;
+        info.title = [0; 128];
+        info.short_title = [0; 128];
+        info.units = [0; 128];
+        raw_utils::wstrcpy(title, info.title.as_mut_ptr());
+        raw_utils::wstrcpy(short_title, info.short_title.as_mut_ptr());
+        raw_utils::wstrcpy(unit_name, info.units.as_mut_ptr());
+        info.unit_id = kRootUnitId;
+        info.value_desc = NoteExpressionValueDescription {
+            default_value,
+            minimum: 0.0,
+            maximum: 1.0,
+            step_count: 0,
+        };
+        info.associated_parameter_id = 0;
+        info.flags = flags;
+
+        kResultOk
+    }
+
+    unsafe fn get_note_expression_string_by_value(
+        &self,
+        _bus_index: i32,
+        _channel: i16,
+        type_id: u32,
+        value: f64,
+        string: *mut String128,
+    ) -> tresult {
+        match format_note_expression(type_id, value) {
+            Some(s) => {
+                raw_utils::tcharcpy(&s, string as *mut TChar);
+                kResultOk
+            }
+            None => kResultFalse,
+        }
+    }
+
+    unsafe fn get_note_expression_value_by_string(
+        &self,
+        _bus_index: i32,
+        _channel: i16,
+        type_id: u32,
+        string: *const TChar,
+        value: *mut f64,
+    ) -> tresult {
+        match parse_note_expression(type_id, &raw_utils::tchar_to_string(string)) {
+            Some(v) => {
+                *value = v;
+                kResultOk
+            }
+            None => kResultFalse,
+        }
+    }
+}
+
 impl IEditController for SoyBoyController {
     unsafe fn set_component_state(&self, state: SharedVstPtr<dyn IBStream>) -> tresult {
         if state.is_null() {
@@ -178,13 +491,22 @@ impl IEditController for SoyBoyController {
             return kResultFalse;
         }
 
-        let set_config = |config: PluginConfigV02| {
-            let mut param_vals = self.param_values.lock().unwrap();
-            for param in SoyBoyParameter::iter() {
-                let param_def = self.param_defs.get(&param).unwrap();
-                let value = config.get_param(&param);
-                let norm = param_def.normalize(value);
-                param_vals.insert(param as u32, norm);
+        let set_config = |mut config: PluginConfigV05| {
+            config.reset_controllers(&self.param_defs);
+
+            let zone = self.mpe_zone();
+            {
+                let mut param_vals = self.param_values.lock().unwrap();
+                for param in SoyBoyParameter::iter() {
+                    let param_def = self.param_defs.get(&param).unwrap();
+                    let value = config.get_param(&param);
+                    let norm = param_def.normalize(value);
+                    param_vals.insert(param as u32, norm);
+                }
+            }
+            let mappings_changed = self.set_midi_mappings(&config.midi_mappings);
+            if mappings_changed || self.mpe_zone() != zone {
+                self.restart_midi_mappings();
             }
         };
 
@@ -194,6 +516,9 @@ impl IEditController for SoyBoyController {
                 vst3_utils::read_config!(config, state);
 
                 let config = PluginConfigV02::from_v01(config, &self.param_defs);
+                let config = PluginConfigV03::from_v02(config, &self.param_defs);
+                let config = PluginConfigV04::from_v03(config, &self.param_defs);
+                let config = PluginConfigV05::from_v04(config, &self.param_defs);
                 (set_config)(config);
                 kResultTrue
             }
@@ -201,6 +526,33 @@ impl IEditController for SoyBoyController {
                 let mut config: PluginConfigV02 = PluginConfigV02::default();
                 vst3_utils::read_config!(config, state);
 
+                let config = PluginConfigV03::from_v02(config, &self.param_defs);
+                let config = PluginConfigV04::from_v03(config, &self.param_defs);
+                let config = PluginConfigV05::from_v04(config, &self.param_defs);
+                (set_config)(config);
+                kResultTrue
+            }
+            PluginConfigV03::CONFIG_VERSION => {
+                let mut config: PluginConfigV03 = PluginConfigV03::default();
+                vst3_utils::read_config!(config, state);
+
+                let config = PluginConfigV04::from_v03(config, &self.param_defs);
+                let config = PluginConfigV05::from_v04(config, &self.param_defs);
+                (set_config)(config);
+                kResultTrue
+            }
+            PluginConfigV04::CONFIG_VERSION => {
+                let mut config: PluginConfigV04 = PluginConfigV04::default();
+                vst3_utils::read_config!(config, state);
+
+                let config = PluginConfigV05::from_v04(config, &self.param_defs);
+                (set_config)(config);
+                kResultTrue
+            }
+            PluginConfigV05::CONFIG_VERSION => {
+                let mut config: PluginConfigV05 = PluginConfigV05::default();
+                vst3_utils::read_config!(config, state);
+
                 (set_config)(config);
                 kResultTrue
             }
@@ -223,10 +575,11 @@ impl IEditController for SoyBoyController {
         self.vst3_params.borrow().len() as i32
     }
 
-    unsafe fn get_parameter_info(&self, id: i32, vst3_params: *mut ParameterInfo) -> tresult {
-        let id = id as u32;
+    unsafe fn get_parameter_info(&self, index: i32, vst3_params: *mut ParameterInfo) -> tresult {
+        let params = self.vst3_params.borrow();
+        let ids = self.param_ids.borrow();
 
-        if let Some(param) = self.vst3_params.borrow().get(&id) {
+        if let Some(param) = ids.get(index as usize).and_then(|id| params.get(id)) {
             *vst3_params = *param;
 
             kResultOk
@@ -307,6 +660,8 @@ impl IEditController for SoyBoyController {
     }
 
     unsafe fn set_param_normalized(&self, id: u32, value: f64) -> tresult {
+        let zone = self.mpe_zone();
+
         if let Ok(param) = SoyBoyParameter::try_from(id) {
             let param_def = self.param_defs.get(&param).unwrap();
             let denorm = param_def.denormalize(value);
@@ -316,10 +671,17 @@ impl IEditController for SoyBoyController {
             }
         }
 
-        match self.param_values.lock().unwrap().insert(id, value) {
+        let result = match self.param_values.lock().unwrap().insert(id, value) {
             Some(_) => kResultTrue,
             _ => kResultFalse,
+        };
+
+        // the member channels' controllers are mapped to other parameters
+        if self.mpe_zone() != zone {
+            self.restart_midi_mappings();
         }
+
+        result
     }
 
     unsafe fn set_component_handler(
@@ -462,8 +824,44 @@ impl IConnectionPoint for SoyBoyController {
     }
 
     unsafe fn notify(&self, message: SharedVstPtr<dyn IMessage>) -> tresult {
+        let message = Vst3Message::from_message(&message);
+
+        // the processor changes parameters by itself, e.g. when switching drum kit pads
+        if let Some(Vst3Message::ConfigurationData(config)) = &message {
+            let zone = self.mpe_zone();
+            let mut changed = false;
+            {
+                let mut param_vals = self.param_values.lock().unwrap();
+                for param in SoyBoyParameter::iter() {
+                    let param_def = self.param_defs.get(&param).unwrap();
+                    let norm = param_def.normalize(config.get_param(&param));
+                    changed |= param_vals.insert(param as u32, norm) != Some(norm);
+                }
+            }
+
+            // notify() runs on the UI thread, so the host can be told here even without the editor
+            if changed {
+                if let Some(handler) = self.component_handler.borrow().clone() {
+                    handler.restart_component(RestartFlags::kParamValuesChanged as i32);
+                }
+            }
+
+            let mappings_changed = self.set_midi_mappings(&config.midi_mappings);
+            if mappings_changed || self.mpe_zone() != zone {
+                self.restart_midi_mappings();
+            }
+        }
+
+        if let Some(Vst3Message::MidiLearning(learning)) = &message {
+            let mut midi_learning = self.midi_learning.lock().unwrap();
+            if *midi_learning != *learning {
+                *midi_learning = *learning;
+                self.restart_midi_mappings();
+            }
+        }
+
         if let Some(sender) = &*self.gui_sender.lock().unwrap() {
-            match Vst3Message::from_message(&message) {
+            match message {
                 Some(Vst3Message::NoteOn) => {
                     let _ = sender.send(GUIEvent::NoteOn);
                 }
@@ -476,6 +874,9 @@ impl IConnectionPoint for SoyBoyController {
                 Some(Vst3Message::WaveformData(wf)) => {
                     let _ = sender.send(GUIEvent::WaveformData(wf));
                 }
+                Some(Vst3Message::Clipped) => {
+                    let _ = sender.send(GUIEvent::Clipped);
+                }
                 _ => (),
             }
         }
@@ -22,6 +22,7 @@ use crate::vst3::{raw_utils, vst3_utils};
 pub struct VST3EventHandler {
     param_values: Arc<Mutex<HashMap<u32, f64>>>,
     component_handler: Option<Arc<dyn IComponentHandler>>,
+    controller_connection: Arc<Mutex<vst3_utils::ControllerConnection>>,
 }
 
 unsafe impl Send for VST3EventHandler {}
@@ -31,10 +32,12 @@ impl VST3EventHandler {
     fn new(
         param_values: Arc<Mutex<HashMap<u32, f64>>>,
         component_handler: Option<Arc<dyn IComponentHandler>>,
+        controller_connection: Arc<Mutex<vst3_utils::ControllerConnection>>,
     ) -> Self {
         Self {
             param_values,
             component_handler,
+            controller_connection,
         }
     }
 }
@@ -56,6 +59,13 @@ impl EventHandler for VST3EventHandler {
                 .insert(p, value_normalized);
         }
     }
+
+    fn learn_midi_cc(&self, p: SoyBoyParameter) {
+        self.controller_connection
+            .lock()
+            .unwrap()
+            .send_message(Vst3Message::LearnMidiCc(p));
+    }
 }
 
 #[VST3(implements(IPlugView, IPlugViewContentScaleSupport))]
@@ -81,6 +91,7 @@ impl SoyBoyVST3GUI {
         let handler = Arc::new(VST3EventHandler::new(
             param_values.clone(),
             component_handler,
+            controller_connection.clone(),
         ));
         let scale_factor = RefCell::new(1.0);
         let handle = RefCell::new(None);
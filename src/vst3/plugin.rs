@@ -2,6 +2,7 @@ use std::cell::RefCell;
 use std::collections::{HashMap, VecDeque};
 use std::convert::TryFrom;
 use std::mem;
+use std::ops::Range;
 use std::os::raw::c_void;
 use std::ptr::null_mut;
 use std::sync::{Arc, Mutex};
@@ -26,18 +27,140 @@ use vst3_sys::{
 };
 
 use crate::common::{
-    config::{PluginConfigV01, PluginConfigV02},
-    constants, Vst3Message, Waveform,
+    config::{PluginConfigV01, PluginConfigV02, PluginConfigV03, PluginConfigV04, PluginConfigV05},
+    constants, i4, Vst3Message, Waveform,
 };
 use crate::soyboy::{
     event::{Event, Triggered},
-    parameters::{Normalizable, ParameterDef, Parametric, SoyBoyParameter},
+    parameters::{Normalizable, ParameterDef, ParameterType, Parametric, SoyBoyParameter},
     AudioProcessor, SoyBoy,
 };
 use crate::vst3::{
-    controller::SoyBoyController, plugin_data, raw_utils, vst3_utils, vst3_utils::SyncPtr,
+    controller::SoyBoyController,
+    plugin_data, raw_utils, vst3_utils,
+    vst3_utils::{Automation, SyncPtr},
 };
 
+/// The number of events in a process block the buffer is allocated for.
+const MAX_EVENTS_PER_BLOCK: usize = 512;
+/// The interval in samples at which ramping automations update their parameters.
+const AUTOMATION_RAMP_INTERVAL: usize = 32;
+
+/// Inserts an event keeping the events sorted by their sample offsets. The events at the
+/// same offset are kept in their order.
+fn insert_event(events: &mut Vec<(usize, Event)>, offset: usize, event: Event) {
+    let index = events.partition_point(|(o, _)| *o <= offset);
+    events.insert(index, (offset, event));
+}
+
+/// Returns whether automation points of `param` ramp in between. The parameters MIDI
+/// controllers are mapped to step at each message like the MPE ones, so that the pedals
+/// switch at the message's offset.
+fn ramps(param: &SoyBoyParameter, param_def: &ParameterDef) -> bool {
+    let controller = matches!(
+        param,
+        SoyBoyParameter::PitchBend
+            | SoyBoyParameter::ModWheel
+            | SoyBoyParameter::Breath
+            | SoyBoyParameter::Aftertouch
+            | SoyBoyParameter::Sustain
+            | SoyBoyParameter::Sostenuto
+    );
+    let continuous = matches!(
+        param_def.r#type,
+        ParameterType::Linear | ParameterType::NonLinear
+    );
+    continuous && !controller
+}
+
+/// What the processor changed by itself in a process block. The snapshot is allocated
+/// beforehand so that the audio thread only copies values into it.
+struct ConfigChanges {
+    patch: bool,
+    // the parameters and the wavetable after switching pads or parts, indexed by parameter IDs
+    params: Vec<f64>,
+    wavetable: [i4; constants::WAVETABLE_SIZE],
+    // the MIDI CC learned for the parameter, which is bound on the timer thread as binding
+    // it may allocate
+    learned: Option<(i16, SoyBoyParameter)>,
+}
+
+impl ConfigChanges {
+    fn new() -> Self {
+        Self {
+            patch: false,
+            params: vec![0.0; SoyBoyParameter::iter().count()],
+            wavetable: [i4::from(0i8); constants::WAVETABLE_SIZE],
+            learned: None,
+        }
+    }
+
+    fn changed(&self) -> bool {
+        self.patch || self.learned.is_some()
+    }
+
+    /// Takes a snapshot of the switched patch.
+    fn set_patch(&mut self, soyboy: &SoyBoy) {
+        for param in SoyBoyParameter::iter() {
+            self.params[param as usize] = soyboy.get_param(&param);
+        }
+        self.wavetable = soyboy.get_wavetable();
+        self.patch = true;
+    }
+}
+
+/// Tells the controller the configuration the processor changed by itself. The audio thread
+/// only takes a snapshot of the changes, and the timer thread builds the configuration and
+/// sends it not to allocate on the audio thread.
+struct ConfigSync {
+    param_defs: HashMap<SoyBoyParameter, ParameterDef>,
+    changes: Arc<Mutex<ConfigChanges>>,
+    // swapped with the shared changes not to hold their lock while reading them
+    taken: ConfigChanges,
+}
+
+impl ConfigSync {
+    fn new(
+        param_defs: HashMap<SoyBoyParameter, ParameterDef>,
+        changes: Arc<Mutex<ConfigChanges>>,
+    ) -> Self {
+        Self {
+            param_defs,
+            changes,
+            taken: ConfigChanges::new(),
+        }
+    }
+
+    /// Updates the configuration with the changes since the last call, and returns it to
+    /// send if there were any.
+    fn sync(&mut self, config: &Mutex<PluginConfigV05>) -> Option<PluginConfigV05> {
+        {
+            let mut changes = self.changes.lock().unwrap();
+            if !changes.changed() {
+                return None;
+            }
+            mem::swap(&mut *changes, &mut self.taken);
+        }
+
+        let mut config = config.lock().unwrap();
+
+        // switching pads or parts changes all patch's parameters, so tell them to the controller
+        if self.taken.patch {
+            for param in SoyBoyParameter::iter() {
+                let param_def = self.param_defs.get(&param).unwrap();
+                config.set_param(&param, param_def, self.taken.params[param as usize]);
+            }
+            config.set_wavetable(&self.taken.wavetable);
+        }
+        if let Some((cc, param)) = self.taken.learned.take() {
+            config.learn_midi_cc(cc, param);
+        }
+        self.taken.patch = false;
+
+        Some(config.clone())
+    }
+}
+
 pub struct PluginTimerThread {
     handle: RefCell<Option<thread::JoinHandle<()>>>,
     quit: Arc<Mutex<bool>>,
@@ -53,11 +176,12 @@ impl PluginTimerThread {
 
     fn start_thread(
         &mut self,
-        config: Arc<Mutex<PluginConfigV02>>,
+        config: Arc<Mutex<PluginConfigV05>>,
         host_context: Arc<Mutex<SyncPtr<dyn IUnknown>>>,
         controller: Arc<Mutex<SyncPtr<dyn IConnectionPoint>>>,
         waveform: Arc<Mutex<Waveform>>,
         queue: Arc<Mutex<VecDeque<Vst3Message>>>,
+        mut sync: ConfigSync,
     ) {
         let config = config.clone();
         let context = host_context.clone();
@@ -75,12 +199,18 @@ impl PluginTimerThread {
 
             let mut msg_note_on = vst3_utils::allocate_message(&host).unwrap();
             let mut msg_waveform = vst3_utils::allocate_message(&host).unwrap();
+            let mut msg_config = vst3_utils::allocate_message(&host).unwrap();
 
             loop {
                 if *quit.lock().unwrap() {
                     break;
                 }
 
+                if let Some(config) = sync.sync(&config) {
+                    Vst3Message::ConfigurationData(config).write_message(&mut msg_config);
+                    vst3_utils::send_message(connection.clone(), &msg_config);
+                }
+
                 {
                     let mut queue = queue.lock().unwrap();
                     loop {
@@ -126,7 +256,7 @@ impl PluginTimerThread {
 #[VST3(implements(IComponent, IAudioProcessor, IConnectionPoint))]
 pub struct SoyBoyPlugin {
     soyboy: Mutex<SoyBoy>,
-    config: Arc<Mutex<PluginConfigV02>>,
+    config: Arc<Mutex<PluginConfigV05>>,
     param_defs: HashMap<SoyBoyParameter, ParameterDef>,
     audio_out: RefCell<BusInfo>,
     event_in: RefCell<BusInfo>,
@@ -135,6 +265,13 @@ pub struct SoyBoyPlugin {
     waveform: Arc<Mutex<Waveform>>,
     event_queue: Arc<Mutex<VecDeque<Vst3Message>>>,
     timer_thread: RefCell<PluginTimerThread>,
+    // the parameter waiting for a MIDI CC to be learned
+    midi_learn: Mutex<Option<SoyBoyParameter>>,
+    // buffers reused in each process block not to allocate on the audio thread
+    events: Mutex<Vec<(usize, Event)>>,
+    automations: Mutex<Vec<Automation>>,
+    automation_points: Mutex<Vec<(usize, f64)>>,
+    config_changes: Arc<Mutex<ConfigChanges>>,
 }
 
 impl SoyBoyPlugin {
@@ -166,7 +303,7 @@ impl SoyBoyPlugin {
 
     pub unsafe fn new(param_defs: HashMap<SoyBoyParameter, ParameterDef>) -> Box<Self> {
         let soyboy = Mutex::new(SoyBoy::new());
-        let config = Arc::new(Mutex::new(PluginConfigV02::default()));
+        let config = Arc::new(Mutex::new(PluginConfigV05::default()));
         let audio_out = RefCell::new(raw_utils::make_empty_bus_info());
         let event_in = RefCell::new(raw_utils::make_empty_bus_info());
         let context = RefCell::new(None);
@@ -174,6 +311,11 @@ impl SoyBoyPlugin {
         let waveform = Arc::new(Mutex::new(Waveform::new()));
         let event_queue = Arc::new(Mutex::new(VecDeque::new()));
         let timer_thread = RefCell::new(PluginTimerThread::new());
+        let midi_learn = Mutex::new(None);
+        let events = Mutex::new(Vec::new());
+        let automations = Mutex::new(Vec::new());
+        let automation_points = Mutex::new(Vec::new());
+        let config_changes = Arc::new(Mutex::new(ConfigChanges::new()));
 
         SoyBoyPlugin::allocate(
             soyboy,
@@ -186,9 +328,103 @@ impl SoyBoyPlugin {
             waveform,
             event_queue,
             timer_thread,
+            midi_learn,
+            events,
+            automations,
+            automation_points,
+            config_changes,
         )
     }
 
+    /// Sets an automated parameter's normalized value. Returns whether it switches the patch
+    /// which voices play.
+    fn set_automated_param(&self, soyboy: &mut SoyBoy, id: u32, value: f64) -> bool {
+        if let Ok(param) = SoyBoyParameter::try_from(id) {
+            let param_def = self.param_defs.get(&param).unwrap();
+            soyboy.set_param(&param, param_def, param_def.denormalize(value));
+
+            matches!(
+                param,
+                SoyBoyParameter::KitPad | SoyBoyParameter::PartMode | SoyBoyParameter::PartEdit
+            )
+        } else if let Some((channel, dimension)) = vst3_utils::mpe_from_param_id(id) {
+            soyboy.set_expression(channel, dimension, value);
+            false
+        } else {
+            false
+        }
+    }
+
+    /// Steps the ramping automations' parameters to their values at the sample `n`.
+    fn step_ramps(
+        &self,
+        soyboy: &mut SoyBoy,
+        automations: &mut [Automation],
+        points: &[(usize, f64)],
+        n: usize,
+    ) {
+        for automation in automations.iter_mut() {
+            if automation.is_ramping(points) {
+                if let Some(value) = automation.value_at(points, n) {
+                    self.set_automated_param(soyboy, automation.id(), value);
+                }
+            }
+        }
+    }
+
+    /// Renders the samples in the block to the outputs. No automation point nor event is
+    /// in between, and the ramping parameters are stepped in it.
+    unsafe fn render(
+        &self,
+        soyboy: &mut SoyBoy,
+        waveform: &mut Waveform,
+        automations: &mut [Automation],
+        points: &[(usize, f64)],
+        data: &ProcessData,
+        samples: Range<usize>,
+    ) {
+        let start = samples.start;
+        let outputs: &mut AudioBusBuffers = &mut *data.outputs;
+        let num_output_channels = outputs.num_channels as usize;
+
+        let sample_rate = (*(data.context)).sample_rate;
+        let out = outputs.buffers;
+
+        match data.symbolic_sample_size {
+            K_SAMPLE32 => {
+                for n in samples.start as isize..samples.end as isize {
+                    if n as usize > start && (n as usize - start) % AUTOMATION_RAMP_INTERVAL == 0 {
+                        self.step_ramps(soyboy, automations, points, n as usize);
+                    }
+                    let s = soyboy.process(sample_rate);
+                    waveform.set_signal((s.0 + s.1) / 2.0);
+
+                    for i in 0..num_output_channels as isize {
+                        let ch_out = *out.offset(i) as *mut f32;
+                        let v = if i == 1 { s.1 } else { s.0 };
+                        *ch_out.offset(n) = v as f32;
+                    }
+                }
+            }
+            K_SAMPLE64 => {
+                for n in samples.start as isize..samples.end as isize {
+                    if n as usize > start && (n as usize - start) % AUTOMATION_RAMP_INTERVAL == 0 {
+                        self.step_ramps(soyboy, automations, points, n as usize);
+                    }
+                    let s = soyboy.process(sample_rate);
+                    waveform.set_signal((s.0 + s.1) / 2.0);
+
+                    for i in 0..num_output_channels as isize {
+                        let ch_out = *out.offset(i) as *mut f64;
+                        let v = if i == 1 { s.1 } else { s.0 };
+                        *ch_out.offset(n) = v;
+                    }
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
     pub fn bus_count(&self, media_type: MediaTypes, dir: BusDirections) -> i32 {
         match media_type {
             MediaTypes::kAudio => match dir {
@@ -370,8 +606,23 @@ impl IComponent for SoyBoyPlugin {
             return kResultFalse;
         }
 
-        let set_config = |config: PluginConfigV02| {
+        let set_config = |mut config: PluginConfigV05| {
+            config.reset_controllers(&self.param_defs);
+
             let mut soyboy = self.soyboy.lock().unwrap();
+            soyboy.set_kit(&config.kit, &config.kit_wavetables);
+            soyboy.set_parts(&config.parts, &config.part_wavetables);
+
+            // select the pad and the part first so that their parameters go to them
+            for param in [
+                SoyBoyParameter::KitPad,
+                SoyBoyParameter::PartMode,
+                SoyBoyParameter::PartEdit,
+            ] {
+                let param_def = self.param_defs.get(&param).unwrap();
+                soyboy.set_param(&param, param_def, config.get_param(&param));
+            }
+
             for param in SoyBoyParameter::iter() {
                 let param_def = self.param_defs.get(&param).unwrap();
                 let denorm = config.get_param(&param);
@@ -389,12 +640,39 @@ impl IComponent for SoyBoyPlugin {
                 println!("set_state with V01");
 
                 let config = PluginConfigV02::from_v01(config, &self.param_defs);
+                let config = PluginConfigV03::from_v02(config, &self.param_defs);
+                let config = PluginConfigV04::from_v03(config, &self.param_defs);
+                let config = PluginConfigV05::from_v04(config, &self.param_defs);
                 (set_config)(config);
             }
             PluginConfigV02::CONFIG_VERSION => {
                 let mut config = PluginConfigV02::default();
                 vst3_utils::read_config!(config, state);
 
+                let config = PluginConfigV03::from_v02(config, &self.param_defs);
+                let config = PluginConfigV04::from_v03(config, &self.param_defs);
+                let config = PluginConfigV05::from_v04(config, &self.param_defs);
+                (set_config)(config);
+            }
+            PluginConfigV03::CONFIG_VERSION => {
+                let mut config = PluginConfigV03::default();
+                vst3_utils::read_config!(config, state);
+
+                let config = PluginConfigV04::from_v03(config, &self.param_defs);
+                let config = PluginConfigV05::from_v04(config, &self.param_defs);
+                (set_config)(config);
+            }
+            PluginConfigV04::CONFIG_VERSION => {
+                let mut config = PluginConfigV04::default();
+                vst3_utils::read_config!(config, state);
+
+                let config = PluginConfigV05::from_v04(config, &self.param_defs);
+                (set_config)(config);
+            }
+            PluginConfigV05::CONFIG_VERSION => {
+                let mut config = PluginConfigV05::default();
+                vst3_utils::read_config!(config, state);
+
                 (set_config)(config);
             }
             _ => {
@@ -417,11 +695,12 @@ impl IComponent for SoyBoyPlugin {
         }
         let state = state.unwrap();
 
-        vst3_utils::write_config!(
-            PluginConfigV02::CONFIG_VERSION,
-            &*self.config.lock().unwrap(),
-            state
-        );
+        let soyboy = self.soyboy.lock().unwrap();
+        let mut config = self.config.lock().unwrap();
+        config.set_kit(soyboy.get_kit_params(), soyboy.get_kit_wavetables());
+        config.set_parts(soyboy.get_parts_params(), soyboy.get_parts_wavetables());
+
+        vst3_utils::write_config!(PluginConfigV05::CONFIG_VERSION, &*config, state);
         kResultOk
     }
 }
@@ -459,7 +738,25 @@ impl IAudioProcessor for SoyBoyPlugin {
         0
     }
 
-    unsafe fn setup_processing(&self, _setup: *const ProcessSetup) -> tresult {
+    unsafe fn setup_processing(&self, setup: *const ProcessSetup) -> tresult {
+        if !setup.is_null() {
+            let sample_rate = (*setup).sample_rate;
+            self.soyboy.lock().unwrap().set_sample_rate(sample_rate);
+
+            let num_params = SoyBoyParameter::iter().count()
+                + vst3_utils::NUMBER_OF_MIDI_CCS as usize
+                + vst3_utils::NUMBER_OF_MPE_PARAMS as usize;
+            let max_samples = (*setup).max_samples_per_block.max(0) as usize;
+            self.events.lock().unwrap().reserve(MAX_EVENTS_PER_BLOCK);
+            self.automations.lock().unwrap().reserve(num_params);
+            // hosts usually send a point or two per parameter, and a dense automation
+            // sends at most one at each sample
+            self.automation_points
+                .lock()
+                .unwrap()
+                .reserve(num_params * 2 + max_samples);
+        }
+
         if let Some(context) = &*self.context.borrow_mut() {
             if let Some(controller) = &*self.controller.borrow_mut() {
                 self.timer_thread.borrow_mut().start_thread(
@@ -468,6 +765,7 @@ impl IAudioProcessor for SoyBoyPlugin {
                     controller.clone(),
                     self.waveform.clone(),
                     self.event_queue.clone(),
+                    ConfigSync::new(self.param_defs.clone(), self.config_changes.clone()),
                 );
             }
         }
@@ -480,7 +778,7 @@ impl IAudioProcessor for SoyBoyPlugin {
     }
 
     unsafe fn get_tail_samples(&self) -> u32 {
-        0
+        self.soyboy.lock().unwrap().get_tail_samples()
     }
 
     unsafe fn process(&self, data: *mut ProcessData) -> tresult {
@@ -491,7 +789,18 @@ impl IAudioProcessor for SoyBoyPlugin {
             return kResultOk;
         }
 
+        // the last sample offset in this block, where the events out of the block are put
+        let last_offset = (data.num_samples - 1).max(0);
+
+        let mut events = self.events.lock().unwrap();
+        let mut automations = self.automations.lock().unwrap();
+        let mut points = self.automation_points.lock().unwrap();
+        events.clear();
+        automations.clear();
+        points.clear();
+
         // process parameters
+        let mut learned = None;
         if !data.input_param_changes.is_null() {
             let param_changes = data.input_param_changes.upgrade().unwrap();
             let count = param_changes.get_parameter_count();
@@ -501,26 +810,62 @@ impl IAudioProcessor for SoyBoyPlugin {
             for i in 0..count {
                 let param_queue = param_changes.get_parameter_data(i);
                 if let Some(param_queue) = param_queue.upgrade() {
-                    let mut value = 0.0;
-                    let mut sample_offset = 0;
+                    let id = param_queue.get_parameter_id();
                     let num_points = param_queue.get_point_count();
-                    if let Ok(param) = SoyBoyParameter::try_from(param_queue.get_parameter_id()) {
+                    let first = points.len();
+                    for p in 0..num_points {
+                        let mut value = 0.0;
+                        let mut sample_offset = 0;
                         if param_queue.get_point(
-                            num_points - 1,
+                            p,
                             &mut sample_offset as *mut _,
                             &mut value as *mut _,
                         ) == kResultTrue
                         {
-                            let param_def = self.param_defs.get(&param).unwrap();
-                            let denorm = param_def.denormalize(value);
-                            config.set_param(&param, param_def, denorm);
-                            soyboy.set_param(&param, param_def, denorm);
+                            points.push((sample_offset.clamp(0, last_offset) as usize, value));
                         }
                     }
+                    let last = match points[first..].last() {
+                        Some((_, value)) => *value,
+                        None => continue,
+                    };
+                    let range = first..points.len();
+
+                    if let Ok(param) = SoyBoyParameter::try_from(id) {
+                        let param_def = self.param_defs.get(&param).unwrap();
+                        config.set_param(&param, param_def, param_def.denormalize(last));
+
+                        let current = param_def.normalize(soyboy.get_param(&param));
+                        automations.push(Automation::new(
+                            id,
+                            ramps(&param, param_def),
+                            current,
+                            range,
+                        ));
+                    } else if let Some(cc) = vst3_utils::midi_cc_from_param_id(id) {
+                        if let Some(param) = self.midi_learn.lock().unwrap().take() {
+                            learned = Some((cc, param));
+                            self.event_queue
+                                .lock()
+                                .unwrap()
+                                .push_back(Vst3Message::MidiLearning(false));
+                        }
+                    } else if vst3_utils::mpe_from_param_id(id).is_some() {
+                        // MPE controllers step at each MIDI message
+                        automations.push(Automation::new(id, false, 0.0, range));
+                    }
                 }
             }
         }
 
+        // process tempo
+        if !data.context.is_null() {
+            let context = &*data.context;
+            if context.state & raw_utils::K_TEMPO_VALID != 0 {
+                soyboy.trigger(&Event::Tempo { bpm: context.tempo });
+            }
+        }
+
         // process event inputs
         if !data.input_events.is_null() {
             let input_events = data.input_events.upgrade().unwrap();
@@ -530,21 +875,60 @@ impl IAudioProcessor for SoyBoyPlugin {
                 let mut e = raw_utils::make_empty_event();
 
                 if input_events.get_event(c, &mut e) == kResultOk {
+                    let offset = e.sample_offset.clamp(0, last_offset) as usize;
                     match raw_utils::as_event_type(e.type_) {
                         Some(EventTypes::kNoteOnEvent) => {
                             self.event_queue
                                 .lock()
                                 .unwrap()
                                 .push_back(Vst3Message::NoteOn);
-                            soyboy.trigger(&Event::NoteOn {
-                                note: e.event.note_on.pitch as u16,
-                                velocity: e.event.note_on.velocity as f64,
-                            });
+                            insert_event(
+                                &mut events,
+                                offset,
+                                Event::NoteOn {
+                                    note: e.event.note_on.pitch as u16,
+                                    velocity: e.event.note_on.velocity as f64,
+                                    channel: e.event.note_on.channel,
+                                    note_id: e.event.note_on.note_id,
+                                },
+                            );
                         }
                         Some(EventTypes::kNoteOffEvent) => {
-                            soyboy.trigger(&Event::NoteOff {
-                                note: e.event.note_off.pitch as u16,
-                            });
+                            insert_event(
+                                &mut events,
+                                offset,
+                                Event::NoteOff {
+                                    note: e.event.note_off.pitch as u16,
+                                    channel: e.event.note_off.channel,
+                                    note_id: e.event.note_off.note_id,
+                                },
+                            );
+                        }
+                        Some(EventTypes::kPolyPressureEvent) => {
+                            insert_event(
+                                &mut events,
+                                offset,
+                                Event::PolyPressure {
+                                    note: e.event.poly_pressure.pitch as u16,
+                                    channel: e.event.poly_pressure.channel,
+                                    note_id: e.event.poly_pressure.note_id,
+                                    pressure: e.event.poly_pressure.pressure as f64,
+                                },
+                            );
+                        }
+                        Some(EventTypes::kNoteExpressionValueEvent) => {
+                            let value = e.event.note_expression_value;
+                            if let Some(expression) = raw_utils::as_note_expression(value.type_id) {
+                                insert_event(
+                                    &mut events,
+                                    offset,
+                                    Event::NoteExpression {
+                                        note_id: value.note_id,
+                                        expression,
+                                        value: value.value,
+                                    },
+                                );
+                            }
                         }
                         Some(_) => (),
                         _ => (),
@@ -553,40 +937,64 @@ impl IAudioProcessor for SoyBoyPlugin {
             }
         }
 
-        // process audio outputs
+        // render the block in sub-blocks split at the events' and the automation points'
+        // sample offsets so that they take effect at the exact samples
         let num_samples = data.num_samples as usize;
-        let outputs: &mut AudioBusBuffers = &mut *data.outputs;
-        let num_output_channels = outputs.num_channels as usize;
-
-        let sample_rate = (*(data.context)).sample_rate;
-        let out = (*(*data).outputs).buffers;
-
         let mut waveform = self.waveform.lock().unwrap();
+        let mut next_event = 0;
+        let mut patch_changed = false;
+        let mut start = 0;
+
+        loop {
+            // parameters come first, e.g. MPE controllers sent with a note-on
+            for automation in automations.iter_mut() {
+                if let Some(value) = automation.value_at(&points, start) {
+                    patch_changed |= self.set_automated_param(&mut soyboy, automation.id(), value);
+                }
+            }
+            while let Some((_, event)) = events
+                .get(next_event)
+                .filter(|(offset, _)| *offset <= start)
+            {
+                soyboy.trigger(event);
+                next_event += 1;
+            }
 
-        match data.symbolic_sample_size {
-            K_SAMPLE32 => {
-                for n in 0..num_samples as isize {
-                    let s = soyboy.process(sample_rate);
-                    waveform.set_signal(s.0);
+            let end = automations
+                .iter()
+                .filter_map(|automation| automation.next_change(&points))
+                .chain(events.get(next_event).map(|(offset, _)| *offset))
+                .fold(num_samples, usize::min);
+            self.render(
+                &mut soyboy,
+                &mut waveform,
+                &mut automations,
+                &points,
+                data,
+                start..end,
+            );
+            start = end;
+
+            if start >= num_samples {
+                break;
+            }
+        }
 
-                    for i in 0..num_output_channels as isize {
-                        let ch_out = *out.offset(i) as *mut f32;
-                        *ch_out.offset(n) = s.0 as f32;
-                    }
-                }
+        if patch_changed || learned.is_some() {
+            let mut changes = self.config_changes.lock().unwrap();
+            if patch_changed {
+                changes.set_patch(&soyboy);
             }
-            K_SAMPLE64 => {
-                for n in 0..num_samples as isize {
-                    let s = soyboy.process(sample_rate);
-                    waveform.set_signal(s.0);
+            if learned.is_some() {
+                changes.learned = learned;
+            }
+        }
 
-                    for i in 0..num_output_channels as isize {
-                        let ch_out = *out.offset(i) as *mut f64;
-                        *ch_out.offset(n) = s.0;
-                    }
-                }
+        if soyboy.take_clipped() {
+            let mut queue = self.event_queue.lock().unwrap();
+            if !queue.iter().any(|msg| matches!(msg, Vst3Message::Clipped)) {
+                queue.push_back(Vst3Message::Clipped);
             }
-            _ => unreachable!(),
         }
 
         kResultOk
@@ -654,6 +1062,28 @@ impl IConnectionPoint for SoyBoyPlugin {
                 let table = soyboy.get_wavetable();
                 self.send_message(Vst3Message::WaveTableData(table));
             }
+            Some(Vst3Message::LearnMidiCc(param)) => {
+                let cancelled = {
+                    let mut learning = self.midi_learn.lock().unwrap();
+                    if *learning == Some(param) {
+                        *learning = None;
+                        true
+                    } else {
+                        *learning = Some(param);
+                        false
+                    }
+                };
+
+                // the controller maps all CCs to be learned while learning
+                self.send_message(Vst3Message::MidiLearning(!cancelled));
+
+                // learning the same parameter twice forgets its CC
+                if cancelled {
+                    let mut config = self.config.lock().unwrap();
+                    config.forget_midi_cc(param);
+                    self.send_message(Vst3Message::ConfigurationData(config.clone()));
+                }
+            }
             Some(Vst3Message::EnableWaveform) => {
                 (*self.config.lock().unwrap()).waveform_view_enabled = true;
             }
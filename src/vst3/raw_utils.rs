@@ -5,6 +5,8 @@ use std::{
 };
 use widestring::U16CString;
 
+use crate::soyboy::event::NoteExpression;
+
 use vst3_sys::{
     base::FIDString,
     vst::{
@@ -83,15 +85,42 @@ pub fn as_bus_dir(n: i32) -> Option<BusDirections> {
 
 const K_NOTE_ON_EVENT: u16 = EventTypes::kNoteOnEvent as u16;
 const K_NOTE_OFF_EVENT: u16 = EventTypes::kNoteOffEvent as u16;
+const K_POLY_PRESSURE_EVENT: u16 = EventTypes::kPolyPressureEvent as u16;
+const K_NOTE_EXPRESSION_VALUE_EVENT: u16 = EventTypes::kNoteExpressionValueEvent as u16;
 
 pub fn as_event_type(n: u16) -> Option<EventTypes> {
     match n {
         K_NOTE_ON_EVENT => Some(EventTypes::kNoteOnEvent),
         K_NOTE_OFF_EVENT => Some(EventTypes::kNoteOffEvent),
+        K_POLY_PRESSURE_EVENT => Some(EventTypes::kPolyPressureEvent),
+        K_NOTE_EXPRESSION_VALUE_EVENT => Some(EventTypes::kNoteExpressionValueEvent),
+        _ => None,
+    }
+}
+
+// NoteExpressionTypeIDs and NoteExpressionTypeInfo::NoteExpressionTypeFlags
+// cf. https://steinbergmedia.github.io/vst3_doc/vstinterfaces/namespaceSteinberg_1_1Vst.html
+pub const K_VOLUME_TYPE_ID: u32 = 0;
+pub const K_PAN_TYPE_ID: u32 = 1;
+pub const K_TUNING_TYPE_ID: u32 = 2;
+pub const K_BRIGHTNESS_TYPE_ID: u32 = 5;
+pub const K_IS_BIPOLAR: i32 = 1 << 0;
+pub const K_IS_ABSOLUTE: i32 = 1 << 2;
+
+pub fn as_note_expression(type_id: u32) -> Option<NoteExpression> {
+    match type_id {
+        K_VOLUME_TYPE_ID => Some(NoteExpression::Volume),
+        K_PAN_TYPE_ID => Some(NoteExpression::Pan),
+        K_TUNING_TYPE_ID => Some(NoteExpression::Tuning),
+        K_BRIGHTNESS_TYPE_ID => Some(NoteExpression::Brightness),
         _ => None,
     }
 }
 
+// ProcessContext::StatesAndFlags::kTempoValid
+// cf. https://steinbergmedia.github.io/vst3_doc/vstinterfaces/structSteinberg_1_1Vst_1_1ProcessContext.html
+pub const K_TEMPO_VALID: u32 = 1 << 10;
+
 pub fn make_empty_event() -> Event {
     let bytes = [0];
     Event {
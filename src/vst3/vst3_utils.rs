@@ -1,4 +1,6 @@
+use std::convert::TryFrom;
 use std::ffi::CString;
+use std::ops::Range;
 use std::os::raw::c_void;
 use std::ptr::null_mut;
 use std::sync::{Arc, Mutex};
@@ -14,7 +16,116 @@ use vst3_sys::{
 };
 
 use super::raw_utils::fidstring_to_string;
-use crate::common::{constants, i4, PluginConfigV02, Vst3Message, Waveform};
+use crate::common::{constants, i4, PluginConfigV05, Vst3Message, Waveform};
+use crate::soyboy::{
+    mpe::{MpeDimension, NUMBER_OF_CHANNELS, NUMBER_OF_DIMENSIONS},
+    parameters::SoyBoyParameter,
+};
+
+/// MIDI CCs not mapped to any parameters are mapped to hidden parameters from this ID
+/// so that the processor can learn which CC is moved.
+pub const MIDI_CC_PARAM_ID_BASE: u32 = 0x1000;
+pub const NUMBER_OF_MIDI_CCS: u32 = 128;
+
+/// Returns the MIDI CC number if `id` is one of the hidden parameters for MIDI learn.
+pub fn midi_cc_from_param_id(id: u32) -> Option<i16> {
+    if (MIDI_CC_PARAM_ID_BASE..MIDI_CC_PARAM_ID_BASE + NUMBER_OF_MIDI_CCS).contains(&id) {
+        Some((id - MIDI_CC_PARAM_ID_BASE) as i16)
+    } else {
+        None
+    }
+}
+
+/// MPE member channels' pitch bend, pressure and timbre are mapped to hidden parameters
+/// from this ID, which are not automated but played like notes.
+pub const MPE_PARAM_ID_BASE: u32 = 0x2000;
+pub const NUMBER_OF_MPE_PARAMS: u32 = NUMBER_OF_CHANNELS as u32 * NUMBER_OF_DIMENSIONS;
+
+/// Returns the hidden parameter's ID for the MIDI `channel`'s controller.
+pub fn mpe_param_id(channel: i16, dimension: MpeDimension) -> u32 {
+    MPE_PARAM_ID_BASE + channel as u32 * NUMBER_OF_DIMENSIONS + dimension as u32
+}
+
+/// Returns the MIDI channel and the controller if `id` is one of the hidden parameters for MPE.
+pub fn mpe_from_param_id(id: u32) -> Option<(i16, MpeDimension)> {
+    if (MPE_PARAM_ID_BASE..MPE_PARAM_ID_BASE + NUMBER_OF_MPE_PARAMS).contains(&id) {
+        let offset = id - MPE_PARAM_ID_BASE;
+        MpeDimension::try_from(offset % NUMBER_OF_DIMENSIONS)
+            .ok()
+            .map(|dimension| ((offset / NUMBER_OF_DIMENSIONS) as i16, dimension))
+    } else {
+        None
+    }
+}
+
+/// A parameter's automation points in a process block, which are normalized values at
+/// sample offsets. Continuous parameters ramp linearly from the point before, and the
+/// others step at the points.
+///
+/// The points are kept in a buffer shared by all parameters in the block, and an
+/// automation holds its range in it so that no memory is allocated on the audio thread.
+pub struct Automation {
+    id: u32,
+    ramp: bool,
+    // the last point passed, which is the value at the block's start at first
+    from: (usize, f64),
+    points: Range<usize>,
+}
+
+impl Automation {
+    pub fn new(id: u32, ramp: bool, current: f64, points: Range<usize>) -> Self {
+        Self {
+            id,
+            ramp,
+            from: (0, current),
+            points,
+        }
+    }
+
+    fn next_point(&self, points: &[(usize, f64)]) -> Option<(usize, f64)> {
+        if self.points.is_empty() {
+            None
+        } else {
+            points.get(self.points.start).copied()
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the value at the sample `n` if it changes there. `n` must not go back.
+    pub fn value_at(&mut self, points: &[(usize, f64)], n: usize) -> Option<f64> {
+        let mut changed = None;
+        while let Some((offset, value)) = self.next_point(points) {
+            if offset > n {
+                break;
+            }
+            self.from = (offset, value);
+            self.points.start += 1;
+            changed = Some(value);
+        }
+
+        match self.next_point(points) {
+            Some((offset, value)) if self.ramp => {
+                let (from_offset, from_value) = self.from;
+                let t = (n - from_offset) as f64 / (offset - from_offset) as f64;
+                Some(from_value + (value - from_value) * t)
+            }
+            _ => changed,
+        }
+    }
+
+    /// Returns whether the value is ramping to the next point.
+    pub fn is_ramping(&self, points: &[(usize, f64)]) -> bool {
+        self.ramp && self.next_point(points).is_some()
+    }
+
+    /// Returns the sample of the next point, where the value steps or a ramp turns.
+    pub fn next_change(&self, points: &[(usize, f64)]) -> Option<usize> {
+        self.next_point(points).map(|(offset, _)| offset)
+    }
+}
 
 pub struct SyncPtr<I: ComInterface + ?Sized> {
     ptr: VstPtr<I>,
@@ -147,10 +258,10 @@ impl Vst3Message {
                 let decoded = options.deserialize(&bytes_src[..]);
                 if decoded.is_err() {
                     log::error!("cannot decode configuration data");
-                    return Some(Vst3Message::ConfigurationData(PluginConfigV02::default()));
+                    return Some(Vst3Message::ConfigurationData(PluginConfigV05::default()));
                 }
 
-                let config: PluginConfigV02 = decoded.unwrap();
+                let config: PluginConfigV05 = decoded.unwrap();
                 Some(Vst3Message::ConfigurationData(config))
             }
             "vst3:set-wavetable-sample" => {
@@ -171,6 +282,34 @@ impl Vst3Message {
 
                 Some(Vst3Message::SetWaveTable(idx as usize, i4::from(val as i8)))
             }
+            "vst3:learn-midi-cc" => {
+                let attr = unsafe { msg.get_attributes() };
+                let id_param = CString::new("param").unwrap();
+                let mut param: i64 = 0;
+
+                unsafe {
+                    attr.upgrade()
+                        .unwrap()
+                        .get_int(id_param.as_ptr(), &mut param as *mut _);
+                };
+
+                SoyBoyParameter::try_from(param as u32)
+                    .ok()
+                    .map(Vst3Message::LearnMidiCc)
+            }
+            "vst3:midi-learning" => {
+                let attr = unsafe { msg.get_attributes() };
+                let id_learning = CString::new("learning").unwrap();
+                let mut learning: i64 = 0;
+
+                unsafe {
+                    attr.upgrade()
+                        .unwrap()
+                        .get_int(id_learning.as_ptr(), &mut learning as *mut _);
+                };
+
+                Some(Vst3Message::MidiLearning(learning != 0))
+            }
             "vst3:waveform-data" => {
                 let attr = unsafe { msg.get_attributes() };
                 let attr_id = CString::new("signals").unwrap();
@@ -202,6 +341,7 @@ impl Vst3Message {
             }
             "vst3:enable-waveform" => Some(Vst3Message::EnableWaveform),
             "vst3:disable-waveform" => Some(Vst3Message::DisableWaveform),
+            "vst3:clipped" => Some(Vst3Message::Clipped),
             _ => None,
         }
     }
@@ -286,6 +426,30 @@ impl Vst3Message {
                     attr.upgrade().unwrap().set_int(id_val.as_ptr(), val);
                 };
             }
+            Vst3Message::LearnMidiCc(param) => {
+                unsafe { msg.set_message_id(self.to_cstring().as_ptr()) };
+
+                let attr = unsafe { msg.get_attributes() };
+                let id_param = CString::new("param").unwrap();
+
+                unsafe {
+                    attr.upgrade()
+                        .unwrap()
+                        .set_int(id_param.as_ptr(), *param as i64);
+                };
+            }
+            Vst3Message::MidiLearning(learning) => {
+                unsafe { msg.set_message_id(self.to_cstring().as_ptr()) };
+
+                let attr = unsafe { msg.get_attributes() };
+                let id_learning = CString::new("learning").unwrap();
+
+                unsafe {
+                    attr.upgrade()
+                        .unwrap()
+                        .set_int(id_learning.as_ptr(), *learning as i64);
+                };
+            }
             Vst3Message::WaveformData(wf) => {
                 unsafe { msg.set_message_id(self.to_cstring().as_ptr()) };
 
@@ -309,6 +473,9 @@ impl Vst3Message {
             Vst3Message::DisableWaveform => {
                 unsafe { msg.set_message_id(self.to_cstring().as_ptr()) };
             }
+            Vst3Message::Clipped => {
+                unsafe { msg.set_message_id(self.to_cstring().as_ptr()) };
+            }
         }
     }
 }
@@ -368,12 +535,25 @@ macro_rules! read_config {
             .reject_trailing_bytes()
             .with_little_endian()
             .with_fixint_encoding();
-        let size = options.serialized_size(&$config).unwrap();
-        let mut bytes: Vec<u8> = vec![0; size as usize];
-
-        let result = $state.read(bytes.as_mut_ptr() as *mut c_void, size as i32, null_mut());
+        // configs may have variable length fields, so read the stream until its end
+        let size = options.serialized_size(&$config).unwrap_or(0);
+        let mut bytes: Vec<u8> = Vec::with_capacity(size as usize);
+        let mut chunk: [u8; 4096] = [0; 4096];
+        loop {
+            let mut num_read: i32 = 0;
+            let result = $state.read(
+                chunk.as_mut_ptr() as *mut c_void,
+                chunk.len() as i32,
+                &mut num_read as *mut _,
+            );
+
+            if result != kResultOk || num_read <= 0 {
+                break;
+            }
+            bytes.extend_from_slice(&chunk[..num_read as usize]);
+        }
 
-        if result != kResultOk {
+        if bytes.is_empty() {
             log::error!("read_config!: cannot read PluginConfig");
             return kResultFalse;
         }
@@ -428,3 +608,51 @@ macro_rules! write_config {
 
 pub(crate) use read_config;
 pub(crate) use write_config;
+
+#[cfg(test)]
+mod tests {
+    use super::Automation;
+
+    #[test]
+    fn test_automation_ramps_between_points() {
+        let points = [(0, 0.0), (4, 1.0), (8, 0.0)];
+        let mut automation = Automation::new(0, true, 0.5, 0..points.len());
+
+        assert_eq!(Some(0.0), automation.value_at(&points, 0));
+        assert_eq!(Some(4), automation.next_change(&points));
+        assert_eq!(Some(0.5), automation.value_at(&points, 2));
+        assert_eq!(Some(1.0), automation.value_at(&points, 4));
+        assert_eq!(Some(8), automation.next_change(&points));
+        assert_eq!(Some(0.75), automation.value_at(&points, 5));
+    }
+
+    #[test]
+    fn test_automation_holds_last_point() {
+        let points = [(2, 0.25), (6, 0.75)];
+        let mut automation = Automation::new(0, true, 0.0, 0..points.len());
+
+        assert_eq!(Some(0.125), automation.value_at(&points, 1));
+        assert_eq!(Some(0.75), automation.value_at(&points, 6));
+        assert!(!automation.is_ramping(&points));
+        assert_eq!(None, automation.next_change(&points));
+        assert_eq!(None, automation.value_at(&points, 7));
+    }
+
+    #[test]
+    fn test_automation_with_single_point() {
+        let points = [(3, 1.0)];
+        let mut ramp = Automation::new(0, true, 0.0, 0..points.len());
+        let mut step = Automation::new(0, false, 0.0, 0..points.len());
+
+        assert_eq!(Some(3), ramp.next_change(&points));
+        assert_eq!(Some(0.0), ramp.value_at(&points, 0));
+        assert_eq!(Some(1.0 / 3.0), ramp.value_at(&points, 1));
+        assert_eq!(Some(1.0), ramp.value_at(&points, 3));
+        assert_eq!(None, ramp.next_change(&points));
+
+        assert_eq!(Some(3), step.next_change(&points));
+        assert_eq!(None, step.value_at(&points, 1));
+        assert_eq!(Some(1.0), step.value_at(&points, 3));
+        assert_eq!(None, step.next_change(&points));
+    }
+}